@@ -0,0 +1,57 @@
+use crate::{
+    encoding::{
+        varint::{decode_varint, encode_varint, varint_len},
+        BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
+    },
+    types::EncodingVersion,
+};
+
+use super::{AnyBlock, DataBlockId};
+
+/// The block for the header of an item bundle
+///
+/// The value is the number of items following the header. Each item is made of its blocks terminated by an end block.
+/// This block is not part of the wynntils format.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BundleData(pub u32);
+
+impl BlockId for BundleData {
+    fn block_id(&self) -> DataBlockId {
+        DataBlockId::BundleData
+    }
+}
+
+impl DataEncoder for BundleData {
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.append(&mut encode_varint(self.0));
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(varint_len(self.0))
+    }
+}
+
+impl DataDecoder for BundleData {
+    fn decode_data(
+        bytes: &mut impl Iterator<Item = u8>,
+        _ver: EncodingVersion,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let count = decode_varint(bytes)?;
+
+        Ok(Self(
+            u32::try_from(count).map_err(|_| DecodeError::BadBundleCount(count))?,
+        ))
+    }
+}
+
+impl From<BundleData> for AnyBlock {
+    fn from(data: BundleData) -> Self {
+        AnyBlock::BundleData(data)
+    }
+}
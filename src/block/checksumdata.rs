@@ -0,0 +1,171 @@
+use crate::{
+    encoding::{BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError},
+    types::EncodingVersion,
+};
+
+use super::{AnyBlock, DataBlockId};
+
+#[cfg(feature = "checksum")]
+use crate::encoding::{DecoderError, EncoderError};
+
+#[cfg(feature = "checksum")]
+use super::EndData;
+
+/// The block for the integrity checksum of an idstring
+///
+/// This block is not part of the wynntils format. It holds the CRC-32 of all the bytes of the idstring before this block,
+/// starting from the start block. In a [bundle](crate::item::encode_bundle) this includes the bundle header and the items before it.
+/// With the `checksum` feature the checksum is verified while decoding and a mismatch is reported as [`DecodeError::ChecksumMismatch`],
+/// without it the block is decoded but not verified.
+///
+/// When decoding without a start block using [`AnyBlock::decode_all`], the checksum is verified against the bytes read by that call.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChecksumData(pub u32);
+
+impl ChecksumData {
+    /// Calculate the checksum of the given bytes
+    pub fn of(bytes: &[u8]) -> Self {
+        Self(crc32(bytes))
+    }
+}
+
+impl BlockId for ChecksumData {
+    fn block_id(&self) -> DataBlockId {
+        DataBlockId::ChecksumData
+    }
+}
+
+impl DataEncoder for ChecksumData {
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.extend(self.0.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(size_of::<u32>())
+    }
+}
+
+impl DataDecoder for ChecksumData {
+    fn decode_data(
+        bytes: &mut impl Iterator<Item = u8>,
+        _ver: EncodingVersion,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let mut value = [0; 4];
+        for b in &mut value {
+            *b = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        }
+
+        Ok(Self(u32::from_be_bytes(value)))
+    }
+}
+
+impl From<ChecksumData> for AnyBlock {
+    fn from(data: ChecksumData) -> Self {
+        AnyBlock::ChecksumData(data)
+    }
+}
+
+/// Encode a list of blocks into idstring bytes with a checksum block
+///
+/// The blocks are encoded like [`encode_blocks`](super::encode_blocks) and a [`ChecksumData`] block covering the encoded bytes is inserted before the final end block.
+/// If the list does not end in an end block the checksum and an end block are appended.
+///
+/// The list should start with a start block as the checksum is verified against the full idstring.
+#[cfg(feature = "checksum")]
+pub fn encode_blocks_checksummed(
+    ver: EncodingVersion,
+    blocks: &[AnyBlock],
+) -> Result<Vec<u8>, EncoderError> {
+    let blocks = match blocks {
+        [rest @ .., AnyBlock::EndData(_)] => rest,
+        _ => blocks,
+    };
+
+    let mut bytes = super::encode_blocks(ver, blocks)?;
+
+    ChecksumData::of(&bytes).encode(ver, &mut bytes)?;
+    EndData.encode(ver, &mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Byte iterator which keeps a running checksum of the bytes read for verifying checksum blocks
+///
+/// The checksum covers the bytes read since the reader was created, so it should be created where the idstring starts.
+pub(crate) struct ChecksumReader<'a, I: Iterator<Item = u8>> {
+    inner: &'a mut I,
+    crc: u32,
+}
+
+impl<'a, I: Iterator<Item = u8>> ChecksumReader<'a, I> {
+    /// Create a reader starting the checksum at the current position of the byte stream
+    pub(crate) fn new(inner: &'a mut I) -> Self {
+        Self { inner, crc: !0 }
+    }
+
+    /// Checksum of the bytes read so far
+    #[cfg(feature = "checksum")]
+    pub(crate) fn checksum(&self) -> ChecksumData {
+        ChecksumData(!self.crc)
+    }
+}
+
+impl<I: Iterator<Item = u8>> ChecksumReader<'_, std::iter::Peekable<I>> {
+    /// See [`Peekable::next_if_eq`](std::iter::Peekable::next_if_eq)
+    pub(crate) fn next_if_eq(&mut self, expected: &u8) -> Option<u8> {
+        let b = self.inner.next_if_eq(expected)?;
+        self.crc = crc32_update(self.crc, b);
+        Some(b)
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for ChecksumReader<'_, I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let b = self.inner.next()?;
+        self.crc = crc32_update(self.crc, b);
+        Some(b)
+    }
+}
+
+/// Verify a checksum block against the checksum found for the bytes before it
+#[cfg(feature = "checksum")]
+pub(crate) fn verify(checksum: ChecksumData, found: ChecksumData) -> Result<(), DecoderError> {
+    if found == checksum {
+        Ok(())
+    } else {
+        Err(DecoderError {
+            error: DecodeError::ChecksumMismatch {
+                expected: checksum.0,
+                found: found.0,
+            },
+            during: Some(DataBlockId::ChecksumData),
+        })
+    }
+}
+
+/// CRC-32 (IEEE) of the given bytes
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, b| crc32_update(crc, *b))
+}
+
+/// Update a running CRC-32 (IEEE) with a single byte, the running value starts at `!0` and is inverted at the end
+fn crc32_update(mut crc: u32, b: u8) -> u32 {
+    crc ^= b as u32;
+    for _ in 0..8 {
+        crc = if crc & 1 == 1 {
+            (crc >> 1) ^ 0xEDB8_8320
+        } else {
+            crc >> 1
+        };
+    }
+
+    crc
+}
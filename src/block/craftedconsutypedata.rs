@@ -17,32 +17,27 @@ impl BlockId for CraftedConsumableTypeData {
 }
 
 impl DataEncoder for CraftedConsumableTypeData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                out.push(self.0.into());
-                Ok(())
-            }
-        }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0.into());
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1)
     }
 }
 
 impl DataDecoder for CraftedConsumableTypeData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let kind = ConsumableType::try_from(
-                    bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?,
-                )?;
-                Ok(Self(kind))
-            }
-        }
+        let kind =
+            ConsumableType::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
+        Ok(Self(kind))
     }
 }
 
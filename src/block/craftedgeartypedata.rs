@@ -17,31 +17,27 @@ impl BlockId for CraftedGearTypeData {
 }
 
 impl DataEncoder for CraftedGearTypeData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                out.push(self.0.into());
-                Ok(())
-            }
-        }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0.into());
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1)
     }
 }
 
 impl DataDecoder for CraftedGearTypeData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let gear = CraftedGearType::try_from(id)?;
-                Ok(Self(gear))
-            }
-        }
+        let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let gear = CraftedGearType::try_from(id)?;
+        Ok(Self(gear))
     }
 }
 
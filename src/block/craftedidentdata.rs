@@ -1,6 +1,6 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
     types::{CraftedStat, EncodingVersion},
@@ -22,55 +22,57 @@ impl BlockId for CraftedIdentificationData {
 }
 
 impl DataEncoder for CraftedIdentificationData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let ident_len = u8::try_from(self.idents.len())
-                    .map_err(|_| EncodeError::TooManyIdentifications)?;
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        let ident_len =
+            u8::try_from(self.idents.len()).map_err(|_| EncodeError::TooManyIdentifications)?;
 
-                // number of idents
-                out.push(ident_len);
+        // number of idents
+        out.push(ident_len);
 
-                for ident in &self.idents {
-                    // ident id
-                    out.push(ident.kind);
+        for ident in &self.idents {
+            // ident id
+            out.push(ident.kind);
 
-                    // ident value
-                    out.append(&mut encode_varint(ident.max));
-                }
-
-                Ok(())
-            }
+            // ident value
+            out.append(&mut encode_varint(ident.max));
         }
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        u8::try_from(self.idents.len()).map_err(|_| EncodeError::TooManyIdentifications)?;
+
+        Ok(1 + self
+            .idents
+            .iter()
+            .map(|ident| 1 + varint_len(ident.max))
+            .sum::<usize>())
     }
 }
 
 impl DataDecoder for CraftedIdentificationData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // ident count
-                let count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let mut idents = Vec::with_capacity(count as usize);
-
-                for _ in 0..count {
-                    // type of ident
-                    let kind = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                    // value of ident
-                    let max = decode_varint(bytes)? as i32;
+        // ident count
+        let count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let mut idents = Vec::with_capacity(count as usize);
 
-                    idents.push(CraftedStat { kind, max });
-                }
+        for _ in 0..count {
+            // type of ident
+            let kind = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+            // value of ident
+            let max = decode_varint(bytes)? as i32;
 
-                Ok(Self { idents })
-            }
+            idents.push(CraftedStat { kind, max });
         }
+
+        Ok(Self { idents })
     }
 }
 
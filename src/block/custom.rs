@@ -0,0 +1,301 @@
+use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
+
+use thiserror::Error;
+
+use crate::{
+    encoding::{
+        ascii::decode_any, DecodeError, DecoderError, EncodeError, EncodedLen, EncoderError,
+    },
+    types::EncodingVersion,
+};
+
+use super::{
+    framed::{frame_len, is_framed, write_frame},
+    AnyBlock, DataBlockId,
+};
+
+/// Block ids from this id upwards are reserved for the extension blocks of this crate and can not be registered
+pub const RESERVED_IDS_START: u8 = 240;
+
+/// Data of a custom block
+///
+/// Custom data is only encoded as part of a [`CustomBlock`], which writes the id the block was created with before the payload.
+/// Types implementing this trait do not implement [`DataEncoder`](crate::encoding::DataEncoder) as they have no block id of their own.
+pub trait CustomData: Debug + Send + Sync + 'static {
+    /// Encode the payload of the block, see [`DataEncoder::encode_data`](crate::encoding::DataEncoder::encode_data)
+    fn encode_custom(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError>;
+
+    /// Decode the payload of the block, see [`DataDecoder::decode_data`](crate::encoding::DataDecoder::decode_data)
+    fn decode_custom(
+        bytes: &mut impl Iterator<Item = u8>,
+        ver: EncodingVersion,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized;
+
+    /// See [`DataEncoder::should_encode_data`](crate::encoding::DataEncoder::should_encode_data)
+    fn should_encode_custom(&self, _ver: EncodingVersion) -> bool {
+        true
+    }
+
+    /// See [`DataEncoder::minimum_version`](crate::encoding::DataEncoder::minimum_version)
+    fn minimum_version_custom(&self) -> EncodingVersion {
+        EncodingVersion::V1
+    }
+
+    /// See [`DataEncoder::encoded_data_len`](crate::encoding::DataEncoder::encoded_data_len)
+    fn encoded_len_custom(&self, ver: EncodingVersion) -> Result<usize, EncodeError> {
+        let mut out = Vec::new();
+        self.encode_custom(ver, &mut out)?;
+
+        Ok(out.len())
+    }
+}
+
+/// Custom data stored in a [`CustomBlock`], allows downcasting and comparing the data
+trait StoredData: CustomData {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_custom(&self) -> &dyn CustomData;
+
+    fn eq_stored(&self, other: &dyn StoredData) -> bool;
+}
+
+impl<T: CustomData + PartialEq> StoredData for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_custom(&self) -> &dyn CustomData {
+        self
+    }
+
+    fn eq_stored(&self, other: &dyn StoredData) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// A block with a user defined id and data
+///
+/// Custom blocks are encoded with their own id followed by the length prefixed payload of their data.
+/// Their ids are in [`FRAMED_IDS`](super::FRAMED_IDS), which lets decoders without the block registered skip over them.
+/// Decoding them requires the id to be registered in a [`BlockRegistry`].
+#[derive(Clone, Debug)]
+pub struct CustomBlock {
+    id: u8,
+    data: Arc<dyn StoredData>,
+}
+
+impl CustomBlock {
+    /// Create a new custom block with the given id
+    ///
+    /// Returns an error if the id can not be registered, see [`BlockRegistry::register`].
+    pub fn new(id: u8, data: impl CustomData + PartialEq) -> Result<Self, RegistryError> {
+        check_id(id)?;
+
+        Ok(Self {
+            id,
+            data: Arc::new(data),
+        })
+    }
+
+    /// The id of this block
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// The data of this block
+    pub fn data(&self) -> &dyn CustomData {
+        self.data.as_custom()
+    }
+
+    /// Get the data of this block if it is of the given type
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.data.as_any().downcast_ref()
+    }
+
+    /// Encode the block id and the data of the block into the given output buffer
+    pub fn encode(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncoderError> {
+        if !self.data.should_encode_custom(ver) {
+            return Ok(());
+        }
+
+        let mut payload = Vec::new();
+        self.data
+            .encode_custom(ver, &mut payload)
+            .map_err(|e| EncoderError {
+                error: e,
+                during: DataBlockId::Custom,
+            })?;
+
+        out.push(self.id);
+        write_frame(&payload, out);
+
+        Ok(())
+    }
+
+    /// Length of the encoded block, see [`CustomBlock::encode`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        if !self.data.should_encode_custom(ver) {
+            return Ok(EncodedLen::default());
+        }
+
+        let len = self
+            .data
+            .encoded_len_custom(ver)
+            .map_err(|e| EncoderError {
+                error: e,
+                during: DataBlockId::Custom,
+            })?;
+
+        Ok(EncodedLen::new(1 + frame_len(len)))
+    }
+}
+
+impl PartialEq for CustomBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.data.eq_stored(other.data.as_ref())
+    }
+}
+
+impl Eq for CustomBlock {}
+
+impl From<CustomBlock> for AnyBlock {
+    fn from(data: CustomBlock) -> Self {
+        AnyBlock::Custom(data)
+    }
+}
+
+/// Errors from registering custom blocks
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    /// The id is used by a block of this crate
+    #[error("Block id {0} is used by a builtin block")]
+    BuiltinId(u8),
+    /// The id is reserved for the extension blocks of this crate
+    #[error("Block id {0} is reserved")]
+    ReservedId(u8),
+    /// The id is outside of [`FRAMED_IDS`](super::FRAMED_IDS), decoders without the block registered could not skip it
+    #[error("Block id {0} is not length prefixed")]
+    UnframedId(u8),
+    /// The id has already been registered
+    #[error("Block id {0} is already registered")]
+    AlreadyRegistered(u8),
+}
+
+type CustomDecoder =
+    fn(&mut dyn Iterator<Item = u8>, EncodingVersion) -> Result<Arc<dyn StoredData>, DecodeError>;
+
+/// Registry of custom blocks used for decoding them
+///
+/// Decoding with a registry works like [`AnyBlock::decode`] except that blocks with registered ids are decoded into [`AnyBlock::Custom`]
+/// using the decoder of the registered type.
+#[derive(Clone, Debug, Default)]
+pub struct BlockRegistry {
+    decoders: HashMap<u8, CustomDecoder>,
+}
+
+impl BlockRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a custom block type with the given id
+    ///
+    /// Only ids in [`FRAMED_IDS`](super::FRAMED_IDS) below [`RESERVED_IDS_START`] can be registered,
+    /// so decoders without the block registered can skip it.
+    pub fn register<T>(&mut self, id: u8) -> Result<(), RegistryError>
+    where
+        T: CustomData + PartialEq,
+    {
+        check_id(id)?;
+
+        if self.decoders.contains_key(&id) {
+            return Err(RegistryError::AlreadyRegistered(id));
+        }
+
+        self.decoders.insert(id, decode_custom::<T>);
+
+        Ok(())
+    }
+
+    /// Check if a block id is registered
+    pub fn is_registered(&self, id: u8) -> bool {
+        self.decoders.contains_key(&id)
+    }
+
+    /// Decode a block from the given byte stream, see [`AnyBlock::decode_one`]
+    pub fn decode_one(
+        &self,
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<AnyBlock, DecoderError> {
+        AnyBlock::decode_one_with(ver, bytes, Some(self))
+    }
+
+    /// Decode all blocks from the given byte stream, see [`AnyBlock::decode_all`]
+    pub fn decode_all(
+        &self,
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Vec<AnyBlock>, DecoderError> {
+        AnyBlock::decode_all_with(ver, bytes, Some(self))
+    }
+
+    /// Decode a fully formed idstring from the given byte stream, see [`AnyBlock::decode`]
+    pub fn decode(
+        &self,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Vec<AnyBlock>, DecoderError> {
+        AnyBlock::decode_with(bytes, Some(self))
+    }
+
+    /// Decode a full idstring into a list of blocks, see [`decode_str`](super::decode_str)
+    pub fn decode_str(&self, input: impl AsRef<str>) -> Result<Vec<AnyBlock>, DecoderError> {
+        let bytes = decode_any(input).map_err(|e| DecoderError {
+            error: e,
+            during: None,
+        })?;
+
+        self.decode(&mut bytes.into_iter())
+    }
+
+    /// Decode the data of a registered block
+    pub(crate) fn decode_custom(
+        &self,
+        id: u8,
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<AnyBlock, DecoderError> {
+        let decoder = self.decoders[&id];
+        let map_err = |e| DecoderError {
+            error: e,
+            during: Some(DataBlockId::Custom),
+        };
+
+        let data = decoder(bytes, ver).map_err(map_err)?;
+
+        Ok(AnyBlock::Custom(CustomBlock { id, data }))
+    }
+}
+
+fn decode_custom<T: CustomData + PartialEq>(
+    mut bytes: &mut dyn Iterator<Item = u8>,
+    ver: EncodingVersion,
+) -> Result<Arc<dyn StoredData>, DecodeError> {
+    Ok(Arc::new(T::decode_custom(&mut bytes, ver)?))
+}
+
+/// Check that an id can be used for a custom block
+fn check_id(id: u8) -> Result<(), RegistryError> {
+    if id >= RESERVED_IDS_START {
+        Err(RegistryError::ReservedId(id))
+    } else if DataBlockId::try_from(id).is_ok() {
+        Err(RegistryError::BuiltinId(id))
+    } else if !is_framed(id) {
+        Err(RegistryError::UnframedId(id))
+    } else {
+        Ok(())
+    }
+}
@@ -2,10 +2,10 @@ use std::ops::Range;
 
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
-    types::{AttackSpeed, Element, EncodingVersion},
+    types::{AttackSpeed, Element, EncodingVersion, Powder},
 };
 
 use super::{AnyBlock, DataBlockId};
@@ -22,6 +22,66 @@ pub struct DamageData {
     pub damages: Vec<(Option<Element>, Range<i32>)>,
 }
 
+/// Damage types in the order they are listed after applying powders, neutral first
+const DAMAGE_TYPES: [Option<Element>; 6] = [
+    None,
+    Some(Element::Earth),
+    Some(Element::Thunder),
+    Some(Element::Water),
+    Some(Element::Fire),
+    Some(Element::Air),
+];
+
+impl DamageData {
+    /// Calculate the damages of the weapon after applying the given powders
+    ///
+    /// Powders are applied in order. Each powder converts a percentage of the original neutral damage into its element,
+    /// limited by the neutral damage which has not been converted yet, and then adds its own elemental damage.
+    ///
+    /// The result contains the neutral damage followed by each element in order, skipping damage types with no damage.
+    pub fn with_powders(&self, powders: &[Powder]) -> DamageData {
+        let mut damages = [(0.0, 0.0); 6];
+        for (elem, range) in &self.damages {
+            let i = elem.map_or(0, |e| e as usize + 1);
+            damages[i].0 += range.start as f64;
+            damages[i].1 += range.end as f64;
+        }
+
+        let neutral = damages[0];
+        let mut remaining = neutral;
+
+        for powder in powders {
+            let stats = powder.stats();
+            let i = powder.element() as usize + 1;
+            let ratio = stats.conversion as f64 / 100.0;
+
+            if remaining.1 > 0.0 {
+                let min = remaining.0.min(neutral.0 * ratio);
+                let max = remaining.1.min(neutral.1 * ratio);
+
+                remaining.0 -= min;
+                remaining.1 -= max;
+                damages[i].0 += min;
+                damages[i].1 += max;
+            }
+
+            damages[i].0 += stats.min as f64;
+            damages[i].1 += stats.max as f64;
+        }
+        damages[0] = remaining;
+
+        DamageData {
+            attack_speed: self.attack_speed,
+            damages: DAMAGE_TYPES
+                .into_iter()
+                .zip(damages)
+                .map(|(elem, (min, max))| (elem, min.round() as i32..max.round() as i32))
+                .filter(|(_, range)| range.start != 0 || range.end != 0)
+                .collect(),
+        }
+    }
+}
+
 impl BlockId for DamageData {
     fn block_id(&self) -> DataBlockId {
         DataBlockId::DamageData
@@ -29,73 +89,75 @@ impl BlockId for DamageData {
 }
 
 impl DataEncoder for DamageData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // attack speed
-                out.push(self.attack_speed as u8);
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // attack speed
+        out.push(self.attack_speed as u8);
 
-                let dmg_count = u8::try_from(self.damages.len())
-                    .map_err(|_| EncodeError::TooManyDamageValues)?;
+        let dmg_count =
+            u8::try_from(self.damages.len()).map_err(|_| EncodeError::TooManyDamageValues)?;
 
-                // number of damage values
-                out.push(dmg_count);
+        // number of damage values
+        out.push(dmg_count);
 
-                for (damage_type, damage_value) in &self.damages {
-                    // damage type
-                    out.push(damage_type.as_ref().map_or(5, |e| (*e).into()));
+        for (damage_type, damage_value) in &self.damages {
+            // damage type
+            out.push(damage_type.as_ref().map_or(5, |e| (*e).into()));
 
-                    // damage value range
-                    out.append(&mut encode_varint(damage_value.start));
-                    out.append(&mut encode_varint(damage_value.end));
-                }
-
-                Ok(())
-            }
+            // damage value range
+            out.append(&mut encode_varint(damage_value.start));
+            out.append(&mut encode_varint(damage_value.end));
         }
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        u8::try_from(self.damages.len()).map_err(|_| EncodeError::TooManyDamageValues)?;
+
+        Ok(2 + self
+            .damages
+            .iter()
+            .map(|(_, value)| 1 + varint_len(value.start) + varint_len(value.end))
+            .sum::<usize>())
     }
 }
 
 impl DataDecoder for DamageData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // attack speed
-                let attack_speed =
-                    AttackSpeed::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
-
-                // number of damage values
-                let num_damages = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let mut damages = Vec::with_capacity(num_damages as usize);
-
-                for _ in 0..num_damages {
-                    // damage type
-                    let dtbyte = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                    let damage_type = if dtbyte == 5 {
-                        None
-                    } else {
-                        Some(Element::try_from(dtbyte)?)
-                    };
-
-                    // damage value range
-                    let start = decode_varint(bytes)? as i32;
-                    let end = decode_varint(bytes)? as i32;
-
-                    damages.push((damage_type, start..end));
-                }
-
-                Ok(Self {
-                    attack_speed,
-                    damages,
-                })
-            }
+        // attack speed
+        let attack_speed =
+            AttackSpeed::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
+
+        // number of damage values
+        let num_damages = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let mut damages = Vec::with_capacity(num_damages as usize);
+
+        for _ in 0..num_damages {
+            // damage type
+            let dtbyte = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+            let damage_type = if dtbyte == 5 {
+                None
+            } else {
+                Some(Element::try_from(dtbyte)?)
+            };
+
+            // damage value range
+            let start = decode_varint(bytes)? as i32;
+            let end = decode_varint(bytes)? as i32;
+
+            damages.push((damage_type, start..end));
         }
+
+        Ok(Self {
+            attack_speed,
+            damages,
+        })
     }
 }
 
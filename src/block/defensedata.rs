@@ -1,9 +1,9 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
-    types::{Element, EncodingVersion},
+    types::{Element, EncodingVersion, Powder},
 };
 
 use super::{AnyBlock, DataBlockId};
@@ -18,6 +18,44 @@ pub struct DefenseData {
     pub defences: Vec<(Element, i32)>,
 }
 
+const ELEMENTS: [Element; 5] = [
+    Element::Earth,
+    Element::Thunder,
+    Element::Water,
+    Element::Fire,
+    Element::Air,
+];
+
+impl DefenseData {
+    /// Calculate the defences of the armour after applying the given powders
+    ///
+    /// Each powder raises the defence of its own element and lowers the defence of the element it is strong against,
+    /// see [`Powder::weakened_element`].
+    ///
+    /// The result lists the elements in order, skipping elements with no defence.
+    pub fn with_powders(&self, powders: &[Powder]) -> DefenseData {
+        let mut defences = [0; 5];
+        for (elem, value) in &self.defences {
+            defences[*elem as usize] += value;
+        }
+
+        for powder in powders {
+            let stats = powder.stats();
+            defences[powder.element() as usize] += stats.defence_bonus;
+            defences[powder.weakened_element() as usize] -= stats.defence_penalty;
+        }
+
+        DefenseData {
+            health: self.health,
+            defences: ELEMENTS
+                .into_iter()
+                .zip(defences)
+                .filter(|(_, value)| *value != 0)
+                .collect(),
+        }
+    }
+}
+
 impl BlockId for DefenseData {
     fn block_id(&self) -> DataBlockId {
         DataBlockId::DefenseData
@@ -25,29 +63,37 @@ impl BlockId for DefenseData {
 }
 
 impl DataEncoder for DefenseData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // health value
-                out.append(&mut encode_varint(self.health));
-
-                let def_len =
-                    u8::try_from(self.defences.len()).map_err(|_| EncodeError::TooManyDefences)?;
-
-                // number of defences
-                out.push(def_len);
-
-                for (element, value) in &self.defences {
-                    // element id
-                    out.push((*element).into());
-
-                    // defence value
-                    out.append(&mut encode_varint(*value));
-                }
-            }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // health value
+        out.append(&mut encode_varint(self.health));
+
+        let def_len =
+            u8::try_from(self.defences.len()).map_err(|_| EncodeError::TooManyDefences)?;
+
+        // number of defences
+        out.push(def_len);
+
+        for (element, value) in &self.defences {
+            // element id
+            out.push((*element).into());
+
+            // defence value
+            out.append(&mut encode_varint(*value));
         }
 
-        todo!()
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        u8::try_from(self.defences.len()).map_err(|_| EncodeError::TooManyDefences)?;
+
+        Ok(varint_len(self.health)
+            + 1
+            + self
+                .defences
+                .iter()
+                .map(|(_, value)| 1 + varint_len(*value))
+                .sum::<usize>())
     }
 
     fn should_encode_data(&self, _ver: EncodingVersion) -> bool {
@@ -58,35 +104,31 @@ impl DataEncoder for DefenseData {
 impl DataDecoder for DefenseData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // health value
-                let health = decode_varint(bytes)? as i32;
+        // health value
+        let health = decode_varint(bytes)? as i32;
 
-                // number of defences
-                let num_defences = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)? as usize;
+        // number of defences
+        let num_defences = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)? as usize;
 
-                let mut defences = Vec::with_capacity(num_defences);
+        let mut defences = Vec::with_capacity(num_defences);
 
-                for _ in 0..num_defences {
-                    // element id
-                    let element =
-                        Element::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
+        for _ in 0..num_defences {
+            // element id
+            let element =
+                Element::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
 
-                    // defence value
-                    let value = decode_varint(bytes)? as i32;
+            // defence value
+            let value = decode_varint(bytes)? as i32;
 
-                    defences.push((element, value));
-                }
-
-                Ok(Self { health, defences })
-            }
+            defences.push((element, value));
         }
+
+        Ok(Self { health, defences })
     }
 }
 
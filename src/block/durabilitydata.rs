@@ -1,6 +1,6 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
     types::EncodingVersion,
@@ -21,6 +21,19 @@ pub struct DurabilityData {
     pub max: i32,
 }
 
+impl DurabilityData {
+    /// The effectiveness of the item as a percentage
+    ///
+    /// This is the effect strength of the item, or 0 if the item is broken as items without durability left do not provide any stats.
+    pub const fn effectiveness(&self) -> u8 {
+        if self.current <= 0 {
+            0
+        } else {
+            self.effect_strenght
+        }
+    }
+}
+
 impl BlockId for DurabilityData {
     fn block_id(&self) -> DataBlockId {
         DataBlockId::DurabilityData
@@ -28,49 +41,45 @@ impl BlockId for DurabilityData {
 }
 
 impl DataEncoder for DurabilityData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // Wynntils does not check this invariant during decoding. So lets just ignore it for fun
-                // if self.effect_strenght > 100 {
-                //     return Err(EncodeError::EffectStrengthTooHigh(self.effect_strenght));
-                // }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // Wynntils does not check this invariant during decoding. So lets just ignore it for fun
+        // if self.effect_strenght > 100 {
+        //     return Err(EncodeError::EffectStrengthTooHigh(self.effect_strenght));
+        // }
 
-                out.push(self.effect_strenght);
+        out.push(self.effect_strenght);
 
-                out.append(&mut encode_varint(self.max));
+        out.append(&mut encode_varint(self.max));
 
-                out.append(&mut encode_varint(self.current));
+        out.append(&mut encode_varint(self.current));
 
-                Ok(())
-            }
-        }
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1 + varint_len(self.max) + varint_len(self.current))
     }
 }
 
 impl DataDecoder for DurabilityData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let effect_strenght = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let effect_strenght = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
 
-                let max = decode_varint(bytes)? as i32;
+        let max = decode_varint(bytes)? as i32;
 
-                let current = decode_varint(bytes)? as i32;
+        let current = decode_varint(bytes)? as i32;
 
-                Ok(Self {
-                    effect_strenght,
-                    current,
-                    max,
-                })
-            }
-        }
+        Ok(Self {
+            effect_strenght,
+            current,
+            max,
+        })
     }
 }
 
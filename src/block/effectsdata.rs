@@ -1,6 +1,6 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
     types::{Effect, EffectType, EncodingVersion},
@@ -22,60 +22,63 @@ impl BlockId for EffectsData {
 }
 
 impl DataEncoder for EffectsData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                if self.effects.len() > 255 {
-                    return Err(EncodeError::TooManyEffects);
-                }
-
-                // number of effects
-                out.push(self.effects.len() as u8);
-
-                for effect in &self.effects {
-                    // effect type
-                    out.push(effect.kind as u8);
-
-                    // effect value
-                    out.append(&mut encode_varint(effect.value));
-                }
-
-                Ok(())
-            }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        if self.effects.len() > 255 {
+            return Err(EncodeError::TooManyEffects);
         }
+
+        // number of effects
+        out.push(self.effects.len() as u8);
+
+        for effect in &self.effects {
+            // effect type
+            out.push(effect.kind as u8);
+
+            // effect value
+            out.append(&mut encode_varint(effect.value));
+        }
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        if self.effects.len() > 255 {
+            return Err(EncodeError::TooManyEffects);
+        }
+
+        Ok(1 + self
+            .effects
+            .iter()
+            .map(|effect| 1 + varint_len(effect.value))
+            .sum::<usize>())
     }
 }
 
 impl DataDecoder for EffectsData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let effect_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let effect_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
 
-                let mut effects = Vec::with_capacity(effect_count as usize);
+        let mut effects = Vec::with_capacity(effect_count as usize);
 
-                for _ in 0..effect_count {
-                    let kind = EffectType::try_from(
-                        bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?,
-                    )?;
+        for _ in 0..effect_count {
+            let kind =
+                EffectType::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
 
-                    let value = decode_varint(bytes)?;
+            let value = decode_varint(bytes)?;
 
-                    effects.push(Effect {
-                        kind,
-                        value: value as i32,
-                    });
-                }
-
-                Ok(Self { effects })
-            }
+            effects.push(Effect {
+                kind,
+                value: value as i32,
+            });
         }
+
+        Ok(Self { effects })
     }
 }
 
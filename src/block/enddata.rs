@@ -21,6 +21,10 @@ impl DataEncoder for EndData {
         // end data is always empty
         Ok(())
     }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(0)
+    }
 }
 
 impl DataDecoder for EndData {
@@ -0,0 +1,121 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    encoding::{
+        varint::{decode_varint, encode_varint, varint_len},
+        DecodeError, EncodeError, EncodedLen, EncoderError,
+    },
+    types::{EncodingVersion, VersionFeature},
+};
+
+use super::{AnyBlock, DataBlockId};
+
+/// Range of block ids using length prefixed framing
+///
+/// Blocks with an id in this range are encoded as the id, the length of the payload as a varint and the payload.
+/// This allows decoders to skip blocks in this range they do not know, such blocks are decoded as [`UnknownBlock`]s.
+/// The range includes the extension blocks of this crate from [`RESERVED_IDS_START`](super::RESERVED_IDS_START) upwards,
+/// so decoders without the features for them can skip them. Only the end block is never framed.
+pub const FRAMED_IDS: RangeInclusive<u8> = 128..=254;
+
+/// Check if a block id uses length prefixed framing
+pub const fn is_framed(id: u8) -> bool {
+    *FRAMED_IDS.start() <= id && id <= *FRAMED_IDS.end()
+}
+
+/// Check if a block of this crate is length prefixed in the given version
+///
+/// Blocks in [`FRAMED_IDS`] are always length prefixed, in versions supporting [`VersionFeature::BlockLengths`] every block but the start block is.
+pub(crate) fn has_frame(id: DataBlockId, ver: EncodingVersion) -> bool {
+    id != DataBlockId::StartData
+        && (is_framed(id as u8) || ver.supports(VersionFeature::BlockLengths))
+}
+
+/// A length prefixed block which is not known by the decoder
+///
+/// The payload is kept as is so the block is encoded back exactly as it was decoded.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownBlock {
+    /// The id of the block
+    ///
+    /// Ids outside of [`FRAMED_IDS`] can only be encoded in versions supporting [`VersionFeature::BlockLengths`].
+    pub id: u8,
+    /// The payload of the block
+    pub data: Vec<u8>,
+}
+
+impl UnknownBlock {
+    /// Encode the block id, the length and the payload of the block into the given output buffer
+    ///
+    /// Returns an error if the id is used by a block of this crate or if the block can not be skipped by decoders of the given version.
+    pub fn encode(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncoderError> {
+        self.check_id(ver)?;
+
+        out.push(self.id);
+        write_frame(&self.data, out);
+
+        Ok(())
+    }
+
+    /// Length of the encoded block, see [`UnknownBlock::encode`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        self.check_id(ver)?;
+
+        Ok(EncodedLen::new(1 + frame_len(self.data.len())))
+    }
+
+    /// The oldest version able to encode the block
+    ///
+    /// Returns `None` if no version available in this build is able to encode the block, see [`UnknownBlock::encode`].
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        EncodingVersion::ALL
+            .iter()
+            .copied()
+            .find(|ver| self.check_id(*ver).is_ok())
+    }
+
+    fn check_id(&self, ver: EncodingVersion) -> Result<(), EncoderError> {
+        // a known id would be decoded as that block, an unframed id would corrupt the stream
+        if DataBlockId::try_from(self.id).is_ok()
+            || !(is_framed(self.id) || ver.supports(VersionFeature::BlockLengths))
+        {
+            return Err(EncoderError {
+                error: EncodeError::InvalidUnknownBlockId(self.id),
+                during: DataBlockId::Unknown,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl From<UnknownBlock> for AnyBlock {
+    fn from(data: UnknownBlock) -> Self {
+        AnyBlock::Unknown(data)
+    }
+}
+
+/// Write a length prefixed payload
+pub(crate) fn write_frame(payload: &[u8], out: &mut Vec<u8>) {
+    out.append(&mut encode_varint(payload.len() as i64));
+    out.extend_from_slice(payload);
+}
+
+/// Length of a length prefixed payload
+pub(crate) fn frame_len(payload_len: usize) -> usize {
+    varint_len(payload_len as i64) + payload_len
+}
+
+/// Read a length prefixed payload
+pub(crate) fn read_frame(bytes: &mut impl Iterator<Item = u8>) -> Result<Vec<u8>, DecodeError> {
+    let len = decode_varint(bytes)?;
+    let len = usize::try_from(len).map_err(|_| DecodeError::BadFrameLength(len))?;
+
+    let payload = bytes.take(len).collect::<Vec<_>>();
+    if payload.len() != len {
+        return Err(DecodeError::UnexpectedEndOfBytes);
+    }
+
+    Ok(payload)
+}
@@ -1,7 +1,9 @@
 use crate::{
     encoding::{
         self,
-        string::{decode_string, encode_string},
+        ascii::{decode_any, encode_ascii},
+        string::encode_string,
+        EncodedLen,
     },
     types::EncodingVersion,
 };
@@ -14,13 +16,14 @@ use super::AnyBlock;
 /// This function will return an error if the idstring is invalid and may error if the data within the idstring is invalid.
 ///
 /// Format version is decoded from the start of the idstring.
+/// Both the private use area form and the [ascii armour](crate::encoding::ascii) are accepted.
 ///
 /// For decoding an already decoded byte stream, use [`AnyBlock::decode`] instead.
 pub fn decode_str(input: impl AsRef<str>) -> Result<Vec<AnyBlock>, encoding::DecoderError> {
     AnyBlock::decode(
-        &mut decode_string(input)
+        &mut decode_any(input)
             .map_err(|e| encoding::DecoderError {
-                error: e.into(),
+                error: e,
                 during: None,
             })?
             .into_iter(),
@@ -48,6 +51,16 @@ pub fn encode_blocks(
     Ok(bytes)
 }
 
+/// Calculate the length of a list of blocks encoded by [`encode_blocks`] without encoding them
+///
+/// The private use area and ascii armoured string lengths of the idstring are available through the returned [`EncodedLen`].
+pub fn encoded_blocks_len(
+    ver: EncodingVersion,
+    blocks: &[AnyBlock],
+) -> Result<EncodedLen, encoding::EncoderError> {
+    blocks.iter().map(|block| block.encoded_len(ver)).sum()
+}
+
 /// Encode a list of blocks into an idstring
 ///
 /// This function encodes a list of blocks into an idstring. The blocks are encoded in the order they are given.
@@ -64,3 +77,15 @@ pub fn encode_blocks_str(
 
     Ok(encode_string(&bytes))
 }
+
+/// Encode a list of blocks into an ascii armoured idstring
+///
+/// See [`encode_blocks_str`] and [`encoding::ascii`]
+pub fn encode_blocks_ascii(
+    ver: EncodingVersion,
+    blocks: &[AnyBlock],
+) -> Result<String, encoding::EncoderError> {
+    let bytes = encode_blocks(ver, blocks)?;
+
+    Ok(encode_ascii(&bytes))
+}
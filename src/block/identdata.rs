@@ -1,6 +1,6 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
     types::{EncodingVersion, RollType, Stat},
@@ -28,53 +28,86 @@ impl BlockId for IdentificationData {
 }
 
 impl DataEncoder for IdentificationData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // wynntils spec allows for an item to have 255 identifications and 255 pre-identified identifications
-                if self
-                    .identifications
-                    .iter()
-                    .filter(|id| id.pre_identified())
-                    .count()
-                    > 255
-                    || self
-                        .identifications
-                        .iter()
-                        .filter(|id| id.pre_identified())
-                        .count()
-                        > 255
-                {
-                    return Err(EncodeError::TooManyIdentifications);
-                }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // wynntils spec allows for an item to have 255 identifications and 255 pre-identified identifications
+        if self
+            .identifications
+            .iter()
+            .filter(|id| id.pre_identified())
+            .count()
+            > 255
+            || self
+                .identifications
+                .iter()
+                .filter(|id| id.pre_identified())
+                .count()
+                > 255
+        {
+            return Err(EncodeError::TooManyIdentifications);
+        }
 
-                let encoded_id_count: u8 = self
-                    .identifications
-                    .iter()
-                    .filter(|id| !id.pre_identified())
-                    .count() as u8;
+        let encoded_id_count: u8 = self
+            .identifications
+            .iter()
+            .filter(|id| !id.pre_identified())
+            .count() as u8;
 
-                out.push(encoded_id_count);
-                out.push(u8::from(self.extended_encoding));
+        out.push(encoded_id_count);
+        out.push(u8::from(self.extended_encoding));
 
-                self.encode_individual_idents(out)?;
+        self.encode_individual_idents(out)?;
 
-                Ok(())
-            }
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        // same limit as when encoding
+        let preid_count = self
+            .identifications
+            .iter()
+            .filter(|id| id.pre_identified())
+            .count();
+        if preid_count > 255 {
+            return Err(EncodeError::TooManyIdentifications);
+        }
+
+        // count of idents and extended encoding flag
+        let mut len = 2;
+
+        if self.extended_encoding {
+            // count of preids
+            len += 1;
         }
+
+        for ident in &self.identifications {
+            let base_len = if self.extended_encoding {
+                varint_len(
+                    ident
+                        .base
+                        .ok_or(EncodeError::NoBasevalueGiven(ident.kind))?,
+                )
+            } else {
+                0
+            };
+
+            len += match ident.roll {
+                // preids are only encoded with extended encoding
+                RollType::PreIdentified if !self.extended_encoding => 0,
+                RollType::PreIdentified => 1 + base_len,
+                RollType::Value(_) => 2 + base_len,
+            };
+        }
+
+        Ok(len)
     }
 
-    fn should_encode_data(&self, ver: EncodingVersion) -> bool {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                if self.extended_encoding {
-                    !self.identifications.is_empty()
-                } else {
-                    self.identifications
-                        .iter()
-                        .any(|id: &Stat| !id.pre_identified())
-                }
-            }
+    fn should_encode_data(&self, _ver: EncodingVersion) -> bool {
+        if self.extended_encoding {
+            !self.identifications.is_empty()
+        } else {
+            self.identifications
+                .iter()
+                .any(|id: &Stat| !id.pre_identified())
         }
     }
 }
@@ -128,66 +161,62 @@ impl IdentificationData {
 impl DataDecoder for IdentificationData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let mut idents = Vec::new();
-
-                // first byte is the number of identifications
-                let ident_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-
-                // second byte is whether or not extended coding is used
-                let extended_encoding = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)? == 1;
-
-                let preid_count = if extended_encoding {
-                    // count of preid idents
-                    bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?
-                } else {
-                    0
-                };
-
-                for i in 0..(ident_count + preid_count) {
-                    // id of the ident
-                    let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-
-                    let preid = i < preid_count;
-
-                    // decode the possible baseval if using extended coding
-                    let baseval = if extended_encoding {
-                        Some(decode_varint(bytes)? as i32)
-                    } else {
-                        None
-                    };
-
-                    // if preid skip decoding the value
-                    if preid {
-                        idents.push(Stat {
-                            kind: id,
-                            base: baseval,
-                            roll: RollType::PreIdentified,
-                        });
-                    } else {
-                        // decode the roll
-                        let introll = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-
-                        idents.push(Stat {
-                            kind: id,
-                            base: baseval,
-                            roll: RollType::Value(introll),
-                        });
-                    }
-                }
-
-                Ok(Self {
-                    identifications: idents,
-                    extended_encoding,
-                })
+        let mut idents = Vec::new();
+
+        // first byte is the number of identifications
+        let ident_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        // second byte is whether or not extended coding is used
+        let extended_encoding = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)? == 1;
+
+        let preid_count = if extended_encoding {
+            // count of preid idents
+            bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?
+        } else {
+            0
+        };
+
+        for i in 0..(ident_count + preid_count) {
+            // id of the ident
+            let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+            let preid = i < preid_count;
+
+            // decode the possible baseval if using extended coding
+            let baseval = if extended_encoding {
+                Some(decode_varint(bytes)? as i32)
+            } else {
+                None
+            };
+
+            // if preid skip decoding the value
+            if preid {
+                idents.push(Stat {
+                    kind: id,
+                    base: baseval,
+                    roll: RollType::PreIdentified,
+                });
+            } else {
+                // decode the roll
+                let introll = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+                idents.push(Stat {
+                    kind: id,
+                    base: baseval,
+                    roll: RollType::Value(introll),
+                });
             }
         }
+
+        Ok(Self {
+            identifications: idents,
+            extended_encoding,
+        })
     }
 }
 
@@ -4,13 +4,32 @@
 //! These blocks are used to represent the data of the items in the idstrings.
 //!
 //! Blocks can be encoded and decoded using the functions provided in this module and the methods provided by [`AnyBlock`] and [`DataBlockId`].
+//! User defined blocks can be added using [`CustomBlock`] and decoded by registering them in a [`BlockRegistry`].
+//! Blocks with ids in [`FRAMED_IDS`] are length prefixed, decoders which do not know such a block keep it as an [`UnknownBlock`] instead of failing.
 //!
 //! In general the item module of this crate should be used for decoding and encoding items when low level block manipulation is not required.
 
+mod framed;
+#[doc(inline)]
+pub use framed::{is_framed, UnknownBlock, FRAMED_IDS};
+pub(crate) use framed::{has_frame, read_frame, write_frame};
+
 mod functions;
 #[doc(inline)]
 pub use functions::*;
 
+mod bundledata;
+#[doc(inline)]
+pub use bundledata::BundleData;
+
+mod checksumdata;
+#[cfg(feature = "checksum")]
+#[doc(inline)]
+pub use checksumdata::encode_blocks_checksummed;
+#[doc(inline)]
+pub use checksumdata::ChecksumData;
+pub(crate) use checksumdata::ChecksumReader;
+
 mod craftedconsutypedata;
 #[doc(inline)]
 pub use craftedconsutypedata::CraftedConsumableTypeData;
@@ -23,6 +42,10 @@ mod craftedidentdata;
 #[doc(inline)]
 pub use craftedidentdata::CraftedIdentificationData;
 
+mod custom;
+#[doc(inline)]
+pub use custom::{BlockRegistry, CustomBlock, CustomData, RegistryError, RESERVED_IDS_START};
+
 mod damagedata;
 #[doc(inline)]
 pub use damagedata::DamageData;
@@ -48,6 +71,7 @@ mod identdata;
 pub use identdata::IdentificationData;
 
 mod namedata;
+pub(crate) use namedata::name_len;
 #[doc(inline)]
 pub use namedata::NameData;
 
@@ -67,10 +91,18 @@ mod shinydata;
 #[doc(inline)]
 pub use shinydata::ShinyData;
 
+mod signaturedata;
+#[doc(inline)]
+pub use signaturedata::{InvalidSignatureLength, SignatureData};
+
 mod startdata;
 #[doc(inline)]
 pub use startdata::StartData;
 
+mod tierdata;
+#[doc(inline)]
+pub use tierdata::TierData;
+
 mod typedata;
 use thiserror::Error;
 #[doc(inline)]
@@ -81,8 +113,8 @@ mod usesdata;
 pub use usesdata::UsesData;
 
 use crate::{
-    encoding::{DataDecoder, DataEncoder, DecodeError, DecoderError, EncoderError},
-    types::EncodingVersion,
+    encoding::{DataDecoder, DataEncoder, DecodeError, DecoderError, EncodedLen, EncoderError},
+    types::{EncodingVersion, VersionFeature},
 };
 
 /// Macro for defining the enums for dealing with the data blocks
@@ -91,15 +123,24 @@ use crate::{
 /// This mainly saves a lot of work writing these manually.
 macro_rules! datablock_defs {
     (
-        $(($name:ident, $id:expr, $ty:ty),)+
+        $($(#[$meta:meta])* ($name:ident, $id:expr, $ty:ty),)+
     ) => {
         /// Enum representing the ids of the data blocks
         #[repr(u8)]
         #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
         pub enum DataBlockId {
             $(
+                $(#[$meta])*
                 $name = $id,
             )+
+            /// Placeholder id of custom blocks
+            ///
+            /// This id is never encoded, custom blocks are encoded using the id they were registered with. See [`BlockRegistry`]
+            Custom = 0xFE,
+            /// Placeholder id of unknown length prefixed blocks
+            ///
+            /// This id is never encoded, unknown blocks are encoded using their own id. See [`UnknownBlock`]
+            Unknown = 0xFD,
         }
 
         #[derive(Error, Debug)]
@@ -112,6 +153,7 @@ macro_rules! datablock_defs {
             fn try_from(value: u8) -> Result<Self, Self::Error> {
                 match value {
                     $(
+                        $(#[$meta])*
                         $id => Ok(DataBlockId::$name),
                     )+
                     _ => Err(InvalidBlockId(value)),
@@ -124,8 +166,14 @@ macro_rules! datablock_defs {
             pub fn decode(&self, ver: EncodingVersion, bytes: &mut impl Iterator<Item = u8>) -> Result<AnyBlock, DecoderError> {
                 Ok(match self {
                     $(
+                        $(#[$meta])*
                         DataBlockId::$name => self.decode_with::<$ty>(bytes, ver)?.into(),
                     )+
+                    // custom blocks can only be decoded using a registry and unknown blocks have no decoder
+                    DataBlockId::Custom | DataBlockId::Unknown => return Err(DecoderError {
+                        error: DecodeError::UnknownBlock(InvalidBlockId(*self as u8)),
+                        during: None,
+                    }),
                 })
             }
         }
@@ -134,8 +182,13 @@ macro_rules! datablock_defs {
         #[derive(Debug, Clone, PartialEq, Eq)]
         pub enum AnyBlock {
             $(
+                $(#[$meta])*
                 $name($ty),
             )+
+            /// A custom block registered in a [`BlockRegistry`]
+            Custom(CustomBlock),
+            /// A length prefixed block not known by the decoder
+            Unknown(UnknownBlock),
         }
 
         impl AnyBlock {
@@ -145,8 +198,38 @@ macro_rules! datablock_defs {
             pub fn encode(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncoderError> {
                 match self {
                     $(
+                        $(#[$meta])*
                         AnyBlock::$name(data) => data.encode(ver, out),
                     )+
+                    AnyBlock::Custom(data) => data.encode(ver, out),
+                    AnyBlock::Unknown(data) => data.encode(ver, out),
+                }
+            }
+
+            /// Length of the encoded block, see [`DataEncoder::encoded_len`]
+            pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+                match self {
+                    $(
+                        $(#[$meta])*
+                        AnyBlock::$name(data) => data.encoded_len(ver),
+                    )+
+                    AnyBlock::Custom(data) => data.encoded_len(ver),
+                    AnyBlock::Unknown(data) => data.encoded_len(ver),
+                }
+            }
+
+            /// The oldest version able to encode this block without losing any of its data, see [`DataEncoder::minimum_version`]
+            ///
+            /// Returns `None` if no version available in this build is able to encode the block.
+            /// This is the case for unknown blocks outside of [`FRAMED_IDS`] without a version supporting [`VersionFeature::BlockLengths`], see [`UnknownBlock::minimum_version`].
+            pub fn minimum_version(&self) -> Option<EncodingVersion> {
+                match self {
+                    $(
+                        $(#[$meta])*
+                        AnyBlock::$name(data) => Some(data.minimum_version()),
+                    )+
+                    AnyBlock::Custom(data) => Some(data.data().minimum_version_custom()),
+                    AnyBlock::Unknown(data) => data.minimum_version(),
                 }
             }
 
@@ -154,8 +237,26 @@ macro_rules! datablock_defs {
             pub const fn as_id(&self) -> DataBlockId {
                 match self {
                     $(
+                        $(#[$meta])*
                         AnyBlock::$name(_) => DataBlockId::$name,
                     )+
+                    AnyBlock::Custom(_) => DataBlockId::Custom,
+                    AnyBlock::Unknown(_) => DataBlockId::Unknown,
+                }
+            }
+        }
+
+        impl std::hash::Hash for AnyBlock {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::mem::discriminant(self).hash(state);
+                match self {
+                    $(
+                        $(#[$meta])*
+                        AnyBlock::$name(data) => data.hash(state),
+                    )+
+                    // the data of custom blocks can not be hashed, equal blocks still have the same id
+                    AnyBlock::Custom(data) => data.id().hash(state),
+                    AnyBlock::Unknown(data) => data.hash(state),
                 }
             }
         }
@@ -179,6 +280,11 @@ datablock_defs! {
     (CraftedConsumableTypeData, 13, CraftedConsumableTypeData),
     (UsesData, 14, UsesData),
     (EffectsData, 15, EffectsData),
+    // not part of the wynntils format, ids from 240 upwards are used for extensions of this crate
+    (ChecksumData, 240, ChecksumData),
+    (SignatureData, 241, SignatureData),
+    (BundleData, 242, BundleData),
+    (TierData, 243, TierData),
     (EndData, 255, EndData),
 }
 
@@ -190,6 +296,26 @@ impl From<DataBlockId> for u8 {
 }
 
 impl DataBlockId {
+    /// Decode the data of a block of this id whose id byte has already been read
+    ///
+    /// This reads the length prefix of the block if it has one, see [`has_frame`].
+    pub(crate) fn decode_payload<T: DataDecoder>(
+        self,
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<T, DecoderError> {
+        if has_frame(self, ver) {
+            let payload = read_frame(bytes).map_err(|e| DecoderError {
+                error: e,
+                during: Some(self),
+            })?;
+
+            self.decode_with(&mut payload.into_iter(), ver)
+        } else {
+            self.decode_with(bytes, ver)
+        }
+    }
+
     /// Attempt to decode a block with assumed type using the given decoder
     fn decode_with<T: DataDecoder>(
         self,
@@ -223,16 +349,71 @@ impl AnyBlock {
     pub fn decode_one(
         ver: EncodingVersion,
         bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Self, DecoderError> {
+        Self::decode_one_with(ver, bytes, None)
+    }
+
+    /// Decode a block consulting the registry for ids which are not known by this crate
+    pub(crate) fn decode_one_with(
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+        registry: Option<&BlockRegistry>,
     ) -> Result<Self, DecoderError> {
         // read the id of the block
         let id = bytes.next().ok_or(DecoderError {
             error: DecodeError::UnexpectedEndOfBytes,
             during: None,
         })?;
-        let block_id = DataBlockId::try_from(id).map_err(|e| DecoderError {
-            error: DecodeError::UnknownBlock(e),
-            during: None,
-        })?;
+        let registered = registry.is_some_and(|r| r.is_registered(id));
+
+        // every block is length prefixed in versions supporting it
+        if ver.supports(VersionFeature::BlockLengths) || is_framed(id) {
+            let payload = read_frame(bytes).map_err(|e| DecoderError {
+                error: e,
+                during: Some(match DataBlockId::try_from(id) {
+                    Ok(block_id) => block_id,
+                    Err(_) if registered => DataBlockId::Custom,
+                    Err(_) => DataBlockId::Unknown,
+                }),
+            })?;
+
+            return Self::decode_block(ver, id, &mut payload.into_iter(), registry, true);
+        }
+
+        Self::decode_block(ver, id, bytes, registry, false)
+    }
+
+    /// Decode the data of a block with the given id
+    ///
+    /// Framed blocks have already had their length prefix read and the bytes only contain the payload of the block.
+    /// Data after the part known by the decoder of a framed block is skipped.
+    fn decode_block(
+        ver: EncodingVersion,
+        id: u8,
+        bytes: &mut impl Iterator<Item = u8>,
+        registry: Option<&BlockRegistry>,
+        framed: bool,
+    ) -> Result<Self, DecoderError> {
+        let block_id = match (DataBlockId::try_from(id), registry) {
+            (Ok(block_id), _) => block_id,
+            (Err(_), Some(registry)) if registry.is_registered(id) => {
+                return registry.decode_custom(id, ver, bytes)
+            }
+            // unknown length prefixed blocks are kept as is
+            (Err(_), _) if framed => {
+                return Ok(UnknownBlock {
+                    id,
+                    data: bytes.collect(),
+                }
+                .into())
+            }
+            (Err(e), _) => {
+                return Err(DecoderError {
+                    error: DecodeError::UnknownBlock(e),
+                    during: None,
+                })
+            }
+        };
 
         // decode using the decoder for the block id
         block_id.decode(ver, bytes)
@@ -244,12 +425,44 @@ impl AnyBlock {
     pub fn decode_all(
         ver: EncodingVersion,
         bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Vec<Self>, DecoderError> {
+        Self::decode_all_with(ver, bytes, None)
+    }
+
+    /// See [`AnyBlock::decode_all`] and [`AnyBlock::decode_one_with`]
+    pub(crate) fn decode_all_with(
+        ver: EncodingVersion,
+        bytes: &mut impl Iterator<Item = u8>,
+        registry: Option<&BlockRegistry>,
+    ) -> Result<Vec<Self>, DecoderError> {
+        // without a start block checksums cover the bytes from here on
+        let bytes = &mut ChecksumReader::new(bytes);
+
+        Self::decode_blocks(ver, bytes, registry)
+    }
+
+    /// Decode blocks up to and including the end block
+    ///
+    /// The bytes are read through a [`ChecksumReader`] created where the idstring starts.
+    /// With the `checksum` feature checksum blocks are verified against it, without it they are decoded but not verified.
+    pub(crate) fn decode_blocks<I: Iterator<Item = u8>>(
+        ver: EncodingVersion,
+        bytes: &mut ChecksumReader<'_, I>,
+        registry: Option<&BlockRegistry>,
     ) -> Result<Vec<Self>, DecoderError> {
         let mut blocks = Vec::new();
         let mut cont = true;
         while cont {
+            #[cfg(feature = "checksum")]
+            let found = bytes.checksum();
+
             // read block
-            let block = Self::decode_one(ver, bytes)?;
+            let block = Self::decode_one_with(ver, bytes, registry)?;
+
+            #[cfg(feature = "checksum")]
+            if let Self::ChecksumData(checksum) = block {
+                checksumdata::verify(checksum, found)?;
+            }
 
             // if we reached the end block, stop
             if let Self::EndData(_) = block {
@@ -265,6 +478,17 @@ impl AnyBlock {
     ///
     /// This function assumes that the byte stream is a valid idstring which starts with a start block and ends with an end block
     pub fn decode(bytes: &mut impl Iterator<Item = u8>) -> Result<Vec<Self>, DecoderError> {
+        Self::decode_with(bytes, None)
+    }
+
+    /// See [`AnyBlock::decode`] and [`AnyBlock::decode_one_with`]
+    pub(crate) fn decode_with(
+        bytes: &mut impl Iterator<Item = u8>,
+        registry: Option<&BlockRegistry>,
+    ) -> Result<Vec<Self>, DecoderError> {
+        // checksums cover the whole idstring including the start block
+        let bytes = &mut ChecksumReader::new(bytes);
+
         // read the start data
         let start = StartData::decode_start_bytes(bytes).map_err(|e| DecoderError {
             error: e,
@@ -275,7 +499,7 @@ impl AnyBlock {
         let mut out = vec![StartData(start).into()];
 
         // decode the rest of the blocks
-        out.append(&mut Self::decode_all(start, bytes)?);
+        out.append(&mut Self::decode_blocks(start, bytes, registry)?);
 
         Ok(out)
     }
@@ -17,46 +17,52 @@ impl BlockId for NameData {
 }
 
 impl DataEncoder for NameData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // check that the string is valid ascii
-                if !self.0.is_ascii() {
-                    return Err(EncodeError::NonAsciiString);
-                }
-
-                // push the bytes
-                out.extend_from_slice(self.0.as_bytes());
-                // push the null terminator
-                out.push(0);
-            }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // check that the string is valid ascii
+        if !self.0.is_ascii() {
+            return Err(EncodeError::NonAsciiString);
         }
 
+        // push the bytes
+        out.extend_from_slice(self.0.as_bytes());
+        // push the null terminator
+        out.push(0);
+
         Ok(())
     }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        name_len(&self.0)
+    }
 }
 
 impl DataDecoder for NameData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let b: Vec<u8> = bytes.take_while(|b| *b != 0).collect();
+        let b: Vec<u8> = bytes.take_while(|b| *b != 0).collect();
 
-                // UTF-8 and ASCII share the same set of characters
-                Ok(NameData(
-                    String::from_utf8(b).map_err(|_| DecodeError::BadString)?,
-                ))
-            }
-        }
+        // UTF-8 and ASCII share the same set of characters
+        Ok(NameData(
+            String::from_utf8(b).map_err(|_| DecodeError::BadString)?,
+        ))
     }
 }
 
+/// Length of a name encoded as the payload of a name block
+pub(crate) fn name_len(name: &str) -> Result<usize, EncodeError> {
+    if !name.is_ascii() {
+        return Err(EncodeError::NonAsciiString);
+    }
+
+    // the bytes and the null terminator
+    Ok(name.len() + 1)
+}
+
 impl From<NameData> for AnyBlock {
     fn from(data: NameData) -> Self {
         AnyBlock::NameData(data)
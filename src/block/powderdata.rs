@@ -1,10 +1,16 @@
 use crate::{
     encoding::{BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError},
-    types::{Element, EncodingVersion, Powder},
+    types::{
+        ActiveSpecial, CraftedGearType, Element, EncodingVersion, Powder, PowderSpecial,
+        VersionFeature,
+    },
 };
 
 use super::{AnyBlock, DataBlockId};
 
+/// Bit of the count byte marking packed powders as encoded in runs rather than as 5 bit values
+const RUNS_FLAG: u8 = 0b10000000;
+
 /// The block for powder data
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -15,6 +21,15 @@ pub struct PowderData {
     pub powders: Vec<Powder>,
 }
 
+impl PowderData {
+    /// Get the powder special active on gear of the given type with these powders
+    ///
+    /// See [`PowderSpecial::detect`]
+    pub fn special(&self, gear_type: CraftedGearType) -> Option<ActiveSpecial> {
+        PowderSpecial::detect(gear_type, &self.powders)
+    }
+}
+
 impl BlockId for PowderData {
     fn block_id(&self) -> DataBlockId {
         DataBlockId::PowderData
@@ -23,42 +38,37 @@ impl BlockId for PowderData {
 
 impl DataEncoder for PowderData {
     fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let powders_len =
-                    u8::try_from(self.powders.len()).map_err(|_| EncodeError::TooManyPowders)?;
-
-                let bits_needed = self.powders.len() * 5;
-                let total_bits = (bits_needed + 7) / 8;
+        out.push(self.powder_slots);
 
-                let mut powder_data = vec![0u8; total_bits];
-
-                for (i, pow) in self.powders.iter().enumerate() {
-                    let (elem, tier) = (pow.element() as u8, pow.tier());
-
-                    // calculate the 5 bit powder value
-                    let powder_num = (elem * 6 + tier) & 0b00011111;
-
-                    // bit position where this specific powder starts
-                    let powder_idx = i * 5;
+        if !ver.supports(VersionFeature::PackedPowders) {
+            return self.encode_bits(out);
+        }
 
-                    // set the values
-                    for j in 0..5 {
-                        // calculate the bit position of this bit
-                        let idx = powder_idx + j;
-                        let bit = (powder_num >> (4 - j)) & 0b1;
-                        powder_data[idx / 8] |= bit << (7 - (idx % 8));
-                    }
-                }
+        // the highest bit of the count byte marks which of the forms is used
+        if self.powders.len() > (!RUNS_FLAG) as usize {
+            return Err(EncodeError::TooManyPackedPowders);
+        }
 
-                out.push(self.powder_slots);
-                out.push(powders_len);
+        if self.run_count() < bits_len(self.powders.len()) {
+            self.encode_runs(out)
+        } else {
+            self.encode_bits(out)
+        }
+    }
 
-                out.append(&mut powder_data);
+    fn encoded_data_len(&self, ver: EncodingVersion) -> Result<usize, EncodeError> {
+        let len = if ver.supports(VersionFeature::PackedPowders) {
+            if self.powders.len() > (!RUNS_FLAG) as usize {
+                return Err(EncodeError::TooManyPackedPowders);
             }
-        }
+            self.run_count().min(bits_len(self.powders.len()))
+        } else {
+            u8::try_from(self.powders.len()).map_err(|_| EncodeError::TooManyPowders)?;
+            bits_len(self.powders.len())
+        };
 
-        Ok(())
+        // slots and count
+        Ok(2 + len)
     }
 }
 
@@ -70,48 +80,175 @@ impl DataDecoder for PowderData {
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let slots = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let powder_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)? as usize;
-
-                let bits_needed = powder_count * 5;
-                let total_bytes = (bits_needed + 7) / 8;
-
-                let bytes: Vec<u8> = bytes.take(total_bytes).collect();
-                let mut powders = Vec::new();
-
-                for powder_idx in 0..powder_count {
-                    let mut powder = 0u8;
-
-                    for i in 0..5 {
-                        let idx = (powder_idx * 5) + i;
-                        let bit = (bytes[idx / 8] >> (7 - (idx % 8))) & 0b1;
-                        powder |= bit << (4 - i);
-                    }
-
-                    if powder == 0 {
-                        // ignore empty powders
-                        continue;
-                    }
-
-                    let (elem, tier) = if powder % 6 == 0 {
-                        ((powder / 6) - 1, 6)
-                    } else {
-                        ((powder / 6), powder % 6)
-                    };
-                    powders.push(Powder::try_from((Element::try_from(elem)?, tier))?);
-                }
-
-                Ok(Self {
-                    powder_slots: slots,
-                    powders,
-                })
+        let slots = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        let count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        let powders = if ver.supports(VersionFeature::PackedPowders) && count & RUNS_FLAG != 0 {
+            Self::decode_runs(count & !RUNS_FLAG, bytes)?
+        } else {
+            Self::decode_bits(count, bytes)?
+        };
+
+        Ok(Self {
+            powder_slots: slots,
+            powders,
+        })
+    }
+}
+
+impl PowderData {
+    /// Encode the powders as 5 bit values
+    fn encode_bits(&self, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        let powders_len =
+            u8::try_from(self.powders.len()).map_err(|_| EncodeError::TooManyPowders)?;
+
+        let mut powder_data = vec![0u8; bits_len(self.powders.len())];
+
+        for (i, pow) in self.powders.iter().enumerate() {
+            // calculate the 5 bit powder value
+            let powder_num = powder_value(pow) & 0b00011111;
+
+            // bit position where this specific powder starts
+            let powder_idx = i * 5;
+
+            // set the values
+            for j in 0..5 {
+                // calculate the bit position of this bit
+                let idx = powder_idx + j;
+
+                let bit = (powder_num >> (4 - j)) & 0b1;
+
+                powder_data[idx / 8] |= bit << (7 - (idx % 8));
+            }
+        }
+
+        out.push(powders_len);
+        out.append(&mut powder_data);
+
+        Ok(())
+    }
+
+    /// Decode powders encoded as 5 bit values
+    fn decode_bits(
+        powder_count: u8,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Vec<Powder>, DecodeError> {
+        let powder_count = powder_count as usize;
+
+        let bytes: Vec<u8> = bytes.take(bits_len(powder_count)).collect();
+        if bytes.len() < bits_len(powder_count) {
+            return Err(DecodeError::UnexpectedEndOfBytes);
+        }
+
+        let mut powders = Vec::new();
+
+        for powder_idx in 0..powder_count {
+            let mut powder = 0u8;
+
+            for i in 0..5 {
+                let idx = (powder_idx * 5) + i;
+
+                let bit = (bytes[idx / 8] >> (7 - (idx % 8))) & 0b1;
+
+                powder |= bit << (4 - i);
+            }
+
+            // ignore empty powders
+            powders.extend(powder_from_value(powder)?);
+        }
+
+        Ok(powders)
+    }
+
+    /// Encode the powders as runs of identical powders
+    ///
+    /// Each run is a single byte with the length of the run minus one in the highest 3 bits and the powder value in the lowest 5 bits.
+    /// As every run takes a full byte this is only used when it is shorter than [`PowderData::encode_bits`].
+    fn encode_runs(&self, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        let mut runs: Vec<(u8, u8)> = Vec::new();
+
+        for pow in &self.powders {
+            let value = powder_value(pow);
+
+            match runs.last_mut() {
+                Some((v, len)) if *v == value && *len < 8 => *len += 1,
+                _ => runs.push((value, 1)),
+            }
+        }
+
+        // the run count is never above the powder count, which is checked to fit next to the flag
+        out.push(runs.len() as u8 | RUNS_FLAG);
+        out.extend(runs.iter().map(|(value, len)| ((len - 1) << 5) | value));
+
+        Ok(())
+    }
+
+    /// Number of runs the powders are encoded as, see [`PowderData::encode_runs`]
+    fn run_count(&self) -> usize {
+        let mut runs = 0;
+        let mut last = None;
+        let mut len = 0;
+
+        for pow in &self.powders {
+            let value = powder_value(pow);
+
+            if last == Some(value) && len < 8 {
+                len += 1;
+            } else {
+                runs += 1;
+                last = Some(value);
+                len = 1;
+            }
+        }
+
+        runs
+    }
+
+    /// Decode powders encoded as runs of identical powders
+    fn decode_runs(
+        run_count: u8,
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Result<Vec<Powder>, DecodeError> {
+        let mut powders = Vec::new();
+
+        for _ in 0..run_count {
+            let run = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+            if let Some(powder) = powder_from_value(run & 0b00011111)? {
+                powders.extend(std::iter::repeat_n(powder, ((run >> 5) + 1) as usize));
             }
         }
+
+        Ok(powders)
     }
 }
 
+/// Number of bytes needed for the given amount of powders encoded as 5 bit values
+fn bits_len(powders: usize) -> usize {
+    (powders * 5).div_ceil(8)
+}
+
+/// The 5 bit value of a powder
+fn powder_value(powder: &Powder) -> u8 {
+    powder.element() as u8 * 6 + powder.tier()
+}
+
+/// Get the powder of a 5 bit value, zero being no powder
+fn powder_from_value(value: u8) -> Result<Option<Powder>, DecodeError> {
+    if value == 0 {
+        return Ok(None);
+    }
+
+    let (elem, tier) = if value % 6 == 0 {
+        ((value / 6) - 1, 6)
+    } else {
+        ((value / 6), value % 6)
+    };
+
+    Ok(Some(Powder::try_from((Element::try_from(elem)?, tier))?))
+}
+
 impl From<PowderData> for AnyBlock {
     fn from(data: PowderData) -> Self {
         AnyBlock::PowderData(data)
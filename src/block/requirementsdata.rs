@@ -1,6 +1,6 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
     types::{ClassType, EncodingVersion, SkillType},
@@ -29,73 +29,73 @@ impl BlockId for RequirementsData {
 }
 
 impl DataEncoder for RequirementsData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // level requirement
-                out.push(self.level);
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // level requirement
+        out.push(self.level);
 
-                // class requirement
-                let class = self.class.map_or(0, Into::into);
-                out.push(class);
+        // class requirement
+        let class = self.class.map_or(0, Into::into);
+        out.push(class);
 
-                let skills_len =
-                    u8::try_from(self.skills.len()).map_err(|_| EncodeError::TooManySkills)?;
+        let skills_len = u8::try_from(self.skills.len()).map_err(|_| EncodeError::TooManySkills)?;
 
-                // encode number of skill requirements
-                out.push(skills_len);
+        // encode number of skill requirements
+        out.push(skills_len);
 
-                for (skill, value) in &self.skills {
-                    // skill id
-                    out.push((*skill).into());
+        for (skill, value) in &self.skills {
+            // skill id
+            out.push((*skill).into());
 
-                    // skill requirement value
-                    out.append(&mut encode_varint(*value));
-                }
-
-                Ok(())
-            }
+            // skill requirement value
+            out.append(&mut encode_varint(*value));
         }
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        u8::try_from(self.skills.len()).map_err(|_| EncodeError::TooManySkills)?;
+
+        Ok(3 + self
+            .skills
+            .iter()
+            .map(|(_, value)| 1 + varint_len(*value))
+            .sum::<usize>())
     }
 }
 
 impl DataDecoder for RequirementsData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let level = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-
-                let class = match bytes.next() {
-                    Some(0) => None,
-                    Some(c) => Some(ClassType::try_from(c)?),
-                    None => return Err(DecodeError::UnexpectedEndOfBytes),
-                };
-
-                let skill_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let mut skills = Vec::with_capacity(skill_count as usize);
-
-                for _ in 0..skill_count {
-                    let skill = SkillType::try_from(
-                        bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?,
-                    )?;
-                    let value = decode_varint(bytes)?;
-
-                    skills.push((skill, value as i32));
-                }
-
-                Ok(Self {
-                    level,
-                    class,
-                    skills,
-                })
-            }
+        let level = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        let class = match bytes.next() {
+            Some(0) => None,
+            Some(c) => Some(ClassType::try_from(c)?),
+            None => return Err(DecodeError::UnexpectedEndOfBytes),
+        };
+
+        let skill_count = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let mut skills = Vec::with_capacity(skill_count as usize);
+
+        for _ in 0..skill_count {
+            let skill =
+                SkillType::try_from(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?)?;
+            let value = decode_varint(bytes)?;
+
+            skills.push((skill, value as i32));
         }
+
+        Ok(Self {
+            level,
+            class,
+            skills,
+        })
     }
 }
 
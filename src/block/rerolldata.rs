@@ -17,28 +17,26 @@ impl BlockId for RerollData {
 }
 
 impl DataEncoder for RerollData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => out.push(self.0),
-        }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0);
 
         Ok(())
     }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1)
+    }
 }
 
 impl DataDecoder for RerollData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                Ok(Self(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?))
-            }
-        }
+        Ok(Self(bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?))
     }
 }
 
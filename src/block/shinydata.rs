@@ -1,9 +1,9 @@
 use crate::{
     encoding::{
-        varint::{decode_varint, encode_varint},
+        varint::{decode_varint, encode_varint, varint_len},
         BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError,
     },
-    types::EncodingVersion,
+    types::{EncodingVersion, VersionFeature},
 };
 
 use super::{AnyBlock, DataBlockId};
@@ -32,21 +32,30 @@ impl BlockId for ShinyData {
 
 impl DataEncoder for ShinyData {
     fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            // V2 adds reroll value as the second byte
-            EncodingVersion::V1 => {
-                out.push(self.id);
-                out.append(&mut encode_varint(self.val));
-            },
-            EncodingVersion::V2 => {
-                out.push(self.id);
-                out.push(self.rr);
-                out.append(&mut encode_varint(self.val));
-            }
+        out.push(self.id);
+        // V2 adds reroll value as the second byte
+        if ver.supports(VersionFeature::ShinyRerolls) {
+            out.push(self.rr);
         }
+        out.append(&mut encode_varint(self.val));
 
         Ok(())
     }
+
+    fn encoded_data_len(&self, ver: EncodingVersion) -> Result<usize, EncodeError> {
+        let rr_len = usize::from(ver.supports(VersionFeature::ShinyRerolls));
+
+        Ok(1 + rr_len + varint_len(self.val))
+    }
+
+    fn minimum_version(&self) -> EncodingVersion {
+        // the rerolls are lost in versions without them
+        if self.rr != 0 {
+            EncodingVersion::V2
+        } else {
+            EncodingVersion::V1
+        }
+    }
 }
 
 impl DataDecoder for ShinyData {
@@ -57,21 +66,15 @@ impl DataDecoder for ShinyData {
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 => {
-                let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let rr = 0;
-                let val = decode_varint(bytes)?;
-                Ok(Self { id, val, rr })
-            },
-            EncodingVersion::V2 => {
-                let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                // V2 adds reroll value as the second byte, thats the only change from V1
-                let rr = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let val = decode_varint(bytes)?;
-                Ok(Self { id, val, rr })
-            }
-        }
+        let id = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        // V2 adds reroll value as the second byte, thats the only change from V1
+        let rr = if ver.supports(VersionFeature::ShinyRerolls) {
+            bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?
+        } else {
+            0
+        };
+        let val = decode_varint(bytes)?;
+        Ok(Self { id, val, rr })
     }
 }
 
@@ -0,0 +1,100 @@
+use thiserror::Error;
+
+use crate::{
+    encoding::{BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError},
+    types::EncodingVersion,
+};
+
+use super::{AnyBlock, DataBlockId};
+
+/// The block for an Ed25519 signature of an item
+///
+/// This block is not part of the wynntils format. The signature is made over the canonical encoded bytes of the item, see `GenericItem::canonical_bytes`.
+/// The block is always decoded, creating and verifying signatures requires the `signature` feature.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vec<u8>", into = "Vec<u8>"))]
+pub struct SignatureData(pub [u8; SignatureData::LEN]);
+
+impl SignatureData {
+    /// Length of an Ed25519 signature in bytes
+    pub const LEN: usize = 64;
+}
+
+/// Error for a signature with a length other than [`SignatureData::LEN`]
+#[derive(Error, Debug)]
+#[error("Invalid signature length: {0}")]
+pub struct InvalidSignatureLength(pub usize);
+
+impl TryFrom<Vec<u8>> for SignatureData {
+    type Error = InvalidSignatureLength;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        let len = value.len();
+
+        Ok(Self(
+            value.try_into().map_err(|_| InvalidSignatureLength(len))?,
+        ))
+    }
+}
+
+impl From<SignatureData> for Vec<u8> {
+    fn from(data: SignatureData) -> Self {
+        data.0.to_vec()
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<ed25519_dalek::Signature> for SignatureData {
+    fn from(signature: ed25519_dalek::Signature) -> Self {
+        Self(signature.to_bytes())
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<SignatureData> for ed25519_dalek::Signature {
+    fn from(data: SignatureData) -> Self {
+        Self::from_bytes(&data.0)
+    }
+}
+
+impl BlockId for SignatureData {
+    fn block_id(&self) -> DataBlockId {
+        DataBlockId::SignatureData
+    }
+}
+
+impl DataEncoder for SignatureData {
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.extend(self.0);
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(Self::LEN)
+    }
+}
+
+impl DataDecoder for SignatureData {
+    fn decode_data(
+        bytes: &mut impl Iterator<Item = u8>,
+        _ver: EncodingVersion,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let mut signature = [0; Self::LEN];
+        for b in &mut signature {
+            *b = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        }
+
+        Ok(Self(signature))
+    }
+}
+
+impl From<SignatureData> for AnyBlock {
+    fn from(data: SignatureData) -> Self {
+        AnyBlock::SignatureData(data)
+    }
+}
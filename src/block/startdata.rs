@@ -17,13 +17,23 @@ impl BlockId for StartData {
 }
 
 impl DataEncoder for StartData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => out.push(self.0.into()),
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        if !self.0.is_available() {
+            return Err(EncodeError::UnavailableVersion(self.0));
         }
 
+        out.push(self.0.into());
+
         Ok(())
     }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        if !self.0.is_available() {
+            return Err(EncodeError::UnavailableVersion(self.0));
+        }
+
+        Ok(1)
+    }
 }
 
 impl DataDecoder for StartData {
@@ -0,0 +1,63 @@
+use crate::{
+    encoding::{BlockId, DataDecoder, DataEncoder, DecodeError, EncodeError},
+    types::{EncodingVersion, ItemTier, VersionFeature},
+};
+
+use super::{AnyBlock, DataBlockId};
+
+/// The block for the rarity tier of an item
+///
+/// This block is only encoded in versions supporting [`VersionFeature::ItemTier`] and skipped in older versions.
+/// It is part of the draft `V3` encoding and uses an id from the extension range of this crate.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierData(pub ItemTier);
+
+impl BlockId for TierData {
+    fn block_id(&self) -> DataBlockId {
+        DataBlockId::TierData
+    }
+}
+
+impl DataEncoder for TierData {
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0.into());
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1)
+    }
+
+    fn should_encode_data(&self, ver: EncodingVersion) -> bool {
+        ver.supports(VersionFeature::ItemTier)
+    }
+
+    fn minimum_version(&self) -> EncodingVersion {
+        // without a version supporting tiers the newest version loses the least data
+        VersionFeature::ItemTier
+            .since()
+            .unwrap_or(EncodingVersion::latest_draft())
+    }
+}
+
+impl DataDecoder for TierData {
+    fn decode_data(
+        bytes: &mut impl Iterator<Item = u8>,
+        _ver: EncodingVersion,
+    ) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let b = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        Ok(Self(ItemTier::try_from(b)?))
+    }
+}
+
+impl From<TierData> for AnyBlock {
+    fn from(data: TierData) -> Self {
+        AnyBlock::TierData(data)
+    }
+}
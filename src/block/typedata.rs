@@ -17,30 +17,28 @@ impl BlockId for TypeData {
 }
 
 impl DataEncoder for TypeData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => out.push(self.0.into()),
-        }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0.into());
 
         Ok(())
     }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(1)
+    }
 }
 
 impl DataDecoder for TypeData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let b = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let b = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
 
-                Ok(Self(ItemType::try_from(b)?))
-            }
-        }
+        Ok(Self(ItemType::try_from(b)?))
     }
 }
 
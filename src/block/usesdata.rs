@@ -22,36 +22,32 @@ impl BlockId for UsesData {
 }
 
 impl DataEncoder for UsesData {
-    fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                // first the current amount left
-                out.push(self.current);
-                // then the max amount
-                out.push(self.max);
-
-                Ok(())
-            }
-        }
+    fn encode_data(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        // first the current amount left
+        out.push(self.current);
+        // then the max amount
+        out.push(self.max);
+
+        Ok(())
+    }
+
+    fn encoded_data_len(&self, _ver: EncodingVersion) -> Result<usize, EncodeError> {
+        Ok(2)
     }
 }
 
 impl DataDecoder for UsesData {
     fn decode_data(
         bytes: &mut impl Iterator<Item = u8>,
-        ver: EncodingVersion,
+        _ver: EncodingVersion,
     ) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
-        match ver {
-            EncodingVersion::V1 | EncodingVersion::V2 => {
-                let current = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-                let max = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
-
-                Ok(Self { current, max })
-            }
-        }
+        let current = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let max = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+
+        Ok(Self { current, max })
     }
 }
 
@@ -0,0 +1,52 @@
+use thiserror::Error;
+
+use crate::{
+    item::error::{ItemConvertError, ItemDecodeError},
+    types::{CraftedGearType, ItemType},
+};
+
+use super::{SkillPoints, Slot};
+
+#[cfg(feature = "database")]
+use crate::database::DatabaseError;
+
+/// Errors that can occur when assembling a [`Build`](super::Build)
+#[derive(Error, Debug)]
+pub enum BuildError {
+    /// The gear type of the item can not be equipped into the slot
+    #[error("A {gear_type:?} can not be equipped as a {slot:?}")]
+    IncompatibleSlot {
+        /// The slot the item was equipped into
+        slot: Slot,
+        /// The gear type of the item
+        gear_type: CraftedGearType,
+    },
+
+    /// Every slot which can hold the item already holds an item
+    #[error("No free slot for a {0:?}")]
+    NoFreeSlot(CraftedGearType),
+
+    /// The items of the build can not be equipped with the available skill points
+    #[error("The build needs {} skill points to be equipped", .assigned.total())]
+    Unequippable {
+        /// The fewest skill points the build would need to be equipped
+        assigned: SkillPoints,
+    },
+
+    /// The item is not gear and can not be part of a build
+    #[error("Items of type {0:?} can not be part of a build")]
+    InvalidItemType(ItemType),
+
+    /// The item is missing data required by its type
+    #[error("Invalid item: {0}")]
+    InvalidItem(#[from] ItemConvertError),
+
+    /// An item of the build could not be decoded
+    #[error("Failed to decode item: {0}")]
+    Decode(#[from] ItemDecodeError),
+
+    /// The static data of an item could not be looked up
+    #[cfg(feature = "database")]
+    #[error("Database error: {0}")]
+    Database(#[from] DatabaseError),
+}
@@ -0,0 +1,250 @@
+//! Module for assembling builds out of multiple items
+//!
+//! A [`Build`] holds one [`BuildPiece`] per equipment [`Slot`] and provides the combined stats of all of the equipped pieces.
+//! Whether the pieces can be worn at the same time can be checked with [`Build::solve_skill_points`].
+//!
+//! Crafted gear contains all of the data needed for a build piece, for other gear the static data of the item has to be looked up
+//! from an `database::ItemDatabase` which requires the `database` feature.
+
+use crate::types::{CraftedGearType, Element, SkillType};
+
+mod error;
+#[doc(inline)]
+pub use error::BuildError;
+
+mod piece;
+#[doc(inline)]
+pub use piece::{BuildPiece, SkillPoints};
+
+mod solver;
+#[doc(inline)]
+pub use solver::{SkillSolution, MAX_ASSIGNED_POINTS, MAX_SKILL_POINTS};
+
+/// The equipment slots of a build
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Slot {
+    Helmet,
+    Chestplate,
+    Leggings,
+    Boots,
+    Ring1,
+    Ring2,
+    Bracelet,
+    Necklace,
+    Weapon,
+}
+
+impl Slot {
+    /// All of the slots in the order they are displayed in
+    pub const ALL: [Slot; 9] = [
+        Slot::Helmet,
+        Slot::Chestplate,
+        Slot::Leggings,
+        Slot::Boots,
+        Slot::Ring1,
+        Slot::Ring2,
+        Slot::Bracelet,
+        Slot::Necklace,
+        Slot::Weapon,
+    ];
+
+    /// Check if an item of the given gear type can be equipped into this slot
+    ///
+    /// The [`CraftedGearType::Weapon`] and [`CraftedGearType::Accessory`] fallback types are accepted by every weapon and accessory slot respectively.
+    pub fn accepts(&self, gear_type: CraftedGearType) -> bool {
+        use CraftedGearType as G;
+
+        match self {
+            Slot::Helmet => gear_type == G::Helmet,
+            Slot::Chestplate => gear_type == G::Chestplate,
+            Slot::Leggings => gear_type == G::Leggings,
+            Slot::Boots => gear_type == G::Boots,
+            Slot::Ring1 | Slot::Ring2 => matches!(gear_type, G::Ring | G::Accessory),
+            Slot::Bracelet => matches!(gear_type, G::Bracelet | G::Accessory),
+            Slot::Necklace => matches!(gear_type, G::Necklace | G::Accessory),
+            Slot::Weapon => matches!(
+                gear_type,
+                G::Spear | G::Wand | G::Dagger | G::Bow | G::Relik | G::Weapon
+            ),
+        }
+    }
+
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A set of equipped items
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Build {
+    pieces: [Option<BuildPiece>; 9],
+}
+
+impl Build {
+    /// Create a new empty build
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Equip a piece into the given slot, returning the piece previously in the slot
+    ///
+    /// # Errors
+    /// Returns [`BuildError::IncompatibleSlot`] if the gear type of the piece does not fit the slot.
+    pub fn set(&mut self, slot: Slot, piece: BuildPiece) -> Result<Option<BuildPiece>, BuildError> {
+        if !slot.accepts(piece.gear_type) {
+            return Err(BuildError::IncompatibleSlot {
+                slot,
+                gear_type: piece.gear_type,
+            });
+        }
+
+        Ok(self.pieces[slot.index()].replace(piece))
+    }
+
+    /// Equip a piece into the first free slot which accepts it and return the slot
+    ///
+    /// # Errors
+    /// Returns [`BuildError::NoFreeSlot`] if every slot which accepts the piece is already taken.
+    pub fn equip(&mut self, piece: BuildPiece) -> Result<Slot, BuildError> {
+        let slot = Slot::ALL
+            .into_iter()
+            .find(|s| s.accepts(piece.gear_type) && self.pieces[s.index()].is_none())
+            .ok_or(BuildError::NoFreeSlot(piece.gear_type))?;
+
+        self.pieces[slot.index()] = Some(piece);
+
+        Ok(slot)
+    }
+
+    /// Remove the piece in the given slot
+    pub fn remove(&mut self, slot: Slot) -> Option<BuildPiece> {
+        self.pieces[slot.index()].take()
+    }
+
+    /// Get the piece in the given slot
+    pub fn get(&self, slot: Slot) -> Option<&BuildPiece> {
+        self.pieces[slot.index()].as_ref()
+    }
+
+    /// Iterate over the equipped pieces and their slots
+    pub fn iter(&self) -> impl Iterator<Item = (Slot, &BuildPiece)> {
+        Slot::ALL
+            .into_iter()
+            .zip(&self.pieces)
+            .filter_map(|(slot, piece)| Some((slot, piece.as_ref()?)))
+    }
+
+    /// Number of equipped pieces
+    pub fn len(&self) -> usize {
+        self.pieces.iter().flatten().count()
+    }
+
+    /// Check if the build has no pieces equipped
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The combined identifications of all of the pieces, sorted by id
+    pub fn identifications(&self) -> Vec<(u8, i32)> {
+        let mut out: Vec<(u8, i32)> = Vec::new();
+
+        for (_, piece) in self.iter() {
+            for (kind, value) in &piece.identifications {
+                match out.iter_mut().find(|(k, _)| k == kind) {
+                    Some((_, total)) => *total += value,
+                    None => out.push((*kind, *value)),
+                }
+            }
+        }
+
+        out.sort_by_key(|(kind, _)| *kind);
+        out
+    }
+
+    /// The combined health of all of the pieces
+    ///
+    /// This does not include the base health of the character or health identifications.
+    pub fn health(&self) -> i32 {
+        self.iter().map(|(_, p)| p.health).sum()
+    }
+
+    /// The combined elemental defences of all of the pieces
+    pub fn defences(&self) -> Vec<(Element, i32)> {
+        [
+            Element::Earth,
+            Element::Thunder,
+            Element::Water,
+            Element::Fire,
+            Element::Air,
+        ]
+        .into_iter()
+        .map(|elem| {
+            let total = self
+                .iter()
+                .flat_map(|(_, p)| &p.defences)
+                .filter(|(e, _)| *e == elem)
+                .map(|(_, v)| v)
+                .sum();
+            (elem, total)
+        })
+        .collect()
+    }
+
+    /// The highest skill point requirement of the pieces for each skill
+    pub fn skill_requirements(&self) -> SkillPoints {
+        let mut out = SkillPoints::default();
+
+        for (_, piece) in self.iter() {
+            for (skill, value) in &piece.requirements.skills {
+                out[*skill] = out[*skill].max(*value);
+            }
+        }
+
+        out
+    }
+
+    /// The combined skill point bonuses of all of the pieces
+    pub fn skill_bonuses(&self) -> SkillPoints {
+        self.iter()
+            .fold(SkillPoints::default(), |acc, (_, p)| acc + p.skill_bonuses)
+    }
+
+    /// The highest level requirement of the pieces
+    pub fn level_requirement(&self) -> u8 {
+        self.iter()
+            .map(|(_, p)| p.requirements.level)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "database")]
+impl Build {
+    /// Decode a build from the idstrings of its items
+    ///
+    /// Each item is equipped into the first free slot which accepts it, see [`Build::equip`].
+    pub fn decode<S: AsRef<str>>(
+        items: impl IntoIterator<Item = S>,
+        db: &crate::database::ItemDatabase,
+    ) -> Result<Self, BuildError> {
+        let mut build = Self::new();
+
+        for item in items {
+            let item = crate::item::GenericItem::decode_string(item)?;
+            build.equip(BuildPiece::from_item(&item, db)?)?;
+        }
+
+        Ok(build)
+    }
+}
+
+/// Names of the skill point identifications in the id table
+pub(crate) const SKILL_KEYS: [(SkillType, &str); 5] = [
+    (SkillType::Strength, "rawStrength"),
+    (SkillType::Dexterity, "rawDexterity"),
+    (SkillType::Intelligence, "rawIntelligence"),
+    (SkillType::Defence, "rawDefence"),
+    (SkillType::Agility, "rawAgility"),
+];
@@ -0,0 +1,215 @@
+use std::ops::{Add, Index, IndexMut};
+
+use crate::{
+    block::{DamageData, RequirementsData},
+    item::CraftedGear,
+    types::{CraftedGearType, Element, IdKeys, SkillType},
+};
+
+#[cfg(feature = "database")]
+use crate::{
+    block::IdentificationData,
+    database::{DatabaseError, ItemDatabase, ItemEntry},
+    item::{GearItem, GenericItem},
+    types::ItemType,
+};
+
+use super::SKILL_KEYS;
+
+#[cfg(feature = "database")]
+use super::BuildError;
+
+/// An amount of skill points for each skill
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkillPoints(pub [i32; 5]);
+
+impl SkillPoints {
+    /// The skills in the order they are stored in
+    pub const SKILLS: [SkillType; 5] = [
+        SkillType::Strength,
+        SkillType::Dexterity,
+        SkillType::Intelligence,
+        SkillType::Defence,
+        SkillType::Agility,
+    ];
+
+    /// Iterate over the skills and their points
+    pub fn iter(&self) -> impl Iterator<Item = (SkillType, i32)> + '_ {
+        Self::SKILLS.into_iter().zip(self.0)
+    }
+
+    /// Sum of the points of every skill
+    pub fn total(&self) -> i32 {
+        self.0.iter().sum()
+    }
+}
+
+impl Index<SkillType> for SkillPoints {
+    type Output = i32;
+
+    fn index(&self, skill: SkillType) -> &Self::Output {
+        &self.0[u8::from(skill) as usize]
+    }
+}
+
+impl IndexMut<SkillType> for SkillPoints {
+    fn index_mut(&mut self, skill: SkillType) -> &mut Self::Output {
+        &mut self.0[u8::from(skill) as usize]
+    }
+}
+
+impl Add for SkillPoints {
+    type Output = SkillPoints;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+        self
+    }
+}
+
+/// A single item of a build
+///
+/// This contains the data of an item which is relevant for a build.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildPiece {
+    /// Name of the item
+    pub name: String,
+    /// The gear type of the item
+    pub gear_type: CraftedGearType,
+    /// Requirements of the item
+    pub requirements: RequirementsData,
+    /// Values of the identifications of the item as `(id, value)`
+    pub identifications: Vec<(u8, i32)>,
+    /// Skill points given by the identifications of the item
+    pub skill_bonuses: SkillPoints,
+    /// Amount of health the item grants
+    pub health: i32,
+    /// Elemental defences of the item
+    pub defences: Vec<(Element, i32)>,
+    /// Base damages of the item if the item is a weapon
+    pub damage: Option<DamageData>,
+}
+
+impl BuildPiece {
+    /// Create a build piece from a crafted gear item
+    ///
+    /// The identifications of crafted items are taken at their full durability values.
+    /// The id table is used for finding the skill point identifications of the item.
+    pub fn from_crafted(item: &CraftedGear, id_keys: &IdKeys) -> Self {
+        let gear_type = item.gear_type().0;
+        let identifications: Vec<_> = item
+            .identifications()
+            .map(|i| i.idents.iter().map(|s| (s.kind, s.max)).collect())
+            .unwrap_or_default();
+        let defense = item.defense();
+
+        Self {
+            name: item
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Crafted {gear_type:?}")),
+            gear_type,
+            requirements: item.requirements().clone(),
+            skill_bonuses: skill_bonuses(&identifications, id_keys),
+            identifications,
+            health: defense.map_or(0, |d| d.health),
+            defences: defense.map(|d| d.defences.clone()).unwrap_or_default(),
+            damage: item.damage().cloned(),
+        }
+    }
+}
+
+#[cfg(feature = "database")]
+impl BuildPiece {
+    /// Create a build piece from a gear item using the database for the static data of the item
+    ///
+    /// Unidentified items use the base values of their identifications.
+    pub fn from_gear(item: &GearItem, db: &ItemDatabase) -> Result<Self, DatabaseError> {
+        let mut item = item.clone();
+        let entry = item.hydrate(db)?;
+
+        Self::from_entry(entry, item.identifications.as_ref(), db.id_keys())
+    }
+
+    /// Create a build piece from a database entry and the identifications of the item
+    ///
+    /// The identifications are expected to contain their base values, see [`ItemEntry::hydrate`].
+    /// If no identifications are given the base values of the identifications of the entry are used.
+    pub fn from_entry(
+        entry: &ItemEntry,
+        idents: Option<&IdentificationData>,
+        id_keys: &IdKeys,
+    ) -> Result<Self, DatabaseError> {
+        let gear_type = entry
+            .gear_type
+            .ok_or_else(|| DatabaseError::WrongItemType {
+                item: entry.name.clone(),
+                expected: ItemType::Gear,
+                found: entry.kind,
+            })?;
+
+        let identifications: Vec<_> = match idents {
+            Some(idents) => idents
+                .identifications
+                .iter()
+                .map(|s| {
+                    s.value()
+                        .map(|v| (s.kind, v))
+                        .ok_or_else(|| DatabaseError::UnknownStat {
+                            item: entry.name.clone(),
+                            kind: s.kind,
+                        })
+                })
+                .collect::<Result<_, _>>()?,
+            None => entry
+                .identifications
+                .iter()
+                .map(|s| (s.kind, s.base))
+                .collect(),
+        };
+
+        Ok(Self {
+            name: entry.name.clone(),
+            gear_type,
+            requirements: entry.requirements.clone(),
+            skill_bonuses: skill_bonuses(&identifications, id_keys),
+            identifications,
+            health: entry.health,
+            defences: entry.defences.clone(),
+            damage: entry.damage.clone(),
+        })
+    }
+
+    /// Create a build piece from either a gear item or a crafted gear item
+    pub fn from_item(item: &GenericItem, db: &ItemDatabase) -> Result<Self, BuildError> {
+        match item.kind {
+            ItemType::Gear => Ok(Self::from_gear(&GearItem::try_from(item.clone())?, db)?),
+            ItemType::CraftedGear => Ok(Self::from_crafted(
+                &CraftedGear::try_from(item.clone())?,
+                db.id_keys(),
+            )),
+            kind => Err(BuildError::InvalidItemType(kind)),
+        }
+    }
+}
+
+/// Sum up the skill point identifications
+fn skill_bonuses(identifications: &[(u8, i32)], id_keys: &IdKeys) -> SkillPoints {
+    let mut out = SkillPoints::default();
+
+    for (skill, key) in SKILL_KEYS {
+        if let Some(id) = id_keys.id(key) {
+            out[skill] = identifications
+                .iter()
+                .filter(|(kind, _)| *kind == id)
+                .map(|(_, v)| v)
+                .sum();
+        }
+    }
+
+    out
+}
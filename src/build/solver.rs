@@ -0,0 +1,155 @@
+use super::{Build, BuildError, BuildPiece, SkillPoints, Slot};
+
+/// The total amount of skill points a character can assign
+pub const MAX_SKILL_POINTS: i32 = 200;
+/// The most skill points a character can assign to a single skill
+pub const MAX_ASSIGNED_POINTS: i32 = 100;
+
+/// A way to equip all of the items of a build
+///
+/// See [`Build::solve_skill_points`]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SkillSolution {
+    /// The skill points which have to be assigned to each skill
+    pub assigned: SkillPoints,
+    /// The total skill points of each skill once every item is equipped, including the bonuses of the items
+    pub total: SkillPoints,
+    /// The order in which the items have to be equipped
+    pub order: Vec<Slot>,
+}
+
+/// State of the search for the best equip order
+struct Search<'a> {
+    pieces: Vec<(Slot, &'a BuildPiece)>,
+    weapon: Option<(Slot, &'a BuildPiece)>,
+    /// The best assignment found so far as `(exceeds the per skill limit, assigned, order)`
+    best: Option<(bool, SkillPoints, Vec<usize>)>,
+}
+
+impl Build {
+    /// Find the equip order which needs the least assigned skill points
+    ///
+    /// See [`Build::solve_skill_points_with`]
+    pub fn solve_skill_points(&self) -> Result<SkillSolution, BuildError> {
+        self.solve_skill_points_with(MAX_SKILL_POINTS)
+    }
+
+    /// Find the equip order which needs the least assigned skill points using the given amount of available skill points
+    ///
+    /// Items follow the Wynncraft equipping rules. An item can only be equipped if the assigned skill points together with the bonuses
+    /// of the items equipped before it meet its requirements, and every item still has to meet its requirements once all items are equipped.
+    /// The weapon is always equipped last. At most [`MAX_ASSIGNED_POINTS`] can be assigned to a single skill.
+    ///
+    /// # Errors
+    /// Returns [`BuildError::Unequippable`] if no equip order works within the available skill points.
+    pub fn solve_skill_points_with(&self, available: i32) -> Result<SkillSolution, BuildError> {
+        let (weapon, armour): (Vec<_>, Vec<_>) = self.iter().partition(|(s, _)| *s == Slot::Weapon);
+
+        // items without requirements or bonuses can be equipped at any point
+        let (passive, pieces): (Vec<_>, Vec<_>) = armour.into_iter().partition(|(_, p)| {
+            p.requirements.skills.iter().all(|(_, v)| *v <= 0) && p.skill_bonuses.0 == [0; 5]
+        });
+
+        let mut search = Search {
+            pieces,
+            weapon: weapon.into_iter().next(),
+            best: None,
+        };
+        search.permute(
+            &mut Vec::new(),
+            SkillPoints::default(),
+            SkillPoints::default(),
+        );
+
+        let (over_limit, assigned, order) = search.best.unwrap_or_default();
+        if over_limit || assigned.total() > available {
+            return Err(BuildError::Unequippable { assigned });
+        }
+
+        let order = passive
+            .iter()
+            .map(|(s, _)| *s)
+            .chain(order.iter().map(|i| search.pieces[*i].0))
+            .chain(search.weapon.map(|(s, _)| s))
+            .collect();
+
+        Ok(SkillSolution {
+            assigned,
+            total: assigned + self.skill_bonuses(),
+            order,
+        })
+    }
+
+    /// Check if the items of the build can be equipped at the same time
+    ///
+    /// See [`Build::solve_skill_points`]
+    pub fn is_equippable(&self) -> bool {
+        self.solve_skill_points().is_ok()
+    }
+}
+
+impl Search<'_> {
+    /// Try every order of the remaining pieces
+    fn permute(&mut self, order: &mut Vec<usize>, assigned: SkillPoints, bonuses: SkillPoints) {
+        if order.len() == self.pieces.len() {
+            self.finish(order, assigned, bonuses);
+            return;
+        }
+
+        for i in 0..self.pieces.len() {
+            if order.contains(&i) {
+                continue;
+            }
+
+            let piece = self.pieces[i].1;
+            order.push(i);
+            self.permute(
+                order,
+                require(piece, assigned, bonuses),
+                bonuses + piece.skill_bonuses,
+            );
+            order.pop();
+        }
+    }
+
+    /// Equip the weapon, check the final requirements and keep the order if it is the best one so far
+    fn finish(&mut self, order: &[usize], mut assigned: SkillPoints, mut bonuses: SkillPoints) {
+        if let Some((_, weapon)) = self.weapon {
+            assigned = require(weapon, assigned, bonuses);
+            bonuses = bonuses + weapon.skill_bonuses;
+        }
+
+        // items with negative bonuses may break the requirements of the items equipped before them
+        for piece in self
+            .pieces
+            .iter()
+            .map(|(_, p)| *p)
+            .chain(self.weapon.map(|(_, p)| p))
+        {
+            assigned = require(piece, assigned, bonuses);
+        }
+
+        let over_limit = assigned.0.iter().any(|v| *v > MAX_ASSIGNED_POINTS);
+        let better = match &self.best {
+            Some((best_over, best, _)) => {
+                (over_limit, assigned.total()) < (*best_over, best.total())
+            }
+            None => true,
+        };
+
+        if better {
+            self.best = Some((over_limit, assigned, order.to_vec()));
+        }
+    }
+}
+
+/// Assign the skill points needed for the piece to meet its requirements with the given bonuses
+fn require(piece: &BuildPiece, mut assigned: SkillPoints, bonuses: SkillPoints) -> SkillPoints {
+    for (skill, req) in &piece.requirements.skills {
+        if *req > 0 {
+            assigned[*skill] = assigned[*skill].max(req - bonuses[*skill]);
+        }
+    }
+
+    assigned
+}
@@ -0,0 +1,37 @@
+use thiserror::Error;
+
+use crate::{
+    item::error::ItemConvertError,
+    types::{AttackSpeed, ConsumableType},
+};
+
+/// Errors that can occur when simulating a [`Craft`](super::Craft)
+#[derive(Error, Debug)]
+pub enum CraftError {
+    /// Material tiers have to be between 1 and 3
+    #[error("Invalid material tier: {0}")]
+    InvalidMaterialTier(u8),
+
+    /// Crafted weapons can only be slow, normal or fast
+    #[error("Crafted weapons can not have the attack speed {0:?}")]
+    InvalidAttackSpeed(AttackSpeed),
+
+    /// The level of an ingredient is higher than the level of the recipe
+    #[error("Ingredient `{name}` is level {level} but the recipe is at most level {max}")]
+    IngredientLevel {
+        /// Name of the ingredient
+        name: String,
+        /// Level of the ingredient
+        level: u8,
+        /// Highest level of the recipe
+        max: u8,
+    },
+
+    /// Only gear can be crafted, recipes for consumables are not supported
+    #[error("Crafting consumables is not supported, the recipe is for a {0:?}")]
+    ConsumableRecipe(ConsumableType),
+
+    /// The crafted item is missing data required by its type
+    #[error("Invalid item: {0}")]
+    InvalidItem(#[from] ItemConvertError),
+}
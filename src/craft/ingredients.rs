@@ -0,0 +1,201 @@
+use std::{collections::HashMap, io::Read};
+
+use serde::Deserialize;
+
+use crate::{
+    database::{schema::SKILL_NAMES, DatabaseError},
+    types::IdKeys,
+};
+
+use super::{Ingredient, IngredientStat, PositionModifiers};
+
+/// Database of ingredients
+///
+/// Ingredients are loaded from the item json provided by the Wynncraft v3 api, the same json an [`ItemDatabase`](crate::database::ItemDatabase) is loaded from.
+#[derive(Clone, Debug, Default)]
+pub struct IngredientDatabase {
+    ingredients: HashMap<String, Ingredient>,
+    id_keys: IdKeys,
+}
+
+/// An ingredient as returned by the api
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ApiIngredient {
+    #[serde(default)]
+    requirements: ApiRequirements,
+    #[serde(default)]
+    identifications: HashMap<String, ApiRange>,
+    #[serde(default)]
+    ingredient_position_modifiers: ApiModifiers,
+    #[serde(default, rename = "itemOnlyIDs")]
+    item_only_ids: ApiItemOnly,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ApiRequirements {
+    #[serde(default)]
+    level: u8,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+enum ApiRange {
+    Fixed(i32),
+    Rolled { min: i32, max: i32 },
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ApiModifiers {
+    left: i32,
+    right: i32,
+    above: i32,
+    under: i32,
+    touching: i32,
+    not_touching: i32,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ApiItemOnly {
+    durability_modifier: i32,
+    strength_requirement: i32,
+    dexterity_requirement: i32,
+    intelligence_requirement: i32,
+    defence_requirement: i32,
+    agility_requirement: i32,
+}
+
+impl IngredientDatabase {
+    /// Create an empty database using the given id table
+    pub fn new(id_keys: IdKeys) -> Self {
+        Self {
+            ingredients: HashMap::new(),
+            id_keys,
+        }
+    }
+
+    /// Load a database from the item json provided by the Wynncraft api
+    ///
+    /// The json is expected to be an object with item names as keys. Entries which are not ingredients are skipped.
+    pub fn from_json(json: &str, id_keys: IdKeys) -> Result<Self, DatabaseError> {
+        Self::from_items(serde_json::from_str(json)?, id_keys)
+    }
+
+    /// Load a database from a reader containing the item json provided by the Wynncraft api
+    ///
+    /// See [`IngredientDatabase::from_json`]
+    pub fn from_reader(reader: impl Read, id_keys: IdKeys) -> Result<Self, DatabaseError> {
+        Self::from_items(serde_json::from_reader(reader)?, id_keys)
+    }
+
+    fn from_items(
+        items: HashMap<String, serde_json::Value>,
+        id_keys: IdKeys,
+    ) -> Result<Self, DatabaseError> {
+        let mut out = Self::new(id_keys);
+
+        for (name, value) in items {
+            if value.get("type").and_then(|t| t.as_str()) != Some("ingredient") {
+                continue;
+            }
+
+            let ingredient: ApiIngredient = serde_json::from_value(value)?;
+            let ingredient = ingredient.into_ingredient(name, &out.id_keys)?;
+            out.insert(ingredient);
+        }
+
+        Ok(out)
+    }
+
+    /// Insert an ingredient into the database, replacing any previous ingredient with the same name
+    pub fn insert(&mut self, ingredient: Ingredient) {
+        self.ingredients.insert(ingredient.name.clone(), ingredient);
+    }
+
+    /// Get the ingredient with the given name
+    pub fn get(&self, name: &str) -> Option<&Ingredient> {
+        self.ingredients.get(name)
+    }
+
+    /// Get the ingredient with the given name or an error if the ingredient is not in the database
+    pub fn entry(&self, name: &str) -> Result<&Ingredient, DatabaseError> {
+        self.get(name)
+            .ok_or_else(|| DatabaseError::UnknownItem(name.to_string()))
+    }
+
+    /// The id table used by this database
+    pub fn id_keys(&self) -> &IdKeys {
+        &self.id_keys
+    }
+
+    /// Number of ingredients in the database
+    pub fn len(&self) -> usize {
+        self.ingredients.len()
+    }
+
+    /// Check if the database is empty
+    pub fn is_empty(&self) -> bool {
+        self.ingredients.is_empty()
+    }
+
+    /// Iterate over all the ingredients in the database
+    pub fn iter(&self) -> impl Iterator<Item = &Ingredient> {
+        self.ingredients.values()
+    }
+}
+
+impl ApiIngredient {
+    fn into_ingredient(self, name: String, id_keys: &IdKeys) -> Result<Ingredient, DatabaseError> {
+        let mut identifications = Vec::with_capacity(self.identifications.len());
+        for (stat_name, value) in &self.identifications {
+            let kind = id_keys
+                .id(stat_name)
+                .ok_or_else(|| DatabaseError::UnknownStatName {
+                    item: name.clone(),
+                    name: stat_name.clone(),
+                })?;
+
+            let (min, max) = match *value {
+                ApiRange::Fixed(v) => (v, v),
+                ApiRange::Rolled { min, max } => (min, max),
+            };
+            identifications.push(IngredientStat { kind, min, max });
+        }
+        // keep the order stable regardless of the order of the json object
+        identifications.sort_by_key(|s| s.kind);
+
+        let mods = self.ingredient_position_modifiers;
+        let item_only = self.item_only_ids;
+        let skills = [
+            item_only.strength_requirement,
+            item_only.dexterity_requirement,
+            item_only.intelligence_requirement,
+            item_only.defence_requirement,
+            item_only.agility_requirement,
+        ];
+
+        Ok(Ingredient {
+            name,
+            level: self.requirements.level,
+            identifications,
+            modifiers: PositionModifiers {
+                left: mods.left,
+                right: mods.right,
+                above: mods.above,
+                under: mods.under,
+                touching: mods.touching,
+                not_touching: mods.not_touching,
+            },
+            // the api gives the durability modifier in thousandths
+            durability: item_only.durability_modifier / 1000,
+            skills: SKILL_NAMES
+                .iter()
+                .zip(skills)
+                .filter(|(_, v)| *v != 0)
+                .map(|((skill, _), v)| (*skill, v))
+                .collect(),
+        })
+    }
+}
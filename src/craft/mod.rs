@@ -0,0 +1,388 @@
+//! Module for simulating crafting
+//!
+//! A [`Craft`] combines a [`Recipe`], the tiers of the materials used and up to six [`Ingredient`]s placed on the 2 by 3 crafting grid
+//! into a [`CraftedGear`] item which can then be encoded into an idstring like any other item.
+//!
+//! Ingredients boost the effectiveness of the other ingredients on the grid according to their [`PositionModifiers`]. The identifications
+//! of each ingredient are scaled by its effectiveness, while the durability and skill requirement modifiers are applied as is.
+//!
+//! Only gear recipes can be simulated. Recipes for potions, food and scrolls are not supported, converting them into a craft fails with
+//! [`CraftError::ConsumableRecipe`].
+//!
+//! Crafting in Wynncraft rolls the values of the item within a range. The simulator always produces the highest possible values,
+//! the same as [`CraftedStat::max`] which stores the value of an identification at full durability.
+//!
+//! With the `database` feature enabled ingredients can be loaded from the item json provided by the Wynncraft v3 api using an `IngredientDatabase`.
+
+use std::ops::Range;
+
+use crate::{
+    block::{
+        CraftedGearTypeData, CraftedIdentificationData, DamageData, DefenseData, DurabilityData,
+        RequirementsData,
+    },
+    item::{CraftedGear, GenericItem},
+    types::{AttackSpeed, ClassType, CraftedGearType, CraftedStat, ItemType, SkillType},
+};
+
+mod error;
+#[doc(inline)]
+pub use error::CraftError;
+
+#[cfg(feature = "database")]
+mod ingredients;
+#[cfg(feature = "database")]
+#[doc(inline)]
+pub use ingredients::IngredientDatabase;
+
+/// Number of ingredient slots on the crafting grid
+pub const GRID_SIZE: usize = 6;
+
+/// Stat multipliers of the material tiers, indexed by tier - 1
+pub const MATERIAL_MULTIPLIERS: [f64; 3] = [1.0, 1.25, 1.4];
+
+/// The static data of a crafting recipe
+///
+/// Recipes exist for each gear type and level range, such as a level 103-105 helmet. Recipes for consumables are not supported.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Recipe {
+    /// The type of gear the recipe creates
+    pub gear_type: CraftedGearType,
+    /// Lowest level of the recipe
+    pub min_level: u8,
+    /// Highest level of the recipe, which is also the level requirement of the crafted item
+    pub max_level: u8,
+    /// Base health of armour and accessories or base neutral damage of weapons
+    pub health_or_damage: Range<i32>,
+    /// Base durability of the crafted item
+    pub durability: Range<i32>,
+    /// Amount of each of the two materials the recipe uses
+    pub material_amounts: [u8; 2],
+}
+
+/// The static data of an ingredient
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Ingredient {
+    /// Name of the ingredient
+    pub name: String,
+    /// Level of the ingredient
+    pub level: u8,
+    /// Identifications the ingredient adds to the crafted item, sorted by id
+    pub identifications: Vec<IngredientStat>,
+    /// How the ingredient changes the effectiveness of the other ingredients
+    pub modifiers: PositionModifiers,
+    /// Durability the ingredient adds to or removes from the crafted item
+    pub durability: i32,
+    /// Skill point requirements the ingredient adds to the crafted item
+    pub skills: Vec<(SkillType, i32)>,
+}
+
+/// An identification of an ingredient
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct IngredientStat {
+    /// The id of the identification
+    pub kind: u8,
+    /// The lowest value of the identification
+    pub min: i32,
+    /// The highest value of the identification
+    pub max: i32,
+}
+
+/// Effectiveness changes an ingredient applies to the other ingredients on the crafting grid
+///
+/// Values are percentages added to the effectiveness of the affected ingredients.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct PositionModifiers {
+    /// Applied to the ingredients left of this ingredient on the same row
+    pub left: i32,
+    /// Applied to the ingredients right of this ingredient on the same row
+    pub right: i32,
+    /// Applied to the ingredients above this ingredient in the same column
+    pub above: i32,
+    /// Applied to the ingredients under this ingredient in the same column
+    pub under: i32,
+    /// Applied to the ingredients next to this ingredient, not counting diagonals
+    pub touching: i32,
+    /// Applied to the ingredients which are not next to this ingredient
+    pub not_touching: i32,
+}
+
+/// A planned craft
+///
+/// Ingredients are placed on the grid row by row, index 0 being the top left and index 5 the bottom right slot.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Craft<'a> {
+    /// The recipe used
+    pub recipe: &'a Recipe,
+    /// Tiers of the two materials, between 1 and 3
+    pub material_tiers: [u8; 2],
+    /// Attack speed of the item, only used for weapons. One of [`AttackSpeed::Slow`], [`AttackSpeed::Normal`] or [`AttackSpeed::Fast`]
+    pub attack_speed: AttackSpeed,
+    /// Ingredients on the crafting grid
+    pub ingredients: [Option<&'a Ingredient>; GRID_SIZE],
+    /// Name given to the crafted item
+    pub name: Option<String>,
+}
+
+impl<'a> Craft<'a> {
+    /// Create a new craft with no ingredients and tier 3 materials
+    pub fn new(recipe: &'a Recipe) -> Self {
+        Self {
+            recipe,
+            material_tiers: [3, 3],
+            attack_speed: AttackSpeed::Normal,
+            ingredients: [None; GRID_SIZE],
+            name: None,
+        }
+    }
+
+    /// Set the tiers of the materials
+    pub fn with_material_tiers(mut self, material_tiers: [u8; 2]) -> Self {
+        self.material_tiers = material_tiers;
+        self
+    }
+
+    /// Set the attack speed of the crafted weapon
+    pub fn with_attack_speed(mut self, attack_speed: AttackSpeed) -> Self {
+        self.attack_speed = attack_speed;
+        self
+    }
+
+    /// Set the ingredients on the crafting grid
+    pub fn with_ingredients(mut self, ingredients: [Option<&'a Ingredient>; GRID_SIZE]) -> Self {
+        self.ingredients = ingredients;
+        self
+    }
+
+    /// Set the name of the crafted item
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Calculate the effectiveness of each slot on the crafting grid as a percentage
+    pub fn effectiveness(&self) -> [i32; GRID_SIZE] {
+        let mut out = [100; GRID_SIZE];
+
+        for (i, ingredient) in self.ingredients.iter().enumerate() {
+            let Some(ingredient) = ingredient else {
+                continue;
+            };
+            let mods = ingredient.modifiers;
+            let (row, col) = (i / 2, i % 2);
+
+            for (j, eff) in out.iter_mut().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let (other_row, other_col) = (j / 2, j % 2);
+
+                if other_row == row {
+                    if other_col < col {
+                        *eff += mods.left;
+                    } else {
+                        *eff += mods.right;
+                    }
+                }
+                if other_col == col {
+                    if other_row < row {
+                        *eff += mods.above;
+                    } else {
+                        *eff += mods.under;
+                    }
+                }
+                if row.abs_diff(other_row) + col.abs_diff(other_col) == 1 {
+                    *eff += mods.touching;
+                } else {
+                    *eff += mods.not_touching;
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Calculate the stat multiplier of the materials
+    ///
+    /// The multiplier is the average of the multipliers of the material tiers weighed by the amount of each material.
+    pub fn material_multiplier(&self) -> Result<f64, CraftError> {
+        let mut total = 0.0;
+        let mut amount = 0.0;
+
+        for (tier, count) in self.material_tiers.iter().zip(self.recipe.material_amounts) {
+            let mult = tier
+                .checked_sub(1)
+                .and_then(|t| MATERIAL_MULTIPLIERS.get(t as usize))
+                .ok_or(CraftError::InvalidMaterialTier(*tier))?;
+
+            total += mult * count as f64;
+            amount += count as f64;
+        }
+
+        Ok(if amount == 0.0 { 1.0 } else { total / amount })
+    }
+
+    /// Calculate the requirements of the crafted item
+    pub fn requirements(&self) -> RequirementsData {
+        let mut skills: Vec<(SkillType, i32)> = Vec::new();
+
+        for (skill, value) in self.ingredients().flat_map(|i| &i.skills) {
+            match skills.iter_mut().find(|(s, _)| s == skill) {
+                Some((_, total)) => *total += value,
+                None => skills.push((*skill, *value)),
+            }
+        }
+        skills.retain(|(_, v)| *v > 0);
+        skills.sort_by_key(|(s, _)| u8::from(*s));
+
+        RequirementsData {
+            level: self.recipe.max_level,
+            class: weapon_class(self.recipe.gear_type),
+            skills,
+        }
+    }
+
+    /// Calculate the identifications of the crafted item
+    ///
+    /// The values of each ingredient are scaled by the effectiveness of its slot and summed up.
+    pub fn identifications(&self) -> CraftedIdentificationData {
+        let effectiveness = self.effectiveness();
+        let mut idents: Vec<CraftedStat> = Vec::new();
+
+        for (ingredient, eff) in self.ingredients.iter().zip(effectiveness) {
+            let Some(ingredient) = ingredient else {
+                continue;
+            };
+
+            for stat in &ingredient.identifications {
+                let scale = |v: i32| (v as f64 * eff as f64 / 100.0).floor() as i32;
+                // negative effectiveness flips the range around
+                let max = scale(stat.min).max(scale(stat.max));
+
+                match idents.iter_mut().find(|s| s.kind == stat.kind) {
+                    Some(ident) => ident.max += max,
+                    None => idents.push(CraftedStat {
+                        kind: stat.kind,
+                        max,
+                    }),
+                }
+            }
+        }
+
+        idents.retain(|s| s.max != 0);
+        idents.sort_by_key(|s| s.kind);
+
+        CraftedIdentificationData { idents }
+    }
+
+    /// Calculate the durability of the crafted item
+    pub fn durability(&self) -> DurabilityData {
+        let max =
+            self.recipe.durability.end + self.ingredients().map(|i| i.durability).sum::<i32>();
+        let max = max.max(1);
+
+        DurabilityData {
+            effect_strenght: 100,
+            current: max,
+            max,
+        }
+    }
+
+    /// Calculate the damage of the crafted item
+    ///
+    /// Returns [`None`] if the recipe is not for a weapon.
+    pub fn damage(&self) -> Result<Option<DamageData>, CraftError> {
+        if !is_weapon(self.recipe.gear_type) {
+            return Ok(None);
+        }
+
+        // slower weapons deal more damage per hit
+        let ratio = match self.attack_speed {
+            AttackSpeed::Slow => 2.05 / 1.5,
+            AttackSpeed::Normal => 1.0,
+            AttackSpeed::Fast => 2.05 / 2.5,
+            speed => return Err(CraftError::InvalidAttackSpeed(speed)),
+        };
+        let mult = self.material_multiplier()? * ratio;
+        let scale = |v: i32| (v as f64 * mult).floor() as i32;
+
+        let base = &self.recipe.health_or_damage;
+
+        Ok(Some(DamageData {
+            attack_speed: self.attack_speed,
+            damages: vec![(None, scale(base.start)..scale(base.end))],
+        }))
+    }
+
+    /// Calculate the defense of the crafted item
+    ///
+    /// Returns [`None`] if the recipe is for a weapon.
+    pub fn defense(&self) -> Result<Option<DefenseData>, CraftError> {
+        if is_weapon(self.recipe.gear_type) {
+            return Ok(None);
+        }
+
+        let mult = self.material_multiplier()?;
+
+        Ok(Some(DefenseData {
+            health: (self.recipe.health_or_damage.end as f64 * mult).floor() as i32,
+            defences: Vec::new(),
+        }))
+    }
+
+    /// Simulate the craft and create the crafted item
+    pub fn craft(&self) -> Result<CraftedGear, CraftError> {
+        for ingredient in self.ingredients() {
+            if ingredient.level > self.recipe.max_level {
+                return Err(CraftError::IngredientLevel {
+                    name: ingredient.name.clone(),
+                    level: ingredient.level,
+                    max: self.recipe.max_level,
+                });
+            }
+        }
+
+        let identifications = self.identifications();
+
+        Ok(CraftedGear::try_from(GenericItem {
+            kind: ItemType::CraftedGear,
+            name: self.name.clone(),
+            crafted_type: Some(CraftedGearTypeData(self.recipe.gear_type)),
+            crafted_durability: Some(self.durability()),
+            crafted_reqs: Some(self.requirements()),
+            crafted_damage: self.damage()?,
+            crafted_defense: self.defense()?,
+            crafted_identifications: (!identifications.idents.is_empty())
+                .then_some(identifications),
+            ..Default::default()
+        })?)
+    }
+
+    fn ingredients(&self) -> impl Iterator<Item = &'a Ingredient> + '_ {
+        self.ingredients.iter().flatten().copied()
+    }
+}
+
+const fn is_weapon(gear_type: CraftedGearType) -> bool {
+    matches!(
+        gear_type,
+        CraftedGearType::Spear
+            | CraftedGearType::Wand
+            | CraftedGearType::Dagger
+            | CraftedGearType::Bow
+            | CraftedGearType::Relik
+            | CraftedGearType::Weapon
+    )
+}
+
+/// The class which can use a weapon of the given type
+const fn weapon_class(gear_type: CraftedGearType) -> Option<ClassType> {
+    match gear_type {
+        CraftedGearType::Spear => Some(ClassType::Warrior),
+        CraftedGearType::Wand => Some(ClassType::Mage),
+        CraftedGearType::Dagger => Some(ClassType::Assasin),
+        CraftedGearType::Bow => Some(ClassType::Archer),
+        CraftedGearType::Relik => Some(ClassType::Shaman),
+        _ => None,
+    }
+}
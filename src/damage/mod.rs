@@ -0,0 +1,255 @@
+//! Module for calculating the damage of weapons
+//!
+//! A [`DamageCalculator`] takes the damage ranges of a weapon along with its powders, the skill points of the player
+//! and the damage identifications of the build and calculates the average damage of melee attacks and spells.
+//!
+//! Damage is calculated per damage type. The base damage of each type is boosted by the percentage bonuses which apply to it,
+//! such as [`DamageBonuses::melee_percent`] and the bonus of the element, and by the skill point bonus of the element.
+//! The skill point bonuses of defence and agility are reduced by [`SKILL_POINT_DAMAGE_MULTIPLIERS`].
+//! Raw bonuses are added to the total afterwards.
+//!
+//! Dexterity gives a chance of landing a critical hit, which deals double damage plus [`DamageBonuses::crit_damage_percent`].
+//! The average damage accounts for critical hits, the damage of a single critical hit is given separately.
+//! Melee damage per second is the damage per hit multiplied by [`AttackSpeed::multiplier`], while spells multiply the weapon damage
+//! by both the attack speed multiplier and the conversion of the spell.
+
+use crate::{
+    block::DamageData,
+    build::SkillPoints,
+    types::{AttackSpeed, Element, IdKeys, Powder},
+};
+
+/// Skill points past this amount do not give any more bonuses
+pub const SKILL_POINT_CAP: i32 = 150;
+
+/// Multipliers of the skill point damage bonus of each element, defence and agility give a reduced bonus
+pub const SKILL_POINT_DAMAGE_MULTIPLIERS: [f64; 5] = [1.0, 1.0, 1.0, 0.867, 0.951];
+
+/// Damage types in the order of [`DamageResult::per_type`], neutral first
+const DAMAGE_TYPES: [Option<Element>; 6] = [
+    None,
+    Some(Element::Earth),
+    Some(Element::Thunder),
+    Some(Element::Water),
+    Some(Element::Fire),
+    Some(Element::Air),
+];
+
+/// Damage identifications of a build
+///
+/// Percentages are given as whole numbers, eg. 20 for +20%.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamageBonuses {
+    /// Bonus to all damage in percent (`damage`)
+    pub damage_percent: i32,
+    /// Raw damage added to every attack and spell (`rawDamage`)
+    pub damage_raw: i32,
+    /// Bonus to melee damage in percent (`mainAttackDamage`)
+    pub melee_percent: i32,
+    /// Raw damage added to melee attacks (`rawMainAttackDamage`)
+    pub melee_raw: i32,
+    /// Bonus to spell damage in percent (`spellDamage`)
+    pub spell_percent: i32,
+    /// Raw damage added to spells, scaled by the conversion of the spell (`rawSpellDamage`)
+    pub spell_raw: i32,
+    /// Bonus to the damage of each element in percent (`earthDamage` etc.), in the order of the element ids
+    pub element_percent: [i32; 5],
+    /// Number of tiers the attack speed is raised by (`rawAttackSpeed`)
+    pub attack_speed: i32,
+    /// Bonus to the damage of critical hits in percent, on top of the doubled damage (`criticalDamageBonus`)
+    pub crit_damage_percent: i32,
+}
+
+/// The calculated damage of an attack
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DamageResult {
+    /// Average damage per hit, including critical hits
+    pub per_hit: f64,
+    /// Average damage per second, including critical hits
+    pub per_second: f64,
+    /// Average damage of a critical hit
+    pub crit_per_hit: f64,
+    /// Average damage per hit of each damage type for hits which are not critical, before raw bonuses.
+    /// Neutral first followed by the elements in order
+    pub per_type: [f64; 6],
+}
+
+/// Calculator for the damage of a weapon
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DamageCalculator {
+    damage: DamageData,
+    skill_points: SkillPoints,
+    bonuses: DamageBonuses,
+}
+
+/// Calculate the bonus given by an amount of skill points
+///
+/// Returns the bonus as a fraction, eg. `0.808` for the maximum bonus of 150 points.
+pub fn skill_point_percentage(points: i32) -> f64 {
+    if points <= 0 {
+        return 0.0;
+    }
+    let x = points.min(SKILL_POINT_CAP) as f64;
+
+    (-0.0000000166 * x.powi(4) + 0.0000122614 * x.powi(3) - 0.0044972 * x.powi(2)
+        + 0.9931 * x
+        + 0.0093)
+        / 100.0
+}
+
+impl DamageBonuses {
+    /// Collect the damage bonuses out of a list of identifications
+    ///
+    /// The identifications are pairs of ids and values, such as the ones returned by `Build::identifications`.
+    /// Identifications which do not affect damage are ignored.
+    pub fn from_identifications(
+        identifications: impl IntoIterator<Item = (u8, i32)>,
+        id_keys: &IdKeys,
+    ) -> Self {
+        let mut out = Self::default();
+
+        for (kind, value) in identifications {
+            let Some(name) = id_keys.name(kind) else {
+                continue;
+            };
+
+            let field = match name {
+                "damage" => &mut out.damage_percent,
+                "rawDamage" => &mut out.damage_raw,
+                "mainAttackDamage" => &mut out.melee_percent,
+                "rawMainAttackDamage" => &mut out.melee_raw,
+                "spellDamage" => &mut out.spell_percent,
+                "rawSpellDamage" => &mut out.spell_raw,
+                "earthDamage" => &mut out.element_percent[Element::Earth as usize],
+                "thunderDamage" => &mut out.element_percent[Element::Thunder as usize],
+                "waterDamage" => &mut out.element_percent[Element::Water as usize],
+                "fireDamage" => &mut out.element_percent[Element::Fire as usize],
+                "airDamage" => &mut out.element_percent[Element::Air as usize],
+                "rawAttackSpeed" => &mut out.attack_speed,
+                "criticalDamageBonus" => &mut out.crit_damage_percent,
+                _ => continue,
+            };
+            *field += value;
+        }
+
+        out
+    }
+}
+
+impl DamageCalculator {
+    /// Create a new calculator for a weapon with the given damages
+    pub fn new(damage: &DamageData) -> Self {
+        Self {
+            damage: damage.clone(),
+            skill_points: SkillPoints::default(),
+            bonuses: DamageBonuses::default(),
+        }
+    }
+
+    /// Apply powders to the weapon
+    ///
+    /// See [`DamageData::with_powders`]
+    pub fn with_powders(mut self, powders: &[Powder]) -> Self {
+        self.damage = self.damage.with_powders(powders);
+        self
+    }
+
+    /// Set the total skill points of the player
+    pub fn with_skill_points(mut self, skill_points: SkillPoints) -> Self {
+        self.skill_points = skill_points;
+        self
+    }
+
+    /// Set the damage bonuses of the build
+    pub fn with_bonuses(mut self, bonuses: DamageBonuses) -> Self {
+        self.bonuses = bonuses;
+        self
+    }
+
+    /// The damages of the weapon, including powders
+    pub fn damage(&self) -> &DamageData {
+        &self.damage
+    }
+
+    /// The attack speed of the weapon after attack speed bonuses
+    pub fn attack_speed(&self) -> AttackSpeed {
+        self.damage.attack_speed.shifted(self.bonuses.attack_speed)
+    }
+
+    /// The chance of landing a critical hit given by dexterity, as a fraction
+    pub fn crit_chance(&self) -> f64 {
+        skill_point_percentage(self.skill_points.0[Element::Thunder as usize])
+    }
+
+    /// Calculate the damage of melee attacks
+    pub fn melee(&self) -> DamageResult {
+        let per_type = self.per_type(1.0, self.bonuses.melee_percent);
+        let normal = per_type.iter().sum::<f64>()
+            + (self.bonuses.melee_raw + self.bonuses.damage_raw) as f64;
+        let (per_hit, crit_per_hit) = self.with_crits(normal);
+
+        DamageResult {
+            per_hit,
+            per_second: per_hit * self.attack_speed().multiplier(),
+            crit_per_hit,
+            per_type,
+        }
+    }
+
+    /// Calculate the damage of a spell
+    ///
+    /// The conversion is the percentage of weapon damage the spell deals, eg. 150 for a spell dealing 150% damage.
+    /// Damage per second is calculated from the number of times the spell is cast each second.
+    pub fn spell(&self, conversion: i32, casts_per_second: f64) -> DamageResult {
+        let conversion = conversion as f64 / 100.0;
+        let mult = self.attack_speed().multiplier() * conversion;
+
+        let per_type = self.per_type(mult, self.bonuses.spell_percent);
+        let normal = per_type.iter().sum::<f64>()
+            + (self.bonuses.spell_raw + self.bonuses.damage_raw) as f64 * conversion;
+        let (per_hit, crit_per_hit) = self.with_crits(normal);
+
+        DamageResult {
+            per_hit,
+            per_second: per_hit * casts_per_second,
+            crit_per_hit,
+            per_type,
+        }
+    }
+
+    /// Calculate the average damage per hit including critical hits and the damage of a critical hit
+    fn with_crits(&self, normal: f64) -> (f64, f64) {
+        let crit = normal * (2.0 + self.bonuses.crit_damage_percent as f64 / 100.0);
+        let chance = self.crit_chance();
+
+        (normal * (1.0 - chance) + crit * chance, crit)
+    }
+
+    /// Calculate the average damage of each damage type
+    fn per_type(&self, mult: f64, percent: i32) -> [f64; 6] {
+        let mut out = [0.0; 6];
+
+        for (i, kind) in DAMAGE_TYPES.into_iter().enumerate() {
+            let base: f64 = self
+                .damage
+                .damages
+                .iter()
+                .filter(|(elem, _)| *elem == kind)
+                .map(|(_, range)| (range.start + range.end) as f64 / 2.0)
+                .sum();
+
+            let mut bonus = (self.bonuses.damage_percent + percent) as f64 / 100.0;
+            if let Some(elem) = kind {
+                bonus += self.bonuses.element_percent[elem as usize] as f64 / 100.0;
+                bonus += skill_point_percentage(self.skill_points.0[elem as usize])
+                    * SKILL_POINT_DAMAGE_MULTIPLIERS[elem as usize];
+            }
+
+            out[i] = (base * mult * (1.0 + bonus)).max(0.0);
+        }
+
+        out
+    }
+}
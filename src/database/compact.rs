@@ -0,0 +1,146 @@
+use crate::{
+    block::IdentificationData,
+    item::{CharmItem, GearItem, TomeItem},
+    types::ItemType,
+};
+
+use super::{DatabaseError, ItemDatabase, ItemEntry, StatMismatch};
+
+impl ItemEntry {
+    /// Find the identifications which do not match this entry
+    ///
+    /// An identification does not match if its base value differs from the base value of this entry,
+    /// if it does not exist on this entry, or if it is pre-identified on only one of the two.
+    /// Pre-identified identifications of this entry which are missing from the given identifications are also reported when extended encoding is used.
+    ///
+    /// Identifications without a base value are only checked for existence, as there is nothing else to compare.
+    pub fn mismatches(&self, idents: &IdentificationData) -> Vec<StatMismatch> {
+        let mut out = Vec::new();
+
+        for stat in &idents.identifications {
+            let entry = self.stat(stat.kind);
+
+            let matches = match entry {
+                Some(entry) => {
+                    entry.pre_identified == stat.pre_identified()
+                        && stat.base.is_none_or(|b| b == entry.base)
+                }
+                None => false,
+            };
+
+            if !matches {
+                out.push(StatMismatch {
+                    kind: stat.kind,
+                    encoded: stat.base,
+                    current: entry.map(|e| e.base),
+                });
+            }
+        }
+
+        // pre-identified identifications are only encoded when using extended encoding
+        let preids = self
+            .identifications
+            .iter()
+            .filter(|e| e.pre_identified && idents.extended_encoding);
+        for entry in preids {
+            if !idents.identifications.iter().any(|i| i.kind == entry.kind) {
+                out.push(StatMismatch {
+                    kind: entry.kind,
+                    encoded: None,
+                    current: Some(entry.base),
+                });
+            }
+        }
+
+        out
+    }
+
+    /// Convert the given identifications into their compact non extended form
+    ///
+    /// The compact form only contains the rolls of the identifications, with the base values and
+    /// pre-identified identifications being dropped as they can be restored from this entry using [`ItemEntry::hydrate`].
+    ///
+    /// # Errors
+    /// Returns [`DatabaseError::BaseMismatch`] if the identifications do not match this entry, as the compact form could
+    /// then not be restored to the original identifications. This is the case for outdated or modified items.
+    pub fn compact(
+        &self,
+        idents: &IdentificationData,
+    ) -> Result<IdentificationData, DatabaseError> {
+        let mismatches = self.mismatches(idents);
+        if !mismatches.is_empty() {
+            return Err(DatabaseError::BaseMismatch {
+                item: self.name.clone(),
+                stats: mismatches,
+            });
+        }
+
+        Ok(IdentificationData {
+            identifications: idents
+                .identifications
+                .iter()
+                .filter(|s| !s.pre_identified())
+                .map(|s| {
+                    let mut s = s.clone();
+                    s.base = None;
+                    s
+                })
+                .collect(),
+            extended_encoding: false,
+        })
+    }
+}
+
+impl ItemDatabase {
+    /// Convert the identifications of the named item into their compact non extended form
+    ///
+    /// See [`ItemEntry::compact`]
+    pub fn compact(
+        &self,
+        name: &str,
+        idents: &IdentificationData,
+    ) -> Result<IdentificationData, DatabaseError> {
+        self.entry(name)?.compact(idents)
+    }
+
+    fn compact_item(
+        &self,
+        name: &str,
+        kind: ItemType,
+        idents: Option<&mut IdentificationData>,
+    ) -> Result<&ItemEntry, DatabaseError> {
+        let entry = self.entry(name)?;
+        entry.check_kind(kind)?;
+
+        if let Some(idents) = idents {
+            *idents = entry.compact(idents)?;
+        }
+
+        Ok(entry)
+    }
+}
+
+impl GearItem {
+    /// Convert the identifications of this item into their compact non extended form
+    ///
+    /// The item is not modified if an error is returned.
+    ///
+    /// See [`ItemEntry::compact`]
+    pub fn compact<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.compact_item(&self.name, ItemType::Gear, self.identifications.as_mut())
+    }
+}
+
+impl TomeItem {
+    /// See [`GearItem::compact`]
+    pub fn compact<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.compact_item(&self.name, ItemType::Tome, self.identifications.as_mut())
+    }
+}
+
+impl CharmItem {
+    /// See [`GearItem::compact`]
+    pub fn compact<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.compact_item(&self.name, ItemType::Charm, self.identifications.as_mut())
+    }
+}
@@ -0,0 +1,74 @@
+use thiserror::Error;
+
+use crate::types::ItemType;
+
+use super::StatMismatch;
+
+/// Errors that can occur when loading or using an [`ItemDatabase`](super::ItemDatabase)
+#[derive(Error, Debug)]
+pub enum DatabaseError {
+    /// The given json could not be parsed
+    #[error("Failed to parse json: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A field of an item in the database contains a value which could not be understood
+    #[error("Item `{item}` has an invalid value `{value}` for field `{field}`")]
+    InvalidField {
+        /// Name of the item
+        item: String,
+        /// Name of the field
+        field: &'static str,
+        /// The value which could not be understood
+        value: String,
+    },
+
+    /// An item in the database has an identification which is missing from the id table
+    #[error("Item `{item}` has the identification `{name}` which is not in the id table")]
+    UnknownStatName {
+        /// Name of the item
+        item: String,
+        /// Name of the identification
+        name: String,
+    },
+
+    /// An identification id is missing from the id table
+    #[error("Identification id: {0} is not in the id table")]
+    UnknownStatId(u8),
+
+    /// A shiny stat id is missing from the shiny stat table
+    #[error("Shiny stat id: {0} is not in the shiny stat table")]
+    UnknownShinyStat(u8),
+
+    /// The item could not be found in the database
+    #[error("Item `{0}` was not found in the database")]
+    UnknownItem(String),
+
+    /// The item has an identification which the database entry of the item does not have
+    #[error("Identification id: {kind} was not found on item `{item}` in the database")]
+    UnknownStat {
+        /// Name of the item
+        item: String,
+        /// Id of the identification
+        kind: u8,
+    },
+
+    /// The type of the item does not match the type of the item in the database
+    #[error("Item `{item}` is a {found:?} in the database but a {expected:?} was expected")]
+    WrongItemType {
+        /// Name of the item
+        item: String,
+        /// The type of the item which was given
+        expected: ItemType,
+        /// The type of the item in the database
+        found: ItemType,
+    },
+
+    /// The identifications of the item do not match the database entry of the item
+    #[error("Item `{item}` has {} identifications which do not match the database", .stats.len())]
+    BaseMismatch {
+        /// Name of the item
+        item: String,
+        /// The identifications which do not match
+        stats: Vec<StatMismatch>,
+    },
+}
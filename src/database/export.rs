@@ -0,0 +1,605 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    block::{
+        CraftedConsumableTypeData, CraftedGearTypeData, CraftedIdentificationData, DamageData,
+        DefenseData, DurabilityData, EffectsData, IdentificationData, PowderData, RerollData,
+        ShinyData, UsesData,
+    },
+    item::GenericItem,
+    types::{CraftedStat, Effect, EffectType, ItemType, Powder, RollType, Stat},
+};
+
+use super::{
+    schema::{
+        invalid, name_of, parse_name, JsonRequirements, ATTACK_SPEED_NAMES, CONSUMABLE_TYPE_NAMES,
+        ELEMENT_NAMES, GEAR_TYPE_NAMES, TIER_NAMES,
+    },
+    DatabaseError, ItemDatabase,
+};
+
+const ITEM_TYPE_NAMES: [(ItemType, &str); 5] = [
+    (ItemType::Gear, "gear"),
+    (ItemType::Tome, "tome"),
+    (ItemType::Charm, "charm"),
+    (ItemType::CraftedGear, "craftedGear"),
+    (ItemType::CraftedConsu, "craftedConsumable"),
+];
+
+const EFFECT_NAMES: [(EffectType, &str); 3] = [
+    (EffectType::Heal, "heal"),
+    (EffectType::Mana, "mana"),
+    (EffectType::Duration, "duration"),
+];
+
+/// An item in a json format modeled after the Wynncraft api
+///
+/// Unlike the serde implementations of the item types, which mirror the encoded data, this format uses
+/// the names of identifications, elements and shiny stats and contains the computed values of the identifications.
+///
+/// See [`ItemDatabase::export`] and [`ItemDatabase::import`]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonItem {
+    /// Name of the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Type of the item
+    ///
+    /// One of `gear`, `tome`, `charm`, `craftedGear` or `craftedConsumable`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Tier of the item if the item is in the database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rarity: Option<String>,
+    /// Whether or not the identifications are encoded using extended encoding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_encoding: Option<bool>,
+    /// Identifications of the item by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifications: Option<JsonMap<JsonStat>>,
+    /// Number of powder slots on the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub powder_slots: Option<u8>,
+    /// Powders applied to the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub powders: Option<Vec<JsonPowder>>,
+    /// Reroll count of the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerolls: Option<u8>,
+    /// Shiny stat of the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shiny_stat: Option<JsonShiny>,
+    /// Gear type of a crafted item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gear_type: Option<String>,
+    /// Type of a crafted consumable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumable_type: Option<String>,
+    /// Durability of a crafted item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub durability: Option<JsonDurability>,
+    /// Requirements of the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<JsonRequirements>,
+    /// Attack speed of a weapon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attack_speed: Option<String>,
+    /// Base damages, health and defences of the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<JsonMap<JsonBase>>,
+    /// Uses of a crafted consumable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uses: Option<JsonUses>,
+    /// Effects of a crafted consumable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effects: Option<JsonMap<i32>>,
+}
+
+/// A json object which keeps the order of its entries
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct JsonMap<V>(pub Vec<(String, V)>);
+
+/// An identification of an exported item
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum JsonStat {
+    /// A pre-identified identification or an identification of a crafted item
+    Fixed(i32),
+    /// A rolled identification
+    Rolled {
+        /// The computed value of the identification if the base value is known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<i32>,
+        /// The base value of the identification if it is encoded in the item
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base: Option<i32>,
+        /// The roll percentage of the identification
+        roll: u8,
+    },
+}
+
+/// A base value of an exported item
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum JsonBase {
+    /// A fixed value such as health or a defence
+    Fixed(i32),
+    /// A range of values such as a damage
+    Range {
+        /// Minimum value
+        min: i32,
+        /// Maximum value
+        max: i32,
+    },
+}
+
+/// A powder of an exported item
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct JsonPowder {
+    /// Name of the element of the powder
+    pub element: String,
+    /// Tier of the powder
+    pub tier: u8,
+}
+
+/// The shiny stat of an exported item
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct JsonShiny {
+    /// Key of the shiny stat
+    pub name: String,
+    /// Value of the shiny stat
+    pub value: i64,
+    /// Number of shiny rerolls
+    #[serde(default)]
+    pub rerolls: u8,
+}
+
+/// The durability of an exported crafted item
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct JsonDurability {
+    /// Current durability
+    pub current: i32,
+    /// Maximum durability
+    pub max: i32,
+    /// Effectiveness of the identifications in percent
+    pub effectiveness: u8,
+}
+
+/// The uses of an exported crafted consumable
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct JsonUses {
+    /// Remaining uses
+    pub current: u8,
+    /// Maximum uses
+    pub max: u8,
+}
+
+impl ItemDatabase {
+    /// Export an item into the json format modeled after the Wynncraft api
+    ///
+    /// If the item is in the database the values of the identifications are computed even if the item does not contain base values
+    /// and the rarity, requirements and base stats of the item are included.
+    ///
+    /// # Errors
+    /// Returns [`DatabaseError::UnknownStatId`] or [`DatabaseError::UnknownShinyStat`] if a name is not in the id or shiny tables.
+    pub fn export(&self, item: &GenericItem) -> Result<JsonItem, DatabaseError> {
+        let entry = item
+            .name
+            .as_deref()
+            .and_then(|name| self.get(name))
+            .filter(|entry| entry.kind == item.kind);
+        let crafted = matches!(item.kind, ItemType::CraftedGear | ItemType::CraftedConsu);
+
+        let stat_name = |kind: u8| {
+            self.id_keys
+                .name(kind)
+                .map(str::to_string)
+                .ok_or(DatabaseError::UnknownStatId(kind))
+        };
+
+        let identifications = if crafted {
+            item.crafted_identifications
+                .as_ref()
+                .map(|idents| {
+                    idents
+                        .idents
+                        .iter()
+                        .map(|s| Ok((stat_name(s.kind)?, JsonStat::Fixed(s.max))))
+                        .collect::<Result<_, DatabaseError>>()
+                        .map(JsonMap)
+                })
+                .transpose()?
+        } else {
+            item.identifications
+                .as_ref()
+                .map(|idents| {
+                    let mut out = Vec::new();
+
+                    for stat in &idents.identifications {
+                        let base = stat.base.or_else(|| Some(entry?.stat(stat.kind)?.base));
+
+                        let value = match stat.roll {
+                            RollType::PreIdentified => match base {
+                                Some(base) => JsonStat::Fixed(base),
+                                // pre-identified values without a base value can not be encoded either
+                                None => continue,
+                            },
+                            RollType::Value(roll) => JsonStat::Rolled {
+                                value: Stat {
+                                    base,
+                                    ..stat.clone()
+                                }
+                                .value(),
+                                base: stat.base,
+                                roll,
+                            },
+                        };
+
+                        out.push((stat_name(stat.kind)?, value));
+                    }
+
+                    Ok::<_, DatabaseError>(JsonMap(out))
+                })
+                .transpose()?
+        };
+
+        let shiny_stat = item
+            .shiny
+            .as_ref()
+            .map(|shiny| {
+                Ok::<_, DatabaseError>(JsonShiny {
+                    name: self
+                        .shiny_stats
+                        .get(shiny.id)
+                        .ok_or(DatabaseError::UnknownShinyStat(shiny.id))?
+                        .key
+                        .clone(),
+                    value: shiny.val,
+                    rerolls: shiny.rr,
+                })
+            })
+            .transpose()?;
+
+        // base values come from the item for crafted items and from the database for other items
+        let (damage, health, defences) = match (crafted, entry) {
+            (true, _) => (
+                item.crafted_damage.as_ref(),
+                item.crafted_defense.as_ref().map(|d| d.health),
+                item.crafted_defense
+                    .as_ref()
+                    .map(|d| d.defences.as_slice())
+                    .unwrap_or_default(),
+            ),
+            (false, Some(entry)) => (
+                entry.damage.as_ref(),
+                Some(entry.health).filter(|h| *h != 0),
+                entry.defences.as_slice(),
+            ),
+            (false, None) => (None, None, Default::default()),
+        };
+
+        let mut base = Vec::new();
+        if let Some(damage) = damage {
+            for (elem, range) in &damage.damages {
+                let elem_name = elem.map_or("", |e| name_of(&ELEMENT_NAMES, e));
+                base.push((
+                    format!("base{elem_name}Damage"),
+                    JsonBase::Range {
+                        min: range.start,
+                        max: range.end,
+                    },
+                ));
+            }
+        }
+        if let Some(health) = health {
+            base.push(("baseHealth".to_string(), JsonBase::Fixed(health)));
+        }
+        for (elem, value) in defences {
+            base.push((
+                format!("base{}Defence", name_of(&ELEMENT_NAMES, *elem)),
+                JsonBase::Fixed(*value),
+            ));
+        }
+
+        let requirements = match (crafted, entry) {
+            (true, _) => item.crafted_reqs.as_ref(),
+            (false, entry) => entry.map(|e| &e.requirements),
+        };
+
+        Ok(JsonItem {
+            name: item.name.clone(),
+            kind: name_of(&ITEM_TYPE_NAMES, item.kind).to_string(),
+            rarity: entry.map(|e| name_of(&TIER_NAMES, e.tier).to_string()),
+            extended_encoding: item.identifications.as_ref().map(|i| i.extended_encoding),
+            identifications,
+            powder_slots: item.powders.as_ref().map(|p| p.powder_slots),
+            powders: item.powders.as_ref().map(|p| {
+                p.powders
+                    .iter()
+                    .map(|pow| JsonPowder {
+                        element: name_of(&ELEMENT_NAMES, pow.element()).to_lowercase(),
+                        tier: pow.tier(),
+                    })
+                    .collect()
+            }),
+            rerolls: item.rerolls.as_ref().map(|r| r.0),
+            shiny_stat,
+            gear_type: item
+                .crafted_type
+                .as_ref()
+                .map(|t| name_of(&GEAR_TYPE_NAMES, t.0).to_string()),
+            consumable_type: item
+                .crafted_consumable_type
+                .as_ref()
+                .map(|t| name_of(&CONSUMABLE_TYPE_NAMES, t.0).to_string()),
+            durability: item.crafted_durability.as_ref().map(|d| JsonDurability {
+                current: d.current,
+                max: d.max,
+                effectiveness: d.effect_strenght,
+            }),
+            requirements: requirements.map(JsonRequirements::from),
+            attack_speed: damage.map(|d| name_of(&ATTACK_SPEED_NAMES, d.attack_speed).to_string()),
+            base: (!base.is_empty()).then_some(JsonMap(base)),
+            uses: item.crafted_uses.as_ref().map(|u| JsonUses {
+                current: u.current,
+                max: u.max,
+            }),
+            effects: item.crafted_effects.as_ref().map(|e| {
+                JsonMap(
+                    e.effects
+                        .iter()
+                        .map(|e| (name_of(&EFFECT_NAMES, e.kind).to_string(), e.value))
+                        .collect(),
+                )
+            }),
+        })
+    }
+
+    /// Export an item into a json string
+    ///
+    /// See [`ItemDatabase::export`]
+    pub fn export_json(&self, item: &GenericItem) -> Result<String, DatabaseError> {
+        Ok(serde_json::to_string(&self.export(item)?)?)
+    }
+
+    /// Import an item from the json format modeled after the Wynncraft api
+    ///
+    /// This is the reverse of [`ItemDatabase::export`]. Computed values and the data which is only exported from the database,
+    /// such as the rarity or the requirements of non crafted items, are ignored.
+    pub fn import(&self, json: &JsonItem) -> Result<GenericItem, DatabaseError> {
+        let item_name = json.name.as_deref().unwrap_or_default();
+        let kind = parse_name(&ITEM_TYPE_NAMES, item_name, "type", &json.kind)?;
+        let crafted = matches!(kind, ItemType::CraftedGear | ItemType::CraftedConsu);
+
+        let stat_id = |name: &str| {
+            self.id_keys
+                .id(name)
+                .ok_or_else(|| DatabaseError::UnknownStatName {
+                    item: item_name.to_string(),
+                    name: name.to_string(),
+                })
+        };
+
+        let mut out = GenericItem {
+            kind,
+            name: json.name.clone(),
+            rerolls: json.rerolls.map(RerollData),
+            ..Default::default()
+        };
+
+        if let Some(idents) = &json.identifications {
+            if crafted {
+                let mut stats = Vec::new();
+                for (name, value) in &idents.0 {
+                    let JsonStat::Fixed(max) = value else {
+                        return Err(invalid(item_name, "identifications", name));
+                    };
+                    stats.push(CraftedStat {
+                        kind: stat_id(name)?,
+                        max: *max,
+                    });
+                }
+                out.crafted_identifications = Some(CraftedIdentificationData { idents: stats });
+            } else {
+                let mut stats = Vec::new();
+                for (name, value) in &idents.0 {
+                    let (base, roll) = match *value {
+                        JsonStat::Fixed(base) => (Some(base), RollType::PreIdentified),
+                        JsonStat::Rolled { base, roll, .. } => (base, RollType::Value(roll)),
+                    };
+                    stats.push(Stat {
+                        kind: stat_id(name)?,
+                        base,
+                        roll,
+                    });
+                }
+                out.identifications = Some(IdentificationData {
+                    identifications: stats,
+                    extended_encoding: json.extended_encoding.unwrap_or_default(),
+                });
+            }
+        }
+
+        if json.powder_slots.is_some() || json.powders.is_some() {
+            let mut powders = Vec::new();
+            for powder in json.powders.iter().flatten() {
+                let elem = ELEMENT_NAMES
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(&powder.element))
+                    .ok_or_else(|| invalid(item_name, "powders", &powder.element))?
+                    .0;
+                powders.push(
+                    Powder::new(elem, powder.tier)
+                        .map_err(|e| invalid(item_name, "powders", &e.0.to_string()))?,
+                );
+            }
+
+            out.powders = Some(PowderData {
+                powder_slots: json.powder_slots.unwrap_or_default(),
+                powders,
+            });
+        }
+
+        if let Some(shiny) = &json.shiny_stat {
+            out.shiny = Some(ShinyData {
+                id: self
+                    .shiny_stats
+                    .by_key(&shiny.name)
+                    .ok_or_else(|| invalid(item_name, "shinyStat", &shiny.name))?
+                    .id,
+                rr: shiny.rerolls,
+                val: shiny.value,
+            });
+        }
+
+        // the rest of the data is only encoded for crafted items
+        if !crafted {
+            return Ok(out);
+        }
+
+        out.crafted_type = json
+            .gear_type
+            .as_deref()
+            .map(|t| parse_name(&GEAR_TYPE_NAMES, item_name, "gearType", t))
+            .transpose()?
+            .map(CraftedGearTypeData);
+        out.crafted_consumable_type = json
+            .consumable_type
+            .as_deref()
+            .map(|t| parse_name(&CONSUMABLE_TYPE_NAMES, item_name, "consumableType", t))
+            .transpose()?
+            .map(CraftedConsumableTypeData);
+        out.crafted_durability = json.durability.as_ref().map(|d| DurabilityData {
+            effect_strenght: d.effectiveness,
+            current: d.current,
+            max: d.max,
+        });
+        out.crafted_reqs = json
+            .requirements
+            .as_ref()
+            .map(|r| r.to_requirements(item_name))
+            .transpose()?;
+        out.crafted_uses = json.uses.as_ref().map(|u| UsesData {
+            current: u.current,
+            max: u.max,
+        });
+        out.crafted_effects = json
+            .effects
+            .as_ref()
+            .map(|effects| {
+                effects
+                    .0
+                    .iter()
+                    .map(|(name, value)| {
+                        Ok(Effect {
+                            kind: parse_name(&EFFECT_NAMES, item_name, "effects", name)?,
+                            value: *value,
+                        })
+                    })
+                    .collect::<Result<_, DatabaseError>>()
+                    .map(|effects| EffectsData { effects })
+            })
+            .transpose()?;
+
+        let mut damages = Vec::new();
+        let mut defense = None;
+        for (key, value) in json.base.iter().flat_map(|b| &b.0) {
+            let bad_key = || invalid(item_name, "base", key);
+
+            match (key.strip_prefix("base"), *value) {
+                (Some("Health"), JsonBase::Fixed(health)) => {
+                    defense
+                        .get_or_insert(DefenseData {
+                            health: 0,
+                            defences: Vec::new(),
+                        })
+                        .health = health;
+                }
+                (Some("Damage"), JsonBase::Range { min, max }) => damages.push((None, min..max)),
+                (Some(rest), value) => {
+                    let (elem, suffix) = ELEMENT_NAMES
+                        .iter()
+                        .find_map(|(elem, name)| Some((elem, rest.strip_prefix(name)?)))
+                        .ok_or_else(bad_key)?;
+
+                    match (suffix, value) {
+                        ("Damage", JsonBase::Range { min, max }) => {
+                            damages.push((Some(*elem), min..max))
+                        }
+                        ("Defence", JsonBase::Fixed(def)) => defense
+                            .get_or_insert(DefenseData {
+                                health: 0,
+                                defences: Vec::new(),
+                            })
+                            .defences
+                            .push((*elem, def)),
+                        _ => return Err(bad_key()),
+                    }
+                }
+                (None, _) => return Err(bad_key()),
+            }
+        }
+
+        out.crafted_damage = json
+            .attack_speed
+            .as_deref()
+            .map(|speed| {
+                Ok::<_, DatabaseError>(DamageData {
+                    attack_speed: parse_name(&ATTACK_SPEED_NAMES, item_name, "attackSpeed", speed)?,
+                    damages,
+                })
+            })
+            .transpose()?;
+        out.crafted_defense = defense;
+
+        Ok(out)
+    }
+
+    /// Import an item from a json string
+    ///
+    /// See [`ItemDatabase::import`]
+    pub fn import_json(&self, json: &str) -> Result<GenericItem, DatabaseError> {
+        self.import(&serde_json::from_str(json)?)
+    }
+}
+
+impl<V: Serialize> Serialize for JsonMap<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for JsonMap<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for MapVisitor<V> {
+            type Value = JsonMap<V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut out = Vec::with_capacity(access.size_hint().unwrap_or_default());
+                while let Some(entry) = access.next_entry()? {
+                    out.push(entry);
+                }
+                Ok(JsonMap(out))
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    block::IdentificationData,
+    item::{CharmItem, GearItem, TomeItem},
+    types::{ItemType, RollType, Stat},
+};
+
+use super::{DatabaseError, ItemDatabase, ItemEntry};
+
+impl ItemEntry {
+    /// Fill in the base values of the given identifications from this entry
+    ///
+    /// Identifications which are missing a base value are given the base value from this entry and
+    /// pre-identified identifications, which are not encoded without extended encoding, are added back.
+    /// Base values which are already present are left untouched.
+    ///
+    /// The identifications are not modified if an error is returned.
+    ///
+    /// # Errors
+    /// Returns [`DatabaseError::UnknownStat`] if an identification is not present on this entry.
+    pub fn hydrate(&self, idents: &mut IdentificationData) -> Result<(), DatabaseError> {
+        let mut bases = Vec::with_capacity(idents.identifications.len());
+        for stat in &idents.identifications {
+            let entry = self
+                .stat(stat.kind)
+                .ok_or_else(|| DatabaseError::UnknownStat {
+                    item: self.name.clone(),
+                    kind: stat.kind,
+                })?;
+            bases.push(entry.base);
+        }
+
+        for (stat, base) in idents.identifications.iter_mut().zip(bases) {
+            stat.base.get_or_insert(base);
+        }
+
+        // pre-identified stats are placed first the same way extended decoding returns them
+        let preids: Vec<_> = self
+            .identifications
+            .iter()
+            .filter(|s| s.pre_identified)
+            .filter(|s| !idents.identifications.iter().any(|i| i.kind == s.kind))
+            .map(|s| Stat {
+                kind: s.kind,
+                base: Some(s.base),
+                roll: RollType::PreIdentified,
+            })
+            .collect();
+        idents.identifications.splice(0..0, preids);
+
+        Ok(())
+    }
+
+    pub(super) fn check_kind(&self, expected: ItemType) -> Result<(), DatabaseError> {
+        if self.kind == expected {
+            Ok(())
+        } else {
+            Err(DatabaseError::WrongItemType {
+                item: self.name.clone(),
+                expected,
+                found: self.kind,
+            })
+        }
+    }
+}
+
+impl ItemDatabase {
+    /// Fill in the base values of the identifications of the named item
+    ///
+    /// See [`ItemEntry::hydrate`]
+    pub fn hydrate(
+        &self,
+        name: &str,
+        idents: &mut IdentificationData,
+    ) -> Result<&ItemEntry, DatabaseError> {
+        let entry = self.entry(name)?;
+        entry.hydrate(idents)?;
+
+        Ok(entry)
+    }
+
+    fn hydrate_item(
+        &self,
+        name: &str,
+        kind: ItemType,
+        idents: Option<&mut IdentificationData>,
+    ) -> Result<&ItemEntry, DatabaseError> {
+        let entry = self.entry(name)?;
+        entry.check_kind(kind)?;
+
+        // unidentified items have nothing to fill in
+        if let Some(idents) = idents {
+            entry.hydrate(idents)?;
+        }
+
+        Ok(entry)
+    }
+}
+
+impl GearItem {
+    /// Fill in the base values of the identifications of this item from the database
+    ///
+    /// Returns the database entry of the item which holds the rest of the static data of the item such as the tier, type, requirements and roll ranges.
+    ///
+    /// See [`ItemEntry::hydrate`]
+    pub fn hydrate<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.hydrate_item(&self.name, ItemType::Gear, self.identifications.as_mut())
+    }
+}
+
+impl TomeItem {
+    /// See [`GearItem::hydrate`]
+    pub fn hydrate<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.hydrate_item(&self.name, ItemType::Tome, self.identifications.as_mut())
+    }
+}
+
+impl CharmItem {
+    /// See [`GearItem::hydrate`]
+    pub fn hydrate<'a>(&mut self, db: &'a ItemDatabase) -> Result<&'a ItemEntry, DatabaseError> {
+        db.hydrate_item(&self.name, ItemType::Charm, self.identifications.as_mut())
+    }
+}
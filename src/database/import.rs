@@ -0,0 +1,175 @@
+use crate::{
+    block::{IdentificationData, PowderData},
+    item::{CharmItem, GearItem, GenericItem, TomeItem},
+    types::{IdKeys, ItemType, RollType, Stat},
+};
+
+use super::{schema, DatabaseError, ItemEntry, StatEntry};
+
+/// The roll to give identifications when creating an item from a database entry
+///
+/// See [`ItemEntry::to_item`]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum RollMode {
+    /// Every identification rolls its highest possible value
+    ///
+    /// This is a roll of 130% for positive base values and 70% for negative base values.
+    Max,
+    /// Every identification rolls its lowest possible value
+    ///
+    /// This is a roll of 30% for positive base values and 130% for negative base values.
+    Min,
+    /// Every identification has the given roll percentage
+    ///
+    /// The roll is clamped to the rolls possible for each identification,
+    /// which are 30% to 130% for positive base values and 70% to 130% for negative base values.
+    Roll(u8),
+}
+
+impl RollMode {
+    /// Get the roll of the given identification using this mode
+    pub fn roll_for(&self, stat: &StatEntry) -> RollType {
+        if stat.pre_identified {
+            return RollType::PreIdentified;
+        }
+
+        RollType::Value(match (self, stat.base < 0) {
+            (RollMode::Max, false) | (RollMode::Min, true) => 130,
+            (RollMode::Max, true) => 70,
+            (RollMode::Min, false) => 30,
+            (RollMode::Roll(roll), false) => (*roll).clamp(30, 130),
+            (RollMode::Roll(roll), true) => (*roll).clamp(70, 130),
+        })
+    }
+}
+
+impl ItemEntry {
+    /// Parse a single item in the format used by the Wynncraft v3 api
+    ///
+    /// The json is expected to be the object describing the item, such as one of the values in the full item database.
+    ///
+    /// # Errors
+    /// Returns [`DatabaseError::InvalidField`] if the item is not gear, a tome or a charm.
+    pub fn from_api_json(name: &str, json: &str, id_keys: &IdKeys) -> Result<Self, DatabaseError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+
+        if !schema::is_identifiable(&value) {
+            return Err(DatabaseError::InvalidField {
+                item: name.to_string(),
+                field: "type",
+                value: value
+                    .get("type")
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            });
+        }
+
+        let item: schema::ApiItem = serde_json::from_value(value)?;
+
+        // the type was already checked so this can not be none
+        item.into_entry(name, id_keys)?
+            .ok_or_else(|| DatabaseError::UnknownItem(name.to_string()))
+    }
+
+    /// Create the identifications of an identified instance of this item
+    ///
+    /// The identifications use extended encoding so that the created item is independent of the database.
+    /// Pre-identified identifications are placed first, the same way extended decoding returns them.
+    pub fn to_identifications(&self, mode: RollMode) -> IdentificationData {
+        let (preids, rolled): (Vec<&StatEntry>, Vec<_>) =
+            self.identifications.iter().partition(|s| s.pre_identified);
+
+        IdentificationData {
+            identifications: preids
+                .into_iter()
+                .chain(rolled)
+                .map(|s| Stat {
+                    kind: s.kind,
+                    base: Some(s.base),
+                    roll: mode.roll_for(s),
+                })
+                .collect(),
+            extended_encoding: true,
+        }
+    }
+
+    /// Powder data with the powder slots of this item and no powders
+    fn empty_powders(&self) -> Option<PowderData> {
+        (self.powder_slots > 0).then(|| PowderData {
+            powder_slots: self.powder_slots,
+            powders: Vec::new(),
+        })
+    }
+
+    /// Create an identified instance of this item with the rolls given by the mode
+    pub fn to_item(&self, mode: RollMode) -> GenericItem {
+        GenericItem {
+            kind: self.kind,
+            name: Some(self.name.clone()),
+            identifications: Some(self.to_identifications(mode)),
+            powders: self.empty_powders(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an identified gear item from this entry
+    ///
+    /// See [`ItemEntry::to_item`]
+    pub fn to_gear(&self, mode: RollMode) -> Result<GearItem, DatabaseError> {
+        self.check_kind(ItemType::Gear)?;
+
+        Ok(GearItem {
+            name: self.name.clone(),
+            identifications: Some(self.to_identifications(mode)),
+            powders: self.empty_powders(),
+            shiny: None,
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        })
+    }
+
+    /// Create an identified tome item from this entry
+    ///
+    /// See [`ItemEntry::to_item`]
+    pub fn to_tome(&self, mode: RollMode) -> Result<TomeItem, DatabaseError> {
+        self.check_kind(ItemType::Tome)?;
+
+        Ok(TomeItem {
+            name: self.name.clone(),
+            identifications: Some(self.to_identifications(mode)),
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        })
+    }
+
+    /// Create an identified charm item from this entry
+    ///
+    /// See [`ItemEntry::to_item`]
+    pub fn to_charm(&self, mode: RollMode) -> Result<CharmItem, DatabaseError> {
+        self.check_kind(ItemType::Charm)?;
+
+        Ok(CharmItem {
+            name: self.name.clone(),
+            identifications: Some(self.to_identifications(mode)),
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        })
+    }
+}
+
+impl GearItem {
+    /// Create a gear item from a single item in the format used by the Wynncraft v3 api
+    ///
+    /// See [`ItemEntry::from_api_json`] and [`ItemEntry::to_gear`]
+    pub fn from_api_json(
+        name: &str,
+        json: &str,
+        id_keys: &IdKeys,
+        mode: RollMode,
+    ) -> Result<Self, DatabaseError> {
+        ItemEntry::from_api_json(name, json, id_keys)?.to_gear(mode)
+    }
+}
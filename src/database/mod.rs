@@ -0,0 +1,243 @@
+//! Module providing an item database for looking up the static data of items
+//!
+//! Idstrings encoded without extended encoding only contain the rolls of the identifications of an item.
+//! The base values, along with the rest of the static data of the item such as the tier and requirements, have to be looked up from an item database.
+//!
+//! The [`ItemDatabase`] can be loaded from a local copy of the item json provided by the Wynncraft v3 api.
+//! As the api uses names for identifications instead of the ids used by the wynntils format an [`IdKeys`] table is also required.
+//!
+//! Entries of the database can also be turned into identified items with [`ItemEntry::to_item`] and the related functions.
+//!
+//! Items can be exported into and imported from a human friendly json format modeled after the Wynncraft api using [`ItemDatabase::export`] and [`ItemDatabase::import`].
+//!
+//! This module is only available with the `database` feature enabled.
+
+use std::{collections::HashMap, io::Read};
+
+use crate::{
+    block::{DamageData, DefenseData, RequirementsData},
+    types::{CraftedGearType, Element, IdKeys, ItemTier, ItemType, Powder},
+};
+
+mod error;
+#[doc(inline)]
+pub use error::DatabaseError;
+
+mod compact;
+mod export;
+#[doc(inline)]
+pub use export::{
+    JsonBase, JsonDurability, JsonItem, JsonMap, JsonPowder, JsonShiny, JsonStat, JsonUses,
+};
+
+mod hydrate;
+
+mod import;
+#[doc(inline)]
+pub use import::RollMode;
+
+mod outdated;
+#[doc(inline)]
+pub use outdated::OutdatedReport;
+
+pub(crate) mod schema;
+#[doc(inline)]
+pub use schema::JsonRequirements;
+
+mod shiny;
+#[doc(inline)]
+pub use shiny::{ShinyStat, ShinyStats};
+
+/// Database of the static data of items
+#[derive(Clone, Debug, Default)]
+pub struct ItemDatabase {
+    items: HashMap<String, ItemEntry>,
+    id_keys: IdKeys,
+    shiny_stats: ShinyStats,
+}
+
+/// The static data of a single item
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ItemEntry {
+    /// Name of the item
+    pub name: String,
+    /// The type of the item
+    ///
+    /// This is one of [`ItemType::Gear`], [`ItemType::Tome`] or [`ItemType::Charm`]
+    pub kind: ItemType,
+    /// The tier of the item
+    pub tier: ItemTier,
+    /// The gear type of the item. [`None`] if the item is not gear
+    pub gear_type: Option<CraftedGearType>,
+    /// Requirements of the item
+    pub requirements: RequirementsData,
+    /// The number of powder slots on the item
+    pub powder_slots: u8,
+    /// The identifications of the item, sorted by id
+    pub identifications: Vec<StatEntry>,
+    /// Amount of health the item grants
+    pub health: i32,
+    /// Elemental defences of the item
+    pub defences: Vec<(Element, i32)>,
+    /// Base damages of the item if the item is a weapon
+    pub damage: Option<DamageData>,
+}
+
+/// The static data of an identification on an item
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct StatEntry {
+    /// The id of the identification
+    pub kind: u8,
+    /// The base value of the identification
+    pub base: i32,
+    /// The lowest value the identification can roll
+    pub min: i32,
+    /// The highest value the identification can roll
+    pub max: i32,
+    /// Whether or not the identification is pre-identified and as such always has its base value
+    pub pre_identified: bool,
+}
+
+/// An identification which does not match the database entry of its item
+///
+/// See [`ItemEntry::mismatches`]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct StatMismatch {
+    /// The id of the identification
+    pub kind: u8,
+    /// The base value encoded in the item. [`None`] if the item does not have the identification or its base value is not known
+    pub encoded: Option<i32>,
+    /// The base value in the database. [`None`] if the database entry does not have the identification
+    pub current: Option<i32>,
+}
+
+impl ItemDatabase {
+    /// Create an empty database using the given id table
+    pub fn new(id_keys: IdKeys) -> Self {
+        Self {
+            items: HashMap::new(),
+            id_keys,
+            shiny_stats: ShinyStats::new(),
+        }
+    }
+
+    /// Load a database from the item json provided by the Wynncraft api
+    ///
+    /// The json is expected to be an object with item names as keys, as returned by the full item database endpoint.
+    /// Entries which are not gear, tomes or charms (such as ingredients) are skipped.
+    pub fn from_json(json: &str, id_keys: IdKeys) -> Result<Self, DatabaseError> {
+        Self::from_items(serde_json::from_str(json)?, id_keys)
+    }
+
+    /// Load a database from a reader containing the item json provided by the Wynncraft api
+    ///
+    /// See [`ItemDatabase::from_json`]
+    pub fn from_reader(reader: impl Read, id_keys: IdKeys) -> Result<Self, DatabaseError> {
+        Self::from_items(serde_json::from_reader(reader)?, id_keys)
+    }
+
+    fn from_items(
+        items: HashMap<String, serde_json::Value>,
+        id_keys: IdKeys,
+    ) -> Result<Self, DatabaseError> {
+        let mut out = Self::new(id_keys);
+
+        for (name, value) in items {
+            if !schema::is_identifiable(&value) {
+                continue;
+            }
+
+            let item: schema::ApiItem = serde_json::from_value(value)?;
+
+            if let Some(entry) = item.into_entry(&name, &out.id_keys)? {
+                out.insert(entry);
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Insert an entry into the database, replacing any previous entry with the same name
+    pub fn insert(&mut self, entry: ItemEntry) {
+        self.items.insert(entry.name.clone(), entry);
+    }
+
+    /// Get the entry of the item with the given name
+    pub fn get(&self, name: &str) -> Option<&ItemEntry> {
+        self.items.get(name)
+    }
+
+    /// Get the entry of the item with the given name or an error if the item is not in the database
+    pub fn entry(&self, name: &str) -> Result<&ItemEntry, DatabaseError> {
+        self.get(name)
+            .ok_or_else(|| DatabaseError::UnknownItem(name.to_string()))
+    }
+
+    /// The id table used by this database
+    pub fn id_keys(&self) -> &IdKeys {
+        &self.id_keys
+    }
+
+    /// The shiny stat table used by this database
+    pub fn shiny_stats(&self) -> &ShinyStats {
+        &self.shiny_stats
+    }
+
+    /// Set the shiny stat table used by this database
+    ///
+    /// The shiny stat table is only needed for exporting and importing items with shiny stats.
+    pub fn set_shiny_stats(&mut self, shiny_stats: ShinyStats) {
+        self.shiny_stats = shiny_stats;
+    }
+
+    /// Number of items in the database
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the database is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over all the entries in the database
+    pub fn iter(&self) -> impl Iterator<Item = &ItemEntry> {
+        self.items.values()
+    }
+}
+
+impl ItemEntry {
+    /// Get the identification with the given id
+    pub fn stat(&self, kind: u8) -> Option<&StatEntry> {
+        self.identifications.iter().find(|s| s.kind == kind)
+    }
+
+    /// Damage of the item with the given powders applied
+    ///
+    /// Returns [`None`] if the item is not a weapon. See [`DamageData::with_powders`]
+    pub fn powdered_damage(&self, powders: &[Powder]) -> Option<DamageData> {
+        self.damage.as_ref().map(|d| d.with_powders(powders))
+    }
+
+    /// Health and defences of the item with the given powders applied
+    ///
+    /// See [`DefenseData::with_powders`]
+    pub fn powdered_defense(&self, powders: &[Powder]) -> DefenseData {
+        DefenseData {
+            health: self.health,
+            defences: self.defences.clone(),
+        }
+        .with_powders(powders)
+    }
+}
+
+impl IdKeys {
+    /// Load an id table from json
+    ///
+    /// The json is expected to be an object mapping identification names to ids, the same as the `id_keys.json` file used by wynntils.
+    pub fn from_json(json: &str) -> Result<Self, DatabaseError> {
+        let map: HashMap<String, u8> = serde_json::from_str(json)?;
+
+        Ok(map.into_iter().collect())
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    block::IdentificationData,
+    item::{CharmItem, GearItem, TomeItem},
+    types::{ItemType, RollType, Stat},
+};
+
+use super::{DatabaseError, ItemDatabase, ItemEntry, StatMismatch};
+
+/// Report of the identifications of an item which are outdated compared to the item database
+///
+/// See [`ItemEntry::check_outdated`]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct OutdatedReport {
+    /// The identifications which are outdated along with their current base values
+    pub stats: Vec<StatMismatch>,
+    /// The identifications updated to match the database
+    pub updated: IdentificationData,
+}
+
+impl OutdatedReport {
+    /// Check if any of the identifications are outdated
+    pub fn is_outdated(&self) -> bool {
+        !self.stats.is_empty()
+    }
+}
+
+impl ItemEntry {
+    /// Compare the identifications of an item against this entry and report the outdated identifications
+    ///
+    /// The report also contains the identifications as they would be if the item was updated to match this entry:
+    /// - base values are replaced with the current base values while the rolls are kept
+    /// - identifications which no longer exist on the item are removed
+    /// - missing pre-identified identifications are added
+    /// - identifications which are no longer pre-identified are given a roll of 100%
+    ///
+    /// Outdated base values can only be detected from items using extended encoding, as the base values are not encoded otherwise.
+    pub fn check_outdated(&self, idents: &IdentificationData) -> OutdatedReport {
+        let stats = self.mismatches(idents);
+
+        let mut updated: Vec<Stat> = idents
+            .identifications
+            .iter()
+            .filter_map(|stat| {
+                let entry = self.stat(stat.kind)?;
+
+                let roll = match (entry.pre_identified, stat.roll) {
+                    (true, _) => RollType::PreIdentified,
+                    (false, RollType::PreIdentified) => RollType::Value(100),
+                    (false, roll) => roll,
+                };
+
+                Some(Stat {
+                    kind: stat.kind,
+                    base: stat.base.map(|_| entry.base),
+                    roll,
+                })
+            })
+            .collect();
+
+        // add the missing pre-identified identifications
+        let preids: Vec<_> = stats
+            .iter()
+            .filter(|s| s.encoded.is_none() && !updated.iter().any(|u| u.kind == s.kind))
+            .filter_map(|s| {
+                Some(Stat {
+                    kind: s.kind,
+                    base: Some(s.current?),
+                    roll: RollType::PreIdentified,
+                })
+            })
+            .collect();
+        updated.splice(0..0, preids);
+
+        OutdatedReport {
+            stats,
+            updated: IdentificationData {
+                identifications: updated,
+                extended_encoding: idents.extended_encoding,
+            },
+        }
+    }
+}
+
+impl ItemDatabase {
+    /// Compare the identifications of the named item against the database
+    ///
+    /// See [`ItemEntry::check_outdated`]
+    pub fn check_outdated(
+        &self,
+        name: &str,
+        idents: &IdentificationData,
+    ) -> Result<OutdatedReport, DatabaseError> {
+        Ok(self.entry(name)?.check_outdated(idents))
+    }
+
+    fn check_item_outdated(
+        &self,
+        name: &str,
+        kind: ItemType,
+        idents: Option<&IdentificationData>,
+    ) -> Result<OutdatedReport, DatabaseError> {
+        let entry = self.entry(name)?;
+        entry.check_kind(kind)?;
+
+        Ok(match idents {
+            Some(idents) => entry.check_outdated(idents),
+            // unidentified items have nothing which could be outdated
+            None => OutdatedReport {
+                stats: Vec::new(),
+                updated: IdentificationData {
+                    identifications: Vec::new(),
+                    extended_encoding: false,
+                },
+            },
+        })
+    }
+}
+
+impl GearItem {
+    /// Compare the identifications of this item against the database
+    ///
+    /// See [`ItemEntry::check_outdated`]
+    pub fn check_outdated(&self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        db.check_item_outdated(&self.name, ItemType::Gear, self.identifications.as_ref())
+    }
+
+    /// Update the identifications of this item to match the database
+    ///
+    /// Returns the report of the outdated identifications. See [`ItemEntry::check_outdated`]
+    pub fn update(&mut self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        let report = self.check_outdated(db)?;
+        if let Some(idents) = &mut self.identifications {
+            *idents = report.updated.clone();
+        }
+
+        Ok(report)
+    }
+}
+
+impl TomeItem {
+    /// See [`GearItem::check_outdated`]
+    pub fn check_outdated(&self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        db.check_item_outdated(&self.name, ItemType::Tome, self.identifications.as_ref())
+    }
+
+    /// See [`GearItem::update`]
+    pub fn update(&mut self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        let report = self.check_outdated(db)?;
+        if let Some(idents) = &mut self.identifications {
+            *idents = report.updated.clone();
+        }
+
+        Ok(report)
+    }
+}
+
+impl CharmItem {
+    /// See [`GearItem::check_outdated`]
+    pub fn check_outdated(&self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        db.check_item_outdated(&self.name, ItemType::Charm, self.identifications.as_ref())
+    }
+
+    /// See [`GearItem::update`]
+    pub fn update(&mut self, db: &ItemDatabase) -> Result<OutdatedReport, DatabaseError> {
+        let report = self.check_outdated(db)?;
+        if let Some(idents) = &mut self.identifications {
+            *idents = report.updated.clone();
+        }
+
+        Ok(report)
+    }
+}
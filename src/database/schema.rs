@@ -0,0 +1,362 @@
+//! Deserialization of the item json used by the Wynncraft v3 api
+//!
+//! <https://docs.wynncraft.com/docs/modules/item.html>
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block::{DamageData, RequirementsData},
+    types::{
+        AttackSpeed, ClassType, ConsumableType, CraftedGearType, Element, IdKeys, ItemTier,
+        ItemType, SkillType,
+    },
+};
+
+use super::{DatabaseError, ItemEntry, StatEntry};
+
+/// Names of the elements as used by the api in the order of the [`Element`] ids
+pub(crate) const ELEMENT_NAMES: [(Element, &str); 5] = [
+    (Element::Earth, "Earth"),
+    (Element::Thunder, "Thunder"),
+    (Element::Water, "Water"),
+    (Element::Fire, "Fire"),
+    (Element::Air, "Air"),
+];
+
+/// Names of the skills as used by the api in the order of the [`SkillType`] ids
+pub(crate) const SKILL_NAMES: [(SkillType, &str); 5] = [
+    (SkillType::Strength, "strength"),
+    (SkillType::Dexterity, "dexterity"),
+    (SkillType::Intelligence, "intelligence"),
+    (SkillType::Defence, "defence"),
+    (SkillType::Agility, "agility"),
+];
+
+/// Names of the gear types as used by the api
+pub(crate) const GEAR_TYPE_NAMES: [(CraftedGearType, &str); 14] = [
+    (CraftedGearType::Spear, "spear"),
+    (CraftedGearType::Wand, "wand"),
+    (CraftedGearType::Dagger, "dagger"),
+    (CraftedGearType::Bow, "bow"),
+    (CraftedGearType::Relik, "relik"),
+    (CraftedGearType::Weapon, "weapon"),
+    (CraftedGearType::Accessory, "accessory"),
+    (CraftedGearType::Ring, "ring"),
+    (CraftedGearType::Bracelet, "bracelet"),
+    (CraftedGearType::Necklace, "necklace"),
+    (CraftedGearType::Helmet, "helmet"),
+    (CraftedGearType::Chestplate, "chestplate"),
+    (CraftedGearType::Leggings, "leggings"),
+    (CraftedGearType::Boots, "boots"),
+];
+
+/// Names of the consumable types as used by the api
+pub(crate) const CONSUMABLE_TYPE_NAMES: [(ConsumableType, &str); 3] = [
+    (ConsumableType::Potion, "potion"),
+    (ConsumableType::Food, "food"),
+    (ConsumableType::Scroll, "scroll"),
+];
+
+/// Names of the tiers as used by the api
+pub(crate) const TIER_NAMES: [(ItemTier, &str); 7] = [
+    (ItemTier::Common, "common"),
+    (ItemTier::Unique, "unique"),
+    (ItemTier::Rare, "rare"),
+    (ItemTier::Legendary, "legendary"),
+    (ItemTier::Fabled, "fabled"),
+    (ItemTier::Mythic, "mythic"),
+    (ItemTier::Set, "set"),
+];
+
+/// Names of the attack speeds as used by the api
+pub(crate) const ATTACK_SPEED_NAMES: [(AttackSpeed, &str); 7] = [
+    (AttackSpeed::SuperFast, "super_fast"),
+    (AttackSpeed::VeryFast, "very_fast"),
+    (AttackSpeed::Fast, "fast"),
+    (AttackSpeed::Normal, "normal"),
+    (AttackSpeed::Slow, "slow"),
+    (AttackSpeed::VerySlow, "very_slow"),
+    (AttackSpeed::SuperSlow, "super_slow"),
+];
+
+/// Names of the classes as used by the api
+pub(crate) const CLASS_NAMES: [(ClassType, &str); 5] = [
+    (ClassType::Mage, "mage"),
+    (ClassType::Archer, "archer"),
+    (ClassType::Warrior, "warrior"),
+    (ClassType::Assasin, "assassin"),
+    (ClassType::Shaman, "shaman"),
+];
+
+/// Look up the name of a value from one of the name tables
+pub(crate) fn name_of<T: PartialEq>(table: &[(T, &'static str)], value: T) -> &'static str {
+    table
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map(|(_, name)| *name)
+        .unwrap_or_default()
+}
+
+/// Look up a value by its name from one of the name tables
+pub(crate) fn parse_name<T: Copy>(
+    table: &[(T, &'static str)],
+    item: &str,
+    field: &'static str,
+    name: &str,
+) -> Result<T, DatabaseError> {
+    table
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(v, _)| *v)
+        .ok_or_else(|| invalid(item, field, name))
+}
+
+pub(crate) fn invalid(item: &str, field: &'static str, value: &str) -> DatabaseError {
+    DatabaseError::InvalidField {
+        item: item.to_string(),
+        field,
+        value: value.to_string(),
+    }
+}
+
+/// An item as returned by the api
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiItem {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub weapon_type: Option<String>,
+    pub armour_type: Option<String>,
+    pub accessory_type: Option<String>,
+    pub rarity: Option<String>,
+    #[serde(default)]
+    pub powder_slots: u8,
+    pub attack_speed: Option<String>,
+    #[serde(default)]
+    pub requirements: JsonRequirements,
+    #[serde(default)]
+    pub identifications: HashMap<String, ApiValue>,
+    #[serde(default)]
+    pub base: HashMap<String, ApiValue>,
+}
+
+/// Requirements of an item in the format used by the Wynncraft api
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonRequirements {
+    /// Level requirement
+    #[serde(default)]
+    pub level: u8,
+    /// Class requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_requirement: Option<String>,
+    /// Strength requirement
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub strength: i32,
+    /// Dexterity requirement
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub dexterity: i32,
+    /// Intelligence requirement
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub intelligence: i32,
+    /// Defence requirement
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub defence: i32,
+    /// Agility requirement
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub agility: i32,
+}
+
+fn is_zero(v: &i32) -> bool {
+    *v == 0
+}
+
+/// A value in the api which is either fixed or rolled
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+pub(crate) enum ApiValue {
+    Fixed(i32),
+    Rolled { min: i32, max: i32, raw: i32 },
+}
+
+impl ApiValue {
+    fn range(self) -> (i32, i32) {
+        match self {
+            ApiValue::Fixed(v) => (v, v),
+            ApiValue::Rolled { min, max, .. } => (min, max),
+        }
+    }
+}
+
+/// Check if the json value is an item which can be encoded as an identifiable item
+///
+/// Other items such as ingredients use a different format and as such are checked before being deserialized
+pub(crate) fn is_identifiable(value: &serde_json::Value) -> bool {
+    matches!(
+        value.get("type").and_then(|t| t.as_str()),
+        Some("weapon" | "armour" | "accessory" | "tome" | "charm")
+    )
+}
+
+/// Get the item type and gear type of an item from the api type names
+///
+/// Returns [`None`] if the item is not an item which can be encoded as an identifiable item
+fn item_kind(
+    name: &str,
+    item: &ApiItem,
+) -> Result<Option<(ItemType, Option<CraftedGearType>)>, DatabaseError> {
+    let (field, subtype) = match item.kind.as_str() {
+        "weapon" => ("weaponType", item.weapon_type.as_deref()),
+        "armour" => ("armourType", item.armour_type.as_deref()),
+        "accessory" => ("accessoryType", item.accessory_type.as_deref()),
+        "tome" => return Ok(Some((ItemType::Tome, None))),
+        "charm" => return Ok(Some((ItemType::Charm, None))),
+        _ => return Ok(None),
+    };
+
+    let gear = parse_name(&GEAR_TYPE_NAMES, name, field, subtype.unwrap_or_default())?;
+
+    Ok(Some((ItemType::Gear, Some(gear))))
+}
+
+impl JsonRequirements {
+    /// Convert the requirements into a requirements block
+    pub(crate) fn to_requirements(&self, name: &str) -> Result<RequirementsData, DatabaseError> {
+        let values = [
+            self.strength,
+            self.dexterity,
+            self.intelligence,
+            self.defence,
+            self.agility,
+        ];
+
+        Ok(RequirementsData {
+            level: self.level,
+            class: self
+                .class_requirement
+                .as_deref()
+                .map(|c| parse_name(&CLASS_NAMES, name, "classRequirement", c))
+                .transpose()?,
+            skills: SKILL_NAMES
+                .iter()
+                .zip(values)
+                .filter(|(_, v)| *v != 0)
+                .map(|((skill, _), v)| (*skill, v))
+                .collect(),
+        })
+    }
+}
+
+impl From<&RequirementsData> for JsonRequirements {
+    fn from(reqs: &RequirementsData) -> Self {
+        let skill = |skill: SkillType| {
+            reqs.skills
+                .iter()
+                .filter(|(s, _)| *s == skill)
+                .map(|(_, v)| *v)
+                .sum()
+        };
+
+        Self {
+            level: reqs.level,
+            class_requirement: reqs.class.map(|c| name_of(&CLASS_NAMES, c).to_string()),
+            strength: skill(SkillType::Strength),
+            dexterity: skill(SkillType::Dexterity),
+            intelligence: skill(SkillType::Intelligence),
+            defence: skill(SkillType::Defence),
+            agility: skill(SkillType::Agility),
+        }
+    }
+}
+
+impl ApiItem {
+    /// Convert the api item into a database entry
+    ///
+    /// Returns [`None`] if the item is not an identifiable item, such as an ingredient or a material
+    pub(crate) fn into_entry(
+        self,
+        name: &str,
+        id_keys: &IdKeys,
+    ) -> Result<Option<ItemEntry>, DatabaseError> {
+        let Some((kind, gear_type)) = item_kind(name, &self)? else {
+            return Ok(None);
+        };
+
+        let mut identifications = Vec::with_capacity(self.identifications.len());
+        for (stat_name, value) in &self.identifications {
+            let kind = id_keys
+                .id(stat_name)
+                .ok_or_else(|| DatabaseError::UnknownStatName {
+                    item: name.to_string(),
+                    name: stat_name.clone(),
+                })?;
+
+            identifications.push(match *value {
+                ApiValue::Fixed(v) => StatEntry {
+                    kind,
+                    base: v,
+                    min: v,
+                    max: v,
+                    pre_identified: true,
+                },
+                ApiValue::Rolled { min, max, raw } => StatEntry {
+                    kind,
+                    base: raw,
+                    min,
+                    max,
+                    pre_identified: false,
+                },
+            });
+        }
+        // keep the order stable regardless of the order of the json object
+        identifications.sort_by_key(|s| s.kind);
+
+        let base = |key: String| self.base.get(&key).map(|v| v.range());
+
+        let damage = self
+            .attack_speed
+            .as_deref()
+            .map(|speed| -> Result<DamageData, DatabaseError> {
+                let mut damages = Vec::new();
+                if let Some((min, max)) = base("baseDamage".to_string()) {
+                    damages.push((None, min..max));
+                }
+                for (elem, elem_name) in ELEMENT_NAMES {
+                    if let Some((min, max)) = base(format!("base{elem_name}Damage")) {
+                        damages.push((Some(elem), min..max));
+                    }
+                }
+
+                Ok(DamageData {
+                    attack_speed: parse_name(&ATTACK_SPEED_NAMES, name, "attackSpeed", speed)?,
+                    damages,
+                })
+            })
+            .transpose()?;
+
+        let tier = match self.rarity.as_deref() {
+            None | Some("normal") => ItemTier::Common,
+            Some(rarity) => parse_name(&TIER_NAMES, name, "rarity", rarity)?,
+        };
+
+        Ok(Some(ItemEntry {
+            name: name.to_string(),
+            kind,
+            tier,
+            gear_type,
+            requirements: self.requirements.to_requirements(name)?,
+            powder_slots: self.powder_slots,
+            identifications,
+            health: base("baseHealth".to_string()).map_or(0, |(v, _)| v),
+            defences: ELEMENT_NAMES
+                .iter()
+                .filter_map(|(elem, elem_name)| {
+                    base(format!("base{elem_name}Defence")).map(|(v, _)| (*elem, v))
+                })
+                .collect(),
+            damage,
+        }))
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::DatabaseError;
+
+/// Table of the shiny stats which can be tracked on items
+///
+/// The table used by wynntils can be found on <https://github.com/Wynntils/Static-Storage/blob/main/Data-Storage/shiny_stats.json>
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ShinyStats {
+    stats: HashMap<u8, ShinyStat>,
+}
+
+/// A single shiny stat
+#[derive(Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ShinyStat {
+    /// The id of the stat as used by [`ShinyData::id`](crate::block::ShinyData::id)
+    pub id: u8,
+    /// The key of the stat
+    pub key: String,
+    /// The name of the stat as displayed in game
+    #[serde(default)]
+    pub display_name: String,
+}
+
+impl ShinyStats {
+    /// Create an empty shiny stat table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a shiny stat table from json
+    ///
+    /// The json is expected to be a list of stats, the same as the `shiny_stats.json` file used by wynntils.
+    pub fn from_json(json: &str) -> Result<Self, DatabaseError> {
+        let stats: Vec<ShinyStat> = serde_json::from_str(json)?;
+
+        Ok(stats.into_iter().collect())
+    }
+
+    /// Add a stat to the table, replacing any previous stat with the same id
+    pub fn insert(&mut self, stat: ShinyStat) {
+        self.stats.insert(stat.id, stat);
+    }
+
+    /// Get the stat with the given id
+    pub fn get(&self, id: u8) -> Option<&ShinyStat> {
+        self.stats.get(&id)
+    }
+
+    /// Get the stat with the given key
+    pub fn by_key(&self, key: &str) -> Option<&ShinyStat> {
+        self.stats.values().find(|s| s.key == key)
+    }
+}
+
+impl FromIterator<ShinyStat> for ShinyStats {
+    fn from_iter<T: IntoIterator<Item = ShinyStat>>(iter: T) -> Self {
+        let mut out = Self::new();
+        for stat in iter {
+            out.insert(stat);
+        }
+        out
+    }
+}
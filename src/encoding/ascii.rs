@@ -0,0 +1,132 @@
+//! ASCII safe armour for the idstring byte stream
+//!
+//! Some platforms mangle the supplementary private use area characters produced by [`encode_string`].
+//! This module provides an alternative representation of the same bytes using URL safe base64 without padding,
+//! prefixed with [`ASCII_PREFIX`] so that it can be told apart from the private use area form.
+//!
+//! The decoding entry points of the crate, such as [`decode_str`](crate::block::decode_str) and
+//! [`GenericItem::decode_string`](crate::item::GenericItem::decode_string), accept both forms.
+
+use thiserror::Error;
+
+use super::{
+    string::{decode_string, encode_string, BadCodepoint},
+    DecodeError,
+};
+
+/// Prefix marking a string as using the ASCII armour
+pub const ASCII_PREFIX: &str = "idm:";
+
+/// The URL safe base64 alphabet
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Errors from decoding an ASCII armoured string
+#[derive(Error, Debug)]
+pub enum BadAsciiString {
+    /// The string does not start with [`ASCII_PREFIX`]
+    #[error("Missing ascii prefix")]
+    MissingPrefix,
+    /// A character outside of the base64 alphabet was encountered
+    #[error("Invalid character in ascii string: {0:?}")]
+    InvalidChar(char),
+    /// The length of the data is not a valid base64 length
+    #[error("Invalid ascii string length: {0}")]
+    InvalidLength(usize),
+}
+
+/// Check if a string uses the ASCII armour
+pub fn is_ascii_encoded(data: impl AsRef<str>) -> bool {
+    data.as_ref().starts_with(ASCII_PREFIX)
+}
+
+/// Encode bytes into an ASCII armoured string
+pub fn encode_ascii(data: &[u8]) -> String {
+    let mut out = String::with_capacity(ASCII_PREFIX.len() + data.len().div_ceil(3) * 4);
+    out.push_str(ASCII_PREFIX);
+
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+
+        // 3 bytes produce 4 chars, the last chunk produces one char more than it has bytes
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+        }
+    }
+
+    out
+}
+
+/// Decode the bytes of an ASCII armoured string
+///
+/// Trailing `=` padding is accepted but not required.
+pub fn decode_ascii(data: impl AsRef<str>) -> Result<Vec<u8>, BadAsciiString> {
+    let data = data
+        .as_ref()
+        .strip_prefix(ASCII_PREFIX)
+        .ok_or(BadAsciiString::MissingPrefix)?;
+
+    decode_base64(data)
+}
+
+/// Convert a private use area string into the ASCII armour
+pub fn to_ascii(data: impl AsRef<str>) -> Result<String, BadCodepoint> {
+    Ok(encode_ascii(&decode_string(data)?))
+}
+
+/// Convert an ASCII armoured string into the private use area form
+pub fn from_ascii(data: impl AsRef<str>) -> Result<String, BadAsciiString> {
+    Ok(encode_string(&decode_ascii(data)?))
+}
+
+/// Decode the bytes of a string in either the private use area form or the ASCII armour
+pub fn decode_any(data: impl AsRef<str>) -> Result<Vec<u8>, DecodeError> {
+    let data = data.as_ref();
+
+    if is_ascii_encoded(data) {
+        Ok(decode_ascii(data)?)
+    } else {
+        Ok(decode_string(data)?)
+    }
+}
+
+/// Decode base64 data without the prefix
+pub(crate) fn decode_base64(data: &str) -> Result<Vec<u8>, BadAsciiString> {
+    let data = data.trim_end_matches('=');
+
+    let chars = data.chars().collect::<Vec<_>>();
+
+    // a single leftover char can not hold a full byte
+    if chars.len() % 4 == 1 {
+        return Err(BadAsciiString::InvalidLength(chars.len()));
+    }
+
+    let mut out = Vec::with_capacity(chars.len() * 3 / 4);
+
+    for chunk in chars.chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= decode_sextet(*c)? << (18 - 6 * i);
+        }
+
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Get the value of a single base64 char
+fn decode_sextet(c: char) -> Result<u32, BadAsciiString> {
+    match c {
+        'A'..='Z' => Ok(c as u32 - 'A' as u32),
+        'a'..='z' => Ok(c as u32 - 'a' as u32 + 26),
+        '0'..='9' => Ok(c as u32 - '0' as u32 + 52),
+        '-' => Ok(62),
+        '_' => Ok(63),
+        _ => Err(BadAsciiString::InvalidChar(c)),
+    }
+}
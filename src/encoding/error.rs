@@ -4,11 +4,12 @@ use crate::{
     block::{DataBlockId, InvalidBlockId},
     types::errors::{
         BadAttackSpeed, BadClassType, BadConsumableType, BadEffectType, BadElement, BadGearType,
-        BadItemType, BadSkillType, InvalidPowderTier, UnknownEncodingVersion,
+        BadItemTier, BadItemType, BadSkillType, InvalidPowderTier, UnknownEncodingVersion,
     },
+    types::EncodingVersion,
 };
 
-use super::string::BadCodepoint;
+use super::{ascii::BadAsciiString, string::BadCodepoint};
 
 /// Potential errors thrown during encoding of id strings
 #[derive(Error, Debug)]
@@ -17,6 +18,10 @@ pub enum EncodeError {
     #[error("No start data block found")]
     NoStartBlock,
 
+    /// The encoding version is not available in this build, see [`EncodingVersion::is_available`]
+    #[error("Encoding version {0:?} is not available in this build")]
+    UnavailableVersion(EncodingVersion),
+
     /// Encoder was given a string with non ascii characters.
     #[error("Cannot encode non ascii string")]
     NonAsciiString,
@@ -33,6 +38,9 @@ pub enum EncodeError {
     /// More than 255 powders were passed for encoding
     #[error("Cannot encode more than 255 powders per item")]
     TooManyPowders,
+    /// More than 127 powders were passed for encoding in a version with packed powders
+    #[error("Cannot encode more than 127 powders per item in versions with packed powders")]
+    TooManyPackedPowders,
 
     /// Effect strength should be a percentage between 0 and 100
     #[error("Effect strength of {0} is too high, it should be a percentage between 0 and 100")]
@@ -53,6 +61,10 @@ pub enum EncodeError {
     /// More than 255 defense values were passed for encoding
     #[error("Cannot encode more than 255 defense values per item")]
     TooManyDefences,
+
+    /// An unknown block has the id of a known block or an id which decoders of the version can not skip
+    #[error("Cannot encode an unknown block with the id {0} in this version")]
+    InvalidUnknownBlockId(u8),
 }
 
 /// Error thrown when encoding multiple blocks
@@ -89,6 +101,9 @@ pub enum DecodeError {
     /// An invalid type was found
     #[error(transparent)]
     BadItemType(#[from] BadItemType),
+    /// An invalid item tier was found
+    #[error(transparent)]
+    BadItemTier(#[from] BadItemTier),
 
     #[error(transparent)]
     BadGearType(#[from] BadGearType),
@@ -120,12 +135,34 @@ pub enum DecodeError {
     #[error(transparent)]
     BadEffectType(#[from] BadEffectType),
 
+    /// The item count of a bundle header does not fit into a u32
+    #[error("Invalid bundle item count: {0}")]
+    BadBundleCount(i64),
+
+    /// The checksum block of the idstring does not match the data
+    ///
+    /// Checksums are only verified with the `checksum` feature.
+    #[error("Checksum mismatch: expected {expected:08X}, found {found:08X}")]
+    ChecksumMismatch {
+        /// The checksum stored in the idstring
+        expected: u32,
+        /// The checksum of the decoded data
+        found: u32,
+    },
+
+    /// The length of a length prefixed block is invalid
+    #[error("Invalid block length: {0}")]
+    BadFrameLength(i64),
+
     /// The decoder unexpectedly ran out of bytes to decode while decoding
     #[error("Unexpectedly hit end of bytestream while decoding")]
     UnexpectedEndOfBytes,
     /// The decoder hit an invalid codepoint while decoding
     #[error(transparent)]
     BadCodepoint(#[from] BadCodepoint),
+    /// An invalid ascii armoured string was encountered
+    #[error(transparent)]
+    BadAscii(#[from] BadAsciiString),
 }
 
 /// Error thrown when decoding multiple blocks
@@ -0,0 +1,67 @@
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
+
+use super::ascii::ASCII_PREFIX;
+
+/// Length of encoded data
+///
+/// Holds the exact number of bytes and provides the length of the strings these bytes are encoded into.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug, Default)]
+pub struct EncodedLen {
+    /// Number of encoded bytes
+    pub bytes: usize,
+}
+
+impl EncodedLen {
+    /// Create a new length from a number of bytes
+    pub const fn new(bytes: usize) -> Self {
+        Self { bytes }
+    }
+
+    /// Number of chars of the private use area string, see [`encode_string`](super::string::encode_string)
+    ///
+    /// Two bytes are encoded into each char.
+    pub const fn chars(self) -> usize {
+        self.bytes.div_ceil(2)
+    }
+
+    /// Number of UTF-16 code units of the private use area string
+    ///
+    /// The private use area chars are outside of the basic multilingual plane and take two code units each.
+    /// This is the length limits in minecraft are counted in.
+    pub const fn utf16_len(self) -> usize {
+        self.chars() * 2
+    }
+
+    /// Number of chars of the ascii armoured string, see [`encode_ascii`](super::ascii::encode_ascii)
+    pub const fn ascii_chars(self) -> usize {
+        let rem = match self.bytes % 3 {
+            0 => 0,
+            n => n + 1,
+        };
+
+        ASCII_PREFIX.len() + self.bytes / 3 * 4 + rem
+    }
+}
+
+impl Add for EncodedLen {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.bytes + rhs.bytes)
+    }
+}
+
+impl AddAssign for EncodedLen {
+    fn add_assign(&mut self, rhs: Self) {
+        self.bytes += rhs.bytes;
+    }
+}
+
+impl Sum for EncodedLen {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
@@ -10,8 +10,15 @@ pub use error::{DecodeError, DecoderError, EncodeError, EncoderError};
 
 pub mod string;
 
+pub mod ascii;
+
 pub(crate) mod varint;
 
+mod length;
+#[doc(inline)]
+pub use length::EncodedLen;
+
 mod traits;
+pub(crate) use traits::block_len;
 #[doc(inline)]
 pub use traits::{BlockId, DataDecoder, DataEncoder};
@@ -1,9 +1,9 @@
 use crate::{
-    block::{AnyBlock, DataBlockId},
+    block::{has_frame, write_frame, AnyBlock, DataBlockId},
     types::EncodingVersion,
 };
 
-use super::{DecodeError, EncodeError, EncoderError};
+use super::{varint::varint_len, DecodeError, EncodeError, EncodedLen, EncoderError};
 
 /// Trait for providing the id of the block
 pub trait BlockId {
@@ -24,11 +24,19 @@ pub trait DataEncoder: BlockId {
         // encode the id
         out.push(u8::from(self.block_id()));
 
-        // encode the data
-        self.encode_data(ver, out).map_err(|e| EncoderError {
+        let map_err = |e| EncoderError {
             error: e,
             during: self.block_id(),
-        })?;
+        };
+
+        // encode the data, the start block is never length prefixed as the version is not known before it
+        if has_frame(self.block_id(), ver) {
+            let mut payload = Vec::new();
+            self.encode_data(ver, &mut payload).map_err(map_err)?;
+            write_frame(&payload, out);
+        } else {
+            self.encode_data(ver, out).map_err(map_err)?;
+        }
 
         Ok(())
     }
@@ -36,10 +44,51 @@ pub trait DataEncoder: BlockId {
     /// Function for encoding the payload of this data
     fn encode_data(&self, ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError>;
 
+    /// Function for calculating the length of the full data block of this data without encoding it
+    fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        if !self.should_encode_data(ver) {
+            return Ok(EncodedLen::default());
+        }
+
+        let len = self.encoded_data_len(ver).map_err(|e| EncoderError {
+            error: e,
+            during: self.block_id(),
+        })?;
+
+        Ok(block_len(self.block_id(), ver, len))
+    }
+
+    /// Function for calculating the length of the payload of this data without encoding it
+    ///
+    /// This returns the same errors as [`DataEncoder::encode_data`].
+    /// The default implementation encodes the payload into a temporary buffer, implementors should override it to avoid the allocation.
+    fn encoded_data_len(&self, ver: EncodingVersion) -> Result<usize, EncodeError> {
+        let mut out = Vec::new();
+        self.encode_data(ver, &mut out)?;
+
+        Ok(out.len())
+    }
+
     /// Whether or not this encoder should actually encode anything
     fn should_encode_data(&self, _ver: EncodingVersion) -> bool {
         true
     }
+
+    /// The oldest version able to encode this data without losing any of it
+    fn minimum_version(&self) -> EncodingVersion {
+        EncodingVersion::V1
+    }
+}
+
+/// Length of a block with the given id and payload length, including the id and the length prefix
+pub(crate) fn block_len(id: DataBlockId, ver: EncodingVersion, data_len: usize) -> EncodedLen {
+    let frame_len = if has_frame(id, ver) {
+        varint_len(data_len as i64)
+    } else {
+        0
+    };
+
+    EncodedLen::new(1 + frame_len + data_len)
 }
 
 /// Trait for decoding data from bytes
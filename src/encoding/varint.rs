@@ -34,6 +34,15 @@ pub(crate) fn encode_varint(value: impl Into<i64>) -> Vec<u8> {
     outbytes
 }
 
+/// Number of bytes an integer is encoded into by [`encode_varint`]
+pub(crate) fn varint_len(value: impl Into<i64>) -> usize {
+    let value = value.into();
+    let value = ((value << 1) ^ (value >> 63)) as u64;
+
+    // 7 bits per byte, zero still takes a byte
+    (u64::BITS - value.leading_zeros()).div_ceil(7).max(1) as usize
+}
+
 /// Decode a variable sized integer (max i64) from the identification data bytestream
 pub(crate) fn decode_varint(bytes: &mut impl Iterator<Item = u8>) -> Result<i64, DecodeError> {
     let mut value = 0;
@@ -78,6 +87,8 @@ mod test {
             i64::MIN,
         ] {
             let bytes = encode_varint(i);
+            assert_eq!(varint_len(i), bytes.len());
+
             let n = decode_varint(&mut bytes.into_iter()).unwrap();
 
             assert_eq!(i, n);
@@ -0,0 +1,48 @@
+use crate::types::EncodingVersion;
+
+use super::{error::BudgetError, GenericItem};
+
+/// Optional data of an item which can be dropped to fit the item into a length limit
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OptionalBlock {
+    /// The name of the item
+    ///
+    /// Gear, tome and charm items can not be converted into their specific item types without a name.
+    Name,
+    /// The shiny of the item
+    Shiny,
+    /// The reroll count of the item
+    Rerolls,
+}
+
+impl GenericItem {
+    /// Drop optional blocks until the encoded item fits into the given number of private use area chars
+    ///
+    /// Blocks are dropped one at a time in the order given in `priority` until the item fits, data which is not part of `priority` is never dropped.
+    /// The length is calculated as in [`GenericItem::encoded_len`], see [`EncodedLen::chars`](crate::encoding::EncodedLen::chars).
+    ///
+    /// Returns an error if the item does not fit even after dropping all the given blocks.
+    pub fn fit_to_budget(
+        mut self,
+        ver: EncodingVersion,
+        budget: usize,
+        priority: &[OptionalBlock],
+    ) -> Result<Self, BudgetError> {
+        let mut priority = priority.iter();
+
+        loop {
+            let len = self.encoded_len(ver)?.chars();
+            if len <= budget {
+                return Ok(self);
+            }
+
+            match priority.next() {
+                Some(OptionalBlock::Name) => self.name = None,
+                Some(OptionalBlock::Shiny) => self.shiny = None,
+                Some(OptionalBlock::Rerolls) => self.rerolls = None,
+                None => return Err(BudgetError::TooLong { len, budget }),
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    block::{
+        encode_blocks_str, AnyBlock, BundleData, ChecksumReader, DataBlockId, EndData, StartData,
+    },
+    encoding::{
+        ascii::{decode_base64, ASCII_PREFIX},
+        string::decode_string,
+        DecoderError, EncoderError,
+    },
+    types::EncodingVersion,
+};
+
+use super::{error::ItemDecodeError, GenericItem, Item};
+
+/// Encode multiple items into a single bundle string
+///
+/// The bundle starts with a single start block holding the version shared by all items, followed by a [`BundleData`] header with the number of items.
+/// Each item is then encoded as its blocks terminated by an end block.
+pub fn encode_bundle<I>(
+    items: impl IntoIterator<Item = I>,
+    ver: EncodingVersion,
+) -> Result<String, EncoderError>
+where
+    I: Into<GenericItem>,
+{
+    let mut item_blocks = Vec::new();
+    let mut count = 0;
+    for item in items {
+        item_blocks.append(&mut item.into().into_blocks());
+        item_blocks.push(EndData.into());
+        count += 1;
+    }
+
+    let mut blocks = vec![StartData(ver).into(), BundleData(count).into()];
+    blocks.append(&mut item_blocks);
+
+    encode_blocks_str(ver, &blocks)
+}
+
+/// Decode a bundle string into generic items
+///
+/// See [`decode_bundle`]
+pub fn decode_bundle_generic(input: impl AsRef<str>) -> Result<Vec<GenericItem>, ItemDecodeError> {
+    let mut bytes = Vec::new();
+    // whitespace between pasted idstrings is not part of the encoding
+    for part in input.as_ref().split_whitespace() {
+        let mut segments = part.split(ASCII_PREFIX);
+
+        // anything before the first prefix is in the private use area form
+        if let Some(pua) = segments.next() {
+            bytes.append(&mut decode_string(pua)?);
+        }
+        for ascii in segments {
+            bytes.append(&mut decode_base64(ascii)?);
+        }
+    }
+    let mut bytes = bytes.into_iter().peekable();
+
+    let mut out = Vec::new();
+    while bytes.peek().is_some() {
+        // checksums cover the whole idstring or bundle starting from its start block
+        let bytes = &mut ChecksumReader::new(&mut bytes);
+
+        let ver = StartData::decode_start_bytes(bytes).map_err(|e| DecoderError {
+            error: e,
+            during: Some(DataBlockId::StartData),
+        })?;
+
+        // a bundle header directly follows the start block, otherwise this is a single idstring
+        let count = if bytes.next_if_eq(&(DataBlockId::BundleData as u8)).is_some() {
+            DataBlockId::BundleData
+                .decode_payload::<BundleData>(ver, bytes)?
+                .0
+        } else {
+            1
+        };
+
+        for _ in 0..count {
+            out.push(GenericItem::from_blocks(AnyBlock::decode_blocks(
+                ver, bytes, None,
+            )?)?);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Decode a bundle string into items
+///
+/// Besides bundles created by [`encode_bundle`] this also accepts individual idstrings concatenated together, optionally separated by whitespace,
+/// as well as any mix of bundles and individual idstrings. Each of them may use either the private use area form or the [ascii armour](crate::encoding::ascii). Items are returned in the order they appear in the input.
+pub fn decode_bundle(input: impl AsRef<str>) -> Result<Vec<Item>, ItemDecodeError> {
+    decode_bundle_generic(input)?
+        .into_iter()
+        .map(|item| Ok(Item::try_from(item)?))
+        .collect()
+}
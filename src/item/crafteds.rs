@@ -2,15 +2,15 @@ use crate::{
     block::{
         AnyBlock, CraftedConsumableTypeData, CraftedGearTypeData, CraftedIdentificationData,
         DamageData, DefenseData, DurabilityData, EffectsData, PowderData, RequirementsData,
-        UsesData,
+        TierData, UsesData,
     },
-    encoding::EncoderError,
-    types::{EncodingVersion, ItemType},
+    encoding::{DataEncoder, EncodedLen, EncoderError},
+    types::{scale_by_effectiveness, EffectType, EncodingVersion, ItemType},
 };
 
 use super::{
-    error::{ItemConvertError, ItemDecodeError},
-    GenericItem,
+    error::{ConsumeError, ItemConvertError, ItemDecodeError},
+    item_len, item_version, GenericItem,
 };
 
 /// Crafted gear item
@@ -35,6 +35,11 @@ pub struct CraftedGear {
     identifications: Option<CraftedIdentificationData>,
     /// Powders of the crafted item
     powders: Option<PowderData>,
+    /// Rarity tier of the crafted item
+    tier: Option<TierData>,
+    /// Custom and unknown blocks of the crafted item
+    #[cfg_attr(feature = "serde", serde(skip))]
+    extra_blocks: Vec<AnyBlock>,
 }
 
 impl TryFrom<GenericItem> for CraftedGear {
@@ -57,6 +62,8 @@ impl TryFrom<GenericItem> for CraftedGear {
                 defense: value.crafted_defense,
                 identifications: value.crafted_identifications,
                 powders: value.powders,
+                tier: value.tier,
+                extra_blocks: value.extra_blocks,
             })
         } else {
             Err(ItemConvertError::InvalidItemType(value.kind))
@@ -76,6 +83,8 @@ impl From<CraftedGear> for GenericItem {
             crafted_defense: value.defense,
             crafted_identifications: value.identifications,
             powders: value.powders,
+            tier: value.tier,
+            extra_blocks: value.extra_blocks,
             ..Default::default()
         }
     }
@@ -101,10 +110,190 @@ impl CraftedGear {
         GenericItem::from(self).into_blocks()
     }
 
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            ItemType::CraftedGear,
+            self.name.as_deref(),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
     /// See [`GenericItem::encode`]
     pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
         GenericItem::from(self).encode(ver)
     }
+
+    /// The blocks of the item other than the type and name
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 8] {
+        [
+            Some(&self.gear_type as &dyn DataEncoder),
+            Some(&self.durability as &dyn DataEncoder),
+            Some(&self.requirements as &dyn DataEncoder),
+            self.damage.as_ref().map(|d| d as &dyn DataEncoder),
+            self.defense.as_ref().map(|d| d as &dyn DataEncoder),
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.powders.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
+
+    /// The type of the crafted item
+    pub fn gear_type(&self) -> &CraftedGearTypeData {
+        &self.gear_type
+    }
+
+    /// Durability of the crafted item
+    pub fn durability(&self) -> &DurabilityData {
+        &self.durability
+    }
+
+    /// Requirements of the crafted item
+    pub fn requirements(&self) -> &RequirementsData {
+        &self.requirements
+    }
+
+    /// Name of the crafted item
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Damage of the crafted item
+    pub fn damage(&self) -> Option<&DamageData> {
+        self.damage.as_ref()
+    }
+
+    /// Defense of the crafted item
+    pub fn defense(&self) -> Option<&DefenseData> {
+        self.defense.as_ref()
+    }
+
+    /// Identifications of the crafted item
+    pub fn identifications(&self) -> Option<&CraftedIdentificationData> {
+        self.identifications.as_ref()
+    }
+
+    /// Powders of the crafted item
+    pub fn powders(&self) -> Option<&PowderData> {
+        self.powders.as_ref()
+    }
+
+    /// Rarity tier of the crafted item, see [`GenericItem::tier`]
+    pub fn tier(&self) -> Option<&TierData> {
+        self.tier.as_ref()
+    }
+
+    /// Custom and unknown blocks of the crafted item, see [`GenericItem::extra_blocks`]
+    pub fn extra_blocks(&self) -> &[AnyBlock] {
+        &self.extra_blocks
+    }
+
+    /// Damage of the crafted item with its powders applied
+    ///
+    /// See [`DamageData::with_powders`]
+    pub fn powdered_damage(&self) -> Option<DamageData> {
+        let powders = self.powders.as_ref().map_or(&[][..], |p| &p.powders);
+
+        self.damage.as_ref().map(|d| d.with_powders(powders))
+    }
+
+    /// Defense of the crafted item with its powders applied
+    ///
+    /// See [`DefenseData::with_powders`]
+    pub fn powdered_defense(&self) -> Option<DefenseData> {
+        let powders = self.powders.as_ref().map_or(&[][..], |p| &p.powders);
+
+        self.defense.as_ref().map(|d| d.with_powders(powders))
+    }
+
+    /// The current effectiveness of the crafted item as a percentage
+    ///
+    /// See [`DurabilityData::effectiveness`]
+    pub fn effectiveness(&self) -> u8 {
+        self.durability.effectiveness()
+    }
+
+    /// The values the identifications of the crafted item currently provide
+    ///
+    /// The values at full durability are scaled by the current effectiveness of the item.
+    /// Returns pairs of identification ids and values in the order they are stored in the item.
+    pub fn effective_identifications(&self) -> Vec<(u8, i32)> {
+        let eff = self.effectiveness();
+
+        self.identifications
+            .iter()
+            .flat_map(|i| &i.idents)
+            .map(|stat| (stat.kind, stat.value_at(eff)))
+            .collect()
+    }
+
+    /// The value the identification with the given id currently provides
+    ///
+    /// Returns [`None`] if the item does not have the identification.
+    pub fn effective_identification(&self, kind: u8) -> Option<i32> {
+        self.identifications
+            .iter()
+            .flat_map(|i| &i.idents)
+            .find(|stat| stat.kind == kind)
+            .map(|stat| stat.value_at(self.effectiveness()))
+    }
+
+    /// The damage the crafted item currently deals
+    ///
+    /// The damage ranges are scaled by the current effectiveness of the item.
+    pub fn effective_damage(&self) -> Option<DamageData> {
+        let eff = self.effectiveness();
+
+        self.damage.as_ref().map(|damage| DamageData {
+            attack_speed: damage.attack_speed,
+            damages: damage
+                .damages
+                .iter()
+                .map(|(elem, range)| {
+                    let start = scale_by_effectiveness(range.start, eff);
+                    let end = scale_by_effectiveness(range.end, eff);
+                    (*elem, start..end)
+                })
+                .collect(),
+        })
+    }
+
+    /// The health and defences the crafted item currently provides
+    ///
+    /// The values are scaled by the current effectiveness of the item.
+    pub fn effective_defense(&self) -> Option<DefenseData> {
+        let eff = self.effectiveness();
+
+        self.defense.as_ref().map(|defense| DefenseData {
+            health: scale_by_effectiveness(defense.health, eff),
+            defences: defense
+                .defences
+                .iter()
+                .map(|(elem, value)| (*elem, scale_by_effectiveness(*value, eff)))
+                .collect(),
+        })
+    }
+}
+
+/// Effects of a crafted consumable
+///
+/// See [`CraftedConsumable::effects_per_use`]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsumableEffects {
+    /// Health restored
+    pub heal: i32,
+    /// Mana restored
+    pub mana: i32,
+    /// Duration of the identifications of the consumable in seconds
+    pub duration: i32,
 }
 
 /// Crafted consumable
@@ -125,6 +314,11 @@ pub struct CraftedConsumable {
     effects: Option<EffectsData>,
     /// Identifications of the crafted item
     identifications: Option<CraftedIdentificationData>,
+    /// Rarity tier of the crafted item
+    tier: Option<TierData>,
+    /// Custom and unknown blocks of the crafted item
+    #[cfg_attr(feature = "serde", serde(skip))]
+    extra_blocks: Vec<AnyBlock>,
 }
 
 impl TryFrom<GenericItem> for CraftedConsumable {
@@ -145,6 +339,8 @@ impl TryFrom<GenericItem> for CraftedConsumable {
                 name: value.name,
                 effects: value.crafted_effects,
                 identifications: value.crafted_identifications,
+                tier: value.tier,
+                extra_blocks: value.extra_blocks,
             })
         } else {
             Err(ItemConvertError::InvalidItemType(value.kind))
@@ -162,6 +358,8 @@ impl From<CraftedConsumable> for GenericItem {
             crafted_reqs: Some(value.requirements),
             crafted_effects: value.effects,
             crafted_identifications: value.identifications,
+            tier: value.tier,
+            extra_blocks: value.extra_blocks,
             ..Default::default()
         }
     }
@@ -187,8 +385,110 @@ impl CraftedConsumable {
         GenericItem::from(self).into_blocks()
     }
 
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            ItemType::CraftedConsu,
+            self.name.as_deref(),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
     /// See [`GenericItem::encode`]
     pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
         GenericItem::from(self).encode(ver)
     }
+
+    /// The blocks of the item other than the type and name
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 6] {
+        [
+            Some(&self.consumable_type as &dyn DataEncoder),
+            Some(&self.uses as &dyn DataEncoder),
+            Some(&self.requirements as &dyn DataEncoder),
+            self.effects.as_ref().map(|d| d as &dyn DataEncoder),
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
+
+    /// Name of the crafted item
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Effects of the crafted item
+    pub fn effects(&self) -> Option<&EffectsData> {
+        self.effects.as_ref()
+    }
+
+    /// Identifications of the crafted item
+    pub fn identifications(&self) -> Option<&CraftedIdentificationData> {
+        self.identifications.as_ref()
+    }
+
+    /// Rarity tier of the crafted item, see [`GenericItem::tier`]
+    pub fn tier(&self) -> Option<&TierData> {
+        self.tier.as_ref()
+    }
+
+    /// Custom and unknown blocks of the crafted item, see [`GenericItem::extra_blocks`]
+    pub fn extra_blocks(&self) -> &[AnyBlock] {
+        &self.extra_blocks
+    }
+
+    /// The effects of a single use of the consumable
+    ///
+    /// Multiple effects of the same type are added together.
+    pub fn effects_per_use(&self) -> ConsumableEffects {
+        let mut out = ConsumableEffects::default();
+
+        for effect in self.effects.iter().flat_map(|e| &e.effects) {
+            match effect.kind {
+                EffectType::Heal => out.heal += effect.value,
+                EffectType::Mana => out.mana += effect.value,
+                EffectType::Duration => out.duration += effect.value,
+            }
+        }
+
+        out
+    }
+
+    /// The total effects of all of the remaining uses of the consumable
+    pub fn remaining_effects(&self) -> ConsumableEffects {
+        let per_use = self.effects_per_use();
+        let uses = self.uses.current as i32;
+
+        ConsumableEffects {
+            heal: per_use.heal * uses,
+            mana: per_use.mana * uses,
+            duration: per_use.duration * uses,
+        }
+    }
+
+    /// Check if the consumable has no uses left
+    pub fn is_used_up(&self) -> bool {
+        self.uses.current == 0
+    }
+
+    /// Use the consumable once
+    ///
+    /// Returns the consumable with one less use remaining along with its new idstring encoded using the given version.
+    pub fn consume(&self, ver: EncodingVersion) -> Result<(Self, String), ConsumeError> {
+        if self.is_used_up() {
+            return Err(ConsumeError::NoUsesLeft);
+        }
+
+        let mut out = self.clone();
+        out.uses.current -= 1;
+        let encoded = out.clone().encode(ver)?;
+
+        Ok((out, encoded))
+    }
 }
@@ -2,7 +2,9 @@ use thiserror::Error;
 
 use crate::{
     block::DataBlockId,
-    encoding::{string::BadCodepoint, DecoderError},
+    encoding::{
+        ascii::BadAsciiString, string::BadCodepoint, DecodeError, DecoderError, EncoderError,
+    },
     types::ItemType,
 };
 
@@ -23,7 +25,54 @@ pub enum ItemDecodeError {
     MissingBlock(DataBlockId),
     #[error("Invalid codepoint encountered: {0}")]
     BadString(#[from] BadCodepoint),
+    #[error("Invalid ascii string: {0}")]
+    BadAscii(#[from] BadAsciiString),
 
     #[error("Invalid item: {0:?}")]
     InvalidItem(#[from] ItemConvertError),
 }
+
+impl From<DecodeError> for ItemDecodeError {
+    fn from(error: DecodeError) -> Self {
+        // keep the string errors of decoding the text of an idstring as their own variants
+        match error {
+            DecodeError::BadCodepoint(e) => Self::BadString(e),
+            DecodeError::BadAscii(e) => Self::BadAscii(e),
+            error => Self::DecoderError(DecoderError {
+                error,
+                during: None,
+            }),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConsumeError {
+    #[error("The consumable has no uses left")]
+    NoUsesLeft,
+
+    #[error("Encode error: {0:?}")]
+    EncoderError(#[from] EncoderError),
+}
+
+#[derive(Error, Debug)]
+pub enum BudgetError {
+    #[error("Item does not fit into {budget} chars, it is {len} chars long")]
+    TooLong { len: usize, budget: usize },
+
+    #[error("Encode error: {0:?}")]
+    EncoderError(#[from] EncoderError),
+}
+
+#[cfg(feature = "signature")]
+#[derive(Error, Debug)]
+pub enum SignatureError {
+    #[error("The item is not signed")]
+    Unsigned,
+
+    #[error("Invalid signature: {0}")]
+    Invalid(#[from] ed25519_dalek::SignatureError),
+
+    #[error("Encode error: {0:?}")]
+    EncoderError(#[from] EncoderError),
+}
@@ -1,12 +1,12 @@
 use crate::{
-    block::{AnyBlock, IdentificationData, PowderData, RerollData, ShinyData},
-    encoding::EncoderError,
+    block::{AnyBlock, IdentificationData, PowderData, RerollData, ShinyData, TierData},
+    encoding::{DataEncoder, EncodedLen, EncoderError},
     types::{EncodingVersion, ItemType},
 };
 
 use super::{
     error::{ItemConvertError, ItemDecodeError},
-    GenericItem,
+    item_len, item_version, GenericItem,
 };
 
 /// Struct Representing an Gear Item
@@ -26,6 +26,12 @@ pub struct GearItem {
     pub shiny: Option<ShinyData>,
     /// Rerolls of the gear item
     pub rerolls: Option<RerollData>,
+    /// Rarity tier of the gear item, see [`GenericItem::tier`]
+    pub tier: Option<TierData>,
+
+    /// Custom and unknown blocks of the item, see [`GenericItem::extra_blocks`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra_blocks: Vec<AnyBlock>,
 }
 
 impl TryFrom<GenericItem> for GearItem {
@@ -41,6 +47,8 @@ impl TryFrom<GenericItem> for GearItem {
                 powders: value.powders,
                 shiny: value.shiny,
                 rerolls: value.rerolls,
+                tier: value.tier,
+                extra_blocks: value.extra_blocks,
             })
         } else {
             Err(ItemConvertError::InvalidItemType(value.kind))
@@ -57,6 +65,8 @@ impl From<GearItem> for GenericItem {
             powders: value.powders,
             shiny: value.shiny,
             rerolls: value.rerolls,
+            tier: value.tier,
+            extra_blocks: value.extra_blocks,
             ..Default::default()
         }
     }
@@ -82,10 +92,37 @@ impl GearItem {
         GenericItem::from(self).into_blocks()
     }
 
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            ItemType::Gear,
+            Some(&self.name),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
     /// See [`GenericItem::encode`]
     pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
         GenericItem::from(self).encode(ver)
     }
+
+    /// The blocks of the item other than the type and name
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 5] {
+        [
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.powders.as_ref().map(|d| d as &dyn DataEncoder),
+            self.shiny.as_ref().map(|d| d as &dyn DataEncoder),
+            self.rerolls.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
 }
 
 /// Struct Representing a Tome Item
@@ -101,6 +138,12 @@ pub struct TomeItem {
     pub identifications: Option<IdentificationData>,
     /// Rerolls of the tome item
     pub rerolls: Option<RerollData>,
+    /// Rarity tier of the tome item, see [`GenericItem::tier`]
+    pub tier: Option<TierData>,
+
+    /// Custom and unknown blocks of the item, see [`GenericItem::extra_blocks`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra_blocks: Vec<AnyBlock>,
 }
 
 impl TryFrom<GenericItem> for TomeItem {
@@ -114,6 +157,8 @@ impl TryFrom<GenericItem> for TomeItem {
                     .ok_or_else(|| ItemConvertError::MissingField("name".to_string()))?,
                 identifications: value.identifications,
                 rerolls: value.rerolls,
+                tier: value.tier,
+                extra_blocks: value.extra_blocks,
             })
         } else {
             Err(ItemConvertError::InvalidItemType(value.kind))
@@ -128,6 +173,8 @@ impl From<TomeItem> for GenericItem {
             name: Some(value.name),
             identifications: value.identifications,
             rerolls: value.rerolls,
+            tier: value.tier,
+            extra_blocks: value.extra_blocks,
             ..Default::default()
         }
     }
@@ -153,10 +200,35 @@ impl TomeItem {
         GenericItem::from(self).into_blocks()
     }
 
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            ItemType::Tome,
+            Some(&self.name),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
     /// See [`GenericItem::encode`]
     pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
         GenericItem::from(self).encode(ver)
     }
+
+    /// The blocks of the item other than the type and name
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 3] {
+        [
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.rerolls.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
 }
 
 /// Struct Representing a Charm Item
@@ -172,6 +244,12 @@ pub struct CharmItem {
     pub identifications: Option<IdentificationData>,
     /// Rerolls of the charm item
     pub rerolls: Option<RerollData>,
+    /// Rarity tier of the charm item, see [`GenericItem::tier`]
+    pub tier: Option<TierData>,
+
+    /// Custom and unknown blocks of the item, see [`GenericItem::extra_blocks`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra_blocks: Vec<AnyBlock>,
 }
 
 impl TryFrom<GenericItem> for CharmItem {
@@ -185,6 +263,8 @@ impl TryFrom<GenericItem> for CharmItem {
                     .ok_or_else(|| ItemConvertError::MissingField("name".to_string()))?,
                 identifications: value.identifications,
                 rerolls: value.rerolls,
+                tier: value.tier,
+                extra_blocks: value.extra_blocks,
             })
         } else {
             Err(ItemConvertError::InvalidItemType(value.kind))
@@ -199,6 +279,8 @@ impl From<CharmItem> for GenericItem {
             name: Some(value.name),
             identifications: value.identifications,
             rerolls: value.rerolls,
+            tier: value.tier,
+            extra_blocks: value.extra_blocks,
             ..Default::default()
         }
     }
@@ -224,8 +306,33 @@ impl CharmItem {
         GenericItem::from(self).into_blocks()
     }
 
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            ItemType::Charm,
+            Some(&self.name),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
     /// See [`GenericItem::encode`]
     pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
         GenericItem::from(self).encode(ver)
     }
+
+    /// The blocks of the item other than the type and name
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 3] {
+        [
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.rerolls.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
 }
@@ -0,0 +1,143 @@
+use crate::{
+    block::AnyBlock,
+    encoding::{EncodedLen, EncoderError},
+    types::{EncodingVersion, ItemType},
+};
+
+use super::{
+    error::{ItemConvertError, ItemDecodeError},
+    CharmItem, CraftedConsumable, CraftedGear, GearItem, GenericItem, TomeItem,
+};
+
+/// Enum representing an item of any type using the specific item types
+///
+/// Unlike [`GenericItem`] this type guarantees that the data required by the item type is present.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Item {
+    /// A gear item
+    Gear(GearItem),
+    /// A tome item
+    Tome(TomeItem),
+    /// A charm item
+    Charm(CharmItem),
+    /// A crafted gear item
+    CraftedGear(CraftedGear),
+    /// A crafted consumable
+    CraftedConsumable(CraftedConsumable),
+}
+
+impl TryFrom<GenericItem> for Item {
+    type Error = ItemConvertError;
+
+    fn try_from(value: GenericItem) -> Result<Self, Self::Error> {
+        Ok(match value.kind {
+            ItemType::Gear => Self::Gear(value.try_into()?),
+            ItemType::Tome => Self::Tome(value.try_into()?),
+            ItemType::Charm => Self::Charm(value.try_into()?),
+            ItemType::CraftedGear => Self::CraftedGear(value.try_into()?),
+            ItemType::CraftedConsu => Self::CraftedConsumable(value.try_into()?),
+        })
+    }
+}
+
+impl From<Item> for GenericItem {
+    fn from(value: Item) -> Self {
+        match value {
+            Item::Gear(item) => item.into(),
+            Item::Tome(item) => item.into(),
+            Item::Charm(item) => item.into(),
+            Item::CraftedGear(item) => item.into(),
+            Item::CraftedConsumable(item) => item.into(),
+        }
+    }
+}
+
+impl From<GearItem> for Item {
+    fn from(value: GearItem) -> Self {
+        Self::Gear(value)
+    }
+}
+
+impl From<TomeItem> for Item {
+    fn from(value: TomeItem) -> Self {
+        Self::Tome(value)
+    }
+}
+
+impl From<CharmItem> for Item {
+    fn from(value: CharmItem) -> Self {
+        Self::Charm(value)
+    }
+}
+
+impl From<CraftedGear> for Item {
+    fn from(value: CraftedGear) -> Self {
+        Self::CraftedGear(value)
+    }
+}
+
+impl From<CraftedConsumable> for Item {
+    fn from(value: CraftedConsumable) -> Self {
+        Self::CraftedConsumable(value)
+    }
+}
+
+impl Item {
+    /// The type of the item
+    pub fn kind(&self) -> ItemType {
+        match self {
+            Self::Gear(_) => ItemType::Gear,
+            Self::Tome(_) => ItemType::Tome,
+            Self::Charm(_) => ItemType::Charm,
+            Self::CraftedGear(_) => ItemType::CraftedGear,
+            Self::CraftedConsumable(_) => ItemType::CraftedConsu,
+        }
+    }
+
+    /// See [`GenericItem::from_blocks`]
+    pub fn from_blocks(blocks: Vec<AnyBlock>) -> Result<Self, ItemDecodeError> {
+        let generic = GenericItem::from_blocks(blocks)?;
+
+        Ok(Self::try_from(generic)?)
+    }
+
+    /// See [`GenericItem::decode_string`]
+    pub fn decode_string(input: impl AsRef<str>) -> Result<Self, ItemDecodeError> {
+        let generic = GenericItem::decode_string(input)?;
+
+        Ok(Self::try_from(generic)?)
+    }
+
+    /// See [`GenericItem::into_blocks`]
+    pub fn into_blocks(self) -> Vec<AnyBlock> {
+        GenericItem::from(self).into_blocks()
+    }
+
+    /// See [`GenericItem::minimum_version`]
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        match self {
+            Item::Gear(item) => item.minimum_version(),
+            Item::Tome(item) => item.minimum_version(),
+            Item::Charm(item) => item.minimum_version(),
+            Item::CraftedGear(item) => item.minimum_version(),
+            Item::CraftedConsumable(item) => item.minimum_version(),
+        }
+    }
+
+    /// See [`GenericItem::encoded_len`]
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        match self {
+            Item::Gear(item) => item.encoded_len(ver),
+            Item::Tome(item) => item.encoded_len(ver),
+            Item::Charm(item) => item.encoded_len(ver),
+            Item::CraftedGear(item) => item.encoded_len(ver),
+            Item::CraftedConsumable(item) => item.encoded_len(ver),
+        }
+    }
+
+    /// See [`GenericItem::encode`]
+    pub fn encode(self, ver: EncodingVersion) -> Result<String, EncoderError> {
+        GenericItem::from(self).encode(ver)
+    }
+}
@@ -4,27 +4,45 @@
 //!
 //! [`GenericItem`] is the main type for representing items. This type can represent any item with any data. The more specific types are used for representing specific item types.
 //!
+//! Multiple items can be shared as a single string using [`encode_bundle`] and [`decode_bundle`].
+//!
 //! This module should generally be used over the block module for decoding and encoding items when low level block manipulation is not required.
 
 use crate::{
     block::{
-        AnyBlock, CraftedConsumableTypeData, CraftedGearTypeData, CraftedIdentificationData,
-        DamageData, DataBlockId, DefenseData, DurabilityData, EffectsData, EndData,
-        IdentificationData, NameData, PowderData, RequirementsData, RerollData, ShinyData,
-        StartData, TypeData, UsesData,
+        encoded_blocks_len, name_len, AnyBlock, CraftedConsumableTypeData, CraftedGearTypeData,
+        CraftedIdentificationData, DamageData, DataBlockId, DefenseData, DurabilityData,
+        EffectsData, EndData, IdentificationData, NameData, PowderData, RequirementsData,
+        RerollData, ShinyData, StartData, TypeData, UsesData,
     },
-    encoding::{string::decode_string, EncoderError},
+    encoding::{ascii::decode_any, block_len, DataEncoder, EncodedLen, EncoderError},
     types::{EncodingVersion, ItemType},
 };
 
+use crate::block::TierData;
+
+mod budget;
+#[doc(inline)]
+pub use budget::OptionalBlock;
+mod bundle;
+#[doc(inline)]
+pub use bundle::*;
 mod crafteds;
 #[doc(inline)]
 pub use crafteds::*;
 pub mod error;
 mod id_items;
+mod items;
 use error::ItemDecodeError;
 #[doc(inline)]
 pub use id_items::*;
+#[doc(inline)]
+pub use items::Item;
+#[cfg(feature = "signature")]
+mod signed;
+#[cfg(feature = "signature")]
+#[doc(inline)]
+pub use signed::SignedItem;
 
 /// Type representing any item with any item data
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
@@ -37,6 +55,8 @@ pub struct GenericItem {
     pub name: Option<String>,
     /// Powders of the item
     pub powders: Option<PowderData>,
+    /// Rarity tier of the item, only encoded in versions supporting [`VersionFeature::ItemTier`](crate::types::VersionFeature::ItemTier)
+    pub tier: Option<TierData>,
 
     // id item specific fields
     /// Identification data of an identifiable item
@@ -71,6 +91,12 @@ pub struct GenericItem {
     pub crafted_uses: Option<UsesData>,
     /// Crafted consumable effects
     pub crafted_effects: Option<EffectsData>,
+
+    /// Custom and unknown blocks of the item, encoded after the other blocks
+    ///
+    /// These blocks are not serialized with `serde`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extra_blocks: Vec<AnyBlock>,
 }
 
 impl Default for GenericItem {
@@ -79,6 +105,7 @@ impl Default for GenericItem {
             kind: ItemType::Gear,
             name: Default::default(),
             powders: Default::default(),
+            tier: Default::default(),
             identifications: Default::default(),
             rerolls: Default::default(),
             shiny: Default::default(),
@@ -91,6 +118,7 @@ impl Default for GenericItem {
             crafted_consumable_type: Default::default(),
             crafted_uses: Default::default(),
             crafted_effects: Default::default(),
+            extra_blocks: Default::default(),
         }
     }
 }
@@ -99,13 +127,21 @@ impl GenericItem {
     /// Decode a generic item from a list of blocks
     ///
     /// This function will attempt to decode a generic item from a list of blocks. This function will return an error if any required blocks are missing.
+    ///
+    /// Checksum and signature blocks are not part of the item and are dropped. Checksums are verified while decoding the blocks,
+    /// signatures can be kept using `SignedItem` with the `signature` feature.
     pub fn from_blocks(blocks: Vec<AnyBlock>) -> Result<Self, ItemDecodeError> {
         let mut out = Self::default();
         let mut kind = None;
 
         for block in blocks {
             match block {
-                AnyBlock::StartData(_) | AnyBlock::EndData(_) => {}
+                AnyBlock::StartData(_) | AnyBlock::EndData(_) | AnyBlock::BundleData(_) => {}
+                block @ (AnyBlock::Custom(_) | AnyBlock::Unknown(_)) => {
+                    out.extra_blocks.push(block)
+                }
+                // checksums only cover the encoded bytes, signatures are kept by SignedItem
+                AnyBlock::ChecksumData(_) | AnyBlock::SignatureData(_) => {}
 
                 AnyBlock::TypeData(type_data) => kind = Some(type_data.0),
                 AnyBlock::NameData(name_data) => out.name = Some(name_data.0),
@@ -113,6 +149,7 @@ impl GenericItem {
                     out.identifications = Some(identification_data)
                 }
                 AnyBlock::PowderData(powder_data) => out.powders = Some(powder_data),
+                AnyBlock::TierData(tier_data) => out.tier = Some(tier_data),
                 AnyBlock::RerollData(reroll_data) => out.rerolls = Some(reroll_data),
                 AnyBlock::ShinyData(shiny_data) => out.shiny = Some(shiny_data),
                 AnyBlock::CraftedGearType(crafted_gear_type_data) => {
@@ -150,8 +187,10 @@ impl GenericItem {
     ///
     /// This function will attempt to decode a generic item from a string. This function will return an error if any required blocks are missing.
     /// This function will also error if an error occurs while decoding the blocks.
+    ///
+    /// Both the private use area form and the [ascii armour](crate::encoding::ascii) are accepted.
     pub fn decode_string(input: impl AsRef<str>) -> Result<Self, ItemDecodeError> {
-        let blocks = AnyBlock::decode(&mut decode_string(input)?.into_iter())?;
+        let blocks = AnyBlock::decode(&mut decode_any(input)?.into_iter())?;
         Self::from_blocks(blocks)
     }
 
@@ -160,7 +199,7 @@ impl GenericItem {
     /// This function will convert the generic item into a list of blocks. The list of blocks will contain all the data from the generic item.
     /// The list however will not contain any start or end blocks, which are required for a full idstring.
     pub fn into_blocks(self) -> Vec<AnyBlock> {
-        [
+        let mut blocks = [
             Some(TypeData(self.kind).into()),
             self.name.map(|n| NameData(n).into()),
             self.identifications.map(Into::into),
@@ -179,7 +218,22 @@ impl GenericItem {
         ]
         .into_iter()
         .flatten()
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>();
+
+        blocks.extend(self.tier.map(AnyBlock::from));
+
+        blocks.extend(self.extra_blocks);
+
+        blocks
+    }
+
+    /// The oldest version able to encode this item without losing any of its data
+    ///
+    /// For example a shiny with rerolls requires [`EncodingVersion::V2`]. See [`AnyBlock::minimum_version`]
+    ///
+    /// Returns `None` if one of the extra blocks of the item can not be encoded by any version available in this build.
+    pub fn minimum_version(&self) -> Option<EncodingVersion> {
+        item_version(&self.data_blocks(), &self.extra_blocks)
     }
 
     /// Encode the generic item into a string
@@ -195,4 +249,119 @@ impl GenericItem {
 
         crate::block::encode_blocks_str(ver, &blocks)
     }
+
+    /// Calculate the length of the encoded item without encoding it
+    ///
+    /// This is the length of the idstring created by [`GenericItem::encode`] or [`GenericItem::encode_ascii`], see [`EncodedLen`].
+    pub fn encoded_len(&self, ver: EncodingVersion) -> Result<EncodedLen, EncoderError> {
+        item_len(
+            ver,
+            self.kind,
+            self.name.as_deref(),
+            &self.data_blocks(),
+            &self.extra_blocks,
+        )
+    }
+
+    /// The blocks of the item other than the type, name and extra blocks
+    fn data_blocks(&self) -> [Option<&dyn DataEncoder>; 14] {
+        [
+            self.identifications.as_ref().map(|d| d as &dyn DataEncoder),
+            self.powders.as_ref().map(|d| d as &dyn DataEncoder),
+            self.rerolls.as_ref().map(|d| d as &dyn DataEncoder),
+            self.shiny.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_type.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_durability
+                .as_ref()
+                .map(|d| d as &dyn DataEncoder),
+            self.crafted_reqs.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_damage.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_defense.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_identifications
+                .as_ref()
+                .map(|d| d as &dyn DataEncoder),
+            self.crafted_consumable_type
+                .as_ref()
+                .map(|d| d as &dyn DataEncoder),
+            self.crafted_uses.as_ref().map(|d| d as &dyn DataEncoder),
+            self.crafted_effects.as_ref().map(|d| d as &dyn DataEncoder),
+            self.tier.as_ref().map(|d| d as &dyn DataEncoder),
+        ]
+    }
+
+    /// Encode the generic item into an ascii armoured string
+    ///
+    /// See [`GenericItem::encode`] and [`crate::encoding::ascii`]
+    pub fn encode_ascii(self, ver: EncodingVersion) -> Result<String, EncoderError> {
+        let mut blocks = vec![StartData(ver).into()];
+
+        blocks.append(&mut self.into_blocks());
+
+        blocks.push(EndData.into());
+
+        crate::block::encode_blocks_ascii(ver, &blocks)
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl GenericItem {
+    /// Encode the generic item into a string with a checksum block
+    ///
+    /// See [`GenericItem::encode`] and [`ChecksumData`](crate::block::ChecksumData)
+    pub fn encode_with_checksum(self, ver: EncodingVersion) -> Result<String, EncoderError> {
+        let mut blocks = vec![StartData(ver).into()];
+
+        blocks.append(&mut self.into_blocks());
+
+        let bytes = crate::block::encode_blocks_checksummed(ver, &blocks)?;
+
+        Ok(crate::encoding::string::encode_string(&bytes))
+    }
+}
+
+/// Length of an item encoded with a start block, the type and name of the item, the given blocks, the extra blocks and an end block
+fn item_len(
+    ver: EncodingVersion,
+    kind: ItemType,
+    name: Option<&str>,
+    blocks: &[Option<&dyn DataEncoder>],
+    extra_blocks: &[AnyBlock],
+) -> Result<EncodedLen, EncoderError> {
+    let mut len = StartData(ver).encoded_len(ver)?
+        + TypeData(kind).encoded_len(ver)?
+        + EndData.encoded_len(ver)?;
+
+    // the name is not stored as a block, avoid allocating one
+    if let Some(name) = name {
+        let name_len = name_len(name).map_err(|e| EncoderError {
+            error: e,
+            during: DataBlockId::NameData,
+        })?;
+        len += block_len(DataBlockId::NameData, ver, name_len);
+    }
+
+    for block in blocks.iter().flatten() {
+        len += block.encoded_len(ver)?;
+    }
+
+    Ok(len + encoded_blocks_len(ver, extra_blocks)?)
+}
+
+/// The oldest version able to encode the given blocks and extra blocks of an item, the type and name of an item are supported by every version
+///
+/// Returns `None` if one of the extra blocks can not be encoded by any version.
+fn item_version(
+    blocks: &[Option<&dyn DataEncoder>],
+    extra_blocks: &[AnyBlock],
+) -> Option<EncodingVersion> {
+    let ver = blocks
+        .iter()
+        .flatten()
+        .map(|block| block.minimum_version())
+        .max()
+        .unwrap_or(EncodingVersion::V1);
+
+    extra_blocks
+        .iter()
+        .try_fold(ver, |ver, block| Some(ver.max(block.minimum_version()?)))
 }
@@ -0,0 +1,121 @@
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+
+use crate::{
+    block::{
+        encode_blocks, encode_blocks_str, AnyBlock, DataBlockId, EndData, SignatureData, StartData,
+    },
+    encoding::{ascii::decode_any, EncoderError},
+    types::EncodingVersion,
+};
+
+use super::{
+    error::{ItemDecodeError, SignatureError},
+    GenericItem,
+};
+
+/// An item along with the signature block of its idstring
+///
+/// Decoding an idstring as a [`GenericItem`] strips the signature block, this type keeps it for verification and re-encoding.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedItem {
+    /// The item
+    pub item: GenericItem,
+    /// The version the item was encoded with, the signature is only valid for this version
+    pub version: EncodingVersion,
+    /// The signature of the item if the idstring was signed
+    pub signature: Option<SignatureData>,
+}
+
+impl SignedItem {
+    /// Decode an item from a string keeping its signature
+    ///
+    /// See [`GenericItem::decode_string`]
+    pub fn decode_string(input: impl AsRef<str>) -> Result<Self, ItemDecodeError> {
+        let blocks = AnyBlock::decode(&mut decode_any(input)?.into_iter())?;
+
+        let version = match blocks.first() {
+            Some(AnyBlock::StartData(StartData(ver))) => *ver,
+            _ => return Err(ItemDecodeError::MissingBlock(DataBlockId::StartData)),
+        };
+        let signature = blocks.iter().find_map(|b| match b {
+            AnyBlock::SignatureData(signature) => Some(*signature),
+            _ => None,
+        });
+
+        Ok(Self {
+            item: GenericItem::from_blocks(blocks)?,
+            version,
+            signature,
+        })
+    }
+
+    /// Verify the signature of the item with the given key
+    pub fn verify(&self, key: &VerifyingKey) -> Result<(), SignatureError> {
+        let signature = self.signature.ok_or(SignatureError::Unsigned)?;
+
+        self.item.verify(self.version, &signature, key)
+    }
+
+    /// Encode the item into a string including the signature block if present
+    pub fn encode(self) -> Result<String, EncoderError> {
+        let mut blocks = vec![StartData(self.version).into()];
+
+        blocks.append(&mut self.item.into_blocks());
+        blocks.extend(self.signature.map(AnyBlock::from));
+        blocks.push(EndData.into());
+
+        encode_blocks_str(self.version, &blocks)
+    }
+
+    /// Get the item without the signature
+    pub fn into_item(self) -> GenericItem {
+        self.item
+    }
+}
+
+impl GenericItem {
+    /// The canonical bytes of the item which are signed
+    ///
+    /// These are the bytes of the start block followed by the blocks of the item in the order of [`GenericItem::into_blocks`],
+    /// without any signature or end block.
+    pub fn canonical_bytes(&self, ver: EncodingVersion) -> Result<Vec<u8>, EncoderError> {
+        let mut blocks = vec![StartData(ver).into()];
+
+        blocks.append(&mut self.clone().into_blocks());
+
+        encode_blocks(ver, &blocks)
+    }
+
+    /// Sign the item with the given key
+    pub fn sign(self, ver: EncodingVersion, key: &SigningKey) -> Result<SignedItem, EncoderError> {
+        let signature = key.sign(&self.canonical_bytes(ver)?);
+
+        Ok(SignedItem {
+            item: self,
+            version: ver,
+            signature: Some(signature.into()),
+        })
+    }
+
+    /// Encode the item into a string with a signature block made with the given key
+    pub fn encode_signed(
+        self,
+        ver: EncodingVersion,
+        key: &SigningKey,
+    ) -> Result<String, EncoderError> {
+        self.sign(ver, key)?.encode()
+    }
+
+    /// Verify a signature of the item made for the given version
+    ///
+    /// Signatures are checked with [`VerifyingKey::verify_strict`], which rejects weak keys and malleable signatures.
+    pub fn verify(
+        &self,
+        ver: EncodingVersion,
+        signature: &SignatureData,
+        key: &VerifyingKey,
+    ) -> Result<(), SignatureError> {
+        Ok(key.verify_strict(&self.canonical_bytes(ver)?, &(*signature).into())?)
+    }
+}
@@ -2,13 +2,45 @@
 //!
 //! For working with items the [`item`] module provides tools for decoding and encoding items from and to strings.
 //!
+//! Builds made out of multiple items can be assembled using the [`build`] module.
+//!
+//! The damage of weapons can be calculated using the [`damage`] module.
+//!
+//! Crafted gear can be planned out of recipes and ingredients using the [`craft`] module.
+//!
+//! Builds shared as WynnBuilder hashes can be read and written using the [`wynnbuilder`] module.
+//!
+//! The [`lore`] module can be used to generate Minecraft style lore for displaying items.
+//!
 //! Alternatively the [`block`] module provides tools for working with the blocks that make up the idstring format.
 //!
 //! Typically idstrings are represented using an encoded string using unicode private use area characters, however the contents of an idstring
 //! may also be represented as bytes. Conversion between the two is provided by the [`encoding::string`] module.
+//!
+//! # Features
+//!
+//! - `serde`: Implements serde traits for the types of this crate
+//! - `database`: Enables the `database` module for looking up the static data of items from an item database
+//! - `render`: Enables the `render` module for rendering item tooltips as png images
+//! - `checksum`: Enables the checksum block for detecting corrupted idstrings, this block is not part of the wynntils format
+//! - `v3-draft`: Enables encoding and decoding the draft `V3` encoding version, which wynntils is not able to decode
+//! - `signature`: Enables signing items with Ed25519 keys using a signature block, this block is not part of the wynntils format
 
 pub mod block;
+pub mod build;
+pub mod craft;
+pub mod damage;
+#[cfg(feature = "database")]
+pub mod database;
 pub mod encoding;
 pub mod item;
+pub mod lore;
+#[cfg(feature = "render")]
+pub mod render;
 pub(crate) mod macros;
 pub mod types;
+pub mod wynnbuilder;
+
+/// Re-export of the Ed25519 implementation used for signing items
+#[cfg(feature = "signature")]
+pub use ed25519_dalek;
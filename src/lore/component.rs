@@ -0,0 +1,255 @@
+use std::fmt::Write;
+
+/// Named colours supported by Minecraft text components
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Color {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+}
+
+impl Color {
+    /// The name of the colour as used in json text components
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Color::Black => "black",
+            Color::DarkBlue => "dark_blue",
+            Color::DarkGreen => "dark_green",
+            Color::DarkAqua => "dark_aqua",
+            Color::DarkRed => "dark_red",
+            Color::DarkPurple => "dark_purple",
+            Color::Gold => "gold",
+            Color::Gray => "gray",
+            Color::DarkGray => "dark_gray",
+            Color::Blue => "blue",
+            Color::Green => "green",
+            Color::Aqua => "aqua",
+            Color::Red => "red",
+            Color::LightPurple => "light_purple",
+            Color::Yellow => "yellow",
+            Color::White => "white",
+        }
+    }
+
+    /// The rgb value of the colour as rendered by the Minecraft client
+    pub const fn rgb(&self) -> [u8; 3] {
+        match self {
+            Color::Black => [0x00, 0x00, 0x00],
+            Color::DarkBlue => [0x00, 0x00, 0xAA],
+            Color::DarkGreen => [0x00, 0xAA, 0x00],
+            Color::DarkAqua => [0x00, 0xAA, 0xAA],
+            Color::DarkRed => [0xAA, 0x00, 0x00],
+            Color::DarkPurple => [0xAA, 0x00, 0xAA],
+            Color::Gold => [0xFF, 0xAA, 0x00],
+            Color::Gray => [0xAA, 0xAA, 0xAA],
+            Color::DarkGray => [0x55, 0x55, 0x55],
+            Color::Blue => [0x55, 0x55, 0xFF],
+            Color::Green => [0x55, 0xFF, 0x55],
+            Color::Aqua => [0x55, 0xFF, 0xFF],
+            Color::Red => [0xFF, 0x55, 0x55],
+            Color::LightPurple => [0xFF, 0x55, 0xFF],
+            Color::Yellow => [0xFF, 0xFF, 0x55],
+            Color::White => [0xFF, 0xFF, 0xFF],
+        }
+    }
+}
+
+/// A Minecraft json text component
+///
+/// Only the subset of the text component format needed for item lore is supported.
+/// Formatting which is not set is inherited from the parent component.
+#[derive(PartialEq, Eq, Clone, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextComponent {
+    /// Text of this component
+    pub text: String,
+    /// Colour of the text
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub color: Option<Color>,
+    /// Whether the text is bold
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub bold: Option<bool>,
+    /// Whether the text is italic
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub italic: Option<bool>,
+    /// Child components which are displayed after the text of this component
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub extra: Vec<TextComponent>,
+}
+
+/// A piece of text with its resolved formatting
+///
+/// See [`TextComponent::spans`]
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct TextSpan<'a> {
+    /// The text of the span
+    pub text: &'a str,
+    /// Colour of the text, white if not set
+    pub color: Color,
+    /// Whether the text is bold
+    pub bold: bool,
+    /// Whether the text is italic
+    pub italic: bool,
+}
+
+impl TextComponent {
+    /// Create a new component with the given text and no formatting
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new component with the given text and colour
+    pub fn colored(text: impl Into<String>, color: Color) -> Self {
+        Self::text(text).color(color)
+    }
+
+    /// Set the colour of this component
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set whether this component is bold
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Set whether this component is italic
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Append a child component
+    pub fn append(mut self, child: TextComponent) -> Self {
+        self.extra.push(child);
+        self
+    }
+
+    /// Get the text of this component and all of its children without any formatting
+    pub fn plain_text(&self) -> String {
+        self.spans().into_iter().map(|s| s.text).collect()
+    }
+
+    /// Flatten this component into spans of text with their formatting resolved
+    ///
+    /// Empty spans are skipped.
+    pub fn spans(&self) -> Vec<TextSpan<'_>> {
+        let mut out = Vec::new();
+        self.collect_spans(Color::White, false, false, &mut out);
+        out
+    }
+
+    fn collect_spans<'a>(
+        &'a self,
+        color: Color,
+        bold: bool,
+        italic: bool,
+        out: &mut Vec<TextSpan<'a>>,
+    ) {
+        let color = self.color.unwrap_or(color);
+        let bold = self.bold.unwrap_or(bold);
+        let italic = self.italic.unwrap_or(italic);
+
+        if !self.text.is_empty() {
+            out.push(TextSpan {
+                text: &self.text,
+                color,
+                bold,
+                italic,
+            });
+        }
+
+        for child in &self.extra {
+            child.collect_spans(color, bold, italic, out);
+        }
+    }
+
+    /// Serialize this component into json
+    ///
+    /// This does not require the `serde` feature and produces the same output as serializing the component with serde.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"text\":");
+        write_json_string(&self.text, out);
+
+        if let Some(color) = self.color {
+            out.push_str(",\"color\":\"");
+            out.push_str(color.name());
+            out.push('"');
+        }
+        if let Some(bold) = self.bold {
+            let _ = write!(out, ",\"bold\":{bold}");
+        }
+        if let Some(italic) = self.italic {
+            let _ = write!(out, ",\"italic\":{italic}");
+        }
+
+        if !self.extra.is_empty() {
+            out.push_str(",\"extra\":[");
+            for (i, child) in self.extra.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                child.write_json(out);
+            }
+            out.push(']');
+        }
+
+        out.push('}');
+    }
+}
+
+fn write_json_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
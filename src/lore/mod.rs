@@ -0,0 +1,593 @@
+//! Module for generating Minecraft style lore for items
+//!
+//! Items are turned into an [`ItemLore`] consisting of a name component and a list of lore lines in the Minecraft json text component format.
+//! The lore is modeled after the tooltips Wynncraft displays for items and is generated only from the data encoded in the item.
+//!
+//! Identifications are encoded using ids, to display their names a [`LoreRenderer`] has to be given an [`IdKeys`] table.
+//! With the `database` feature enabled the renderer can also be given an `database::ItemDatabase` which is used for
+//! the static data of items such as the tier, base stats and requirements, as well as for the values of non extended identifications.
+
+use crate::{
+    block::{
+        CraftedIdentificationData, DamageData, DefenseData, IdentificationData, PowderData,
+        RequirementsData, ShinyData,
+    },
+    item::{
+        error::ItemConvertError, CharmItem, CraftedConsumable, CraftedGear, GearItem, GenericItem,
+        TomeItem,
+    },
+    types::{EffectType, IdKeys, ItemTier, ItemType, RollType, Stat},
+};
+
+#[cfg(feature = "database")]
+use crate::database::ItemDatabase;
+
+mod component;
+#[doc(inline)]
+pub use component::{Color, TextComponent, TextSpan};
+
+pub(crate) mod names;
+
+/// The lore of an item
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemLore {
+    /// The display name of the item
+    pub name: TextComponent,
+    /// The lore lines of the item. Empty lines are used as separators between sections
+    pub lines: Vec<TextComponent>,
+}
+
+impl ItemLore {
+    /// Get the lore as plain text lines, starting with the name
+    pub fn plain_text(&self) -> Vec<String> {
+        std::iter::once(&self.name)
+            .chain(&self.lines)
+            .map(TextComponent::plain_text)
+            .collect()
+    }
+}
+
+/// Generator for item lore
+///
+/// ```
+/// use idmangler_lib::{item::GearItem, lore::LoreRenderer};
+///
+/// let item = GearItem::decode_string("󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿").unwrap();
+/// let lore = LoreRenderer::new().gear(&item);
+///
+/// assert_eq!(lore.name.plain_text(), "Breezehands");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoreRenderer<'a> {
+    id_keys: Option<&'a IdKeys>,
+    #[cfg(feature = "database")]
+    db: Option<&'a ItemDatabase>,
+}
+
+/// The static data of an identified item used in its lore
+#[derive(Default)]
+struct StaticData<'a> {
+    tier: Option<ItemTier>,
+    requirements: Option<&'a RequirementsData>,
+    damage: Option<&'a DamageData>,
+    defense: Option<DefenseData>,
+}
+
+impl<'a> LoreRenderer<'a> {
+    /// Create a new renderer without any name tables
+    ///
+    /// Identifications are displayed using their ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given id table for the names of identifications
+    pub fn with_id_keys(mut self, id_keys: &'a IdKeys) -> Self {
+        self.id_keys = Some(id_keys);
+        self
+    }
+
+    /// Use the given database for the static data of items
+    ///
+    /// This also uses the id table of the database for the names of identifications unless one is already set.
+    #[cfg(feature = "database")]
+    pub fn with_database(mut self, db: &'a ItemDatabase) -> Self {
+        self.db = Some(db);
+        self.id_keys.get_or_insert(db.id_keys());
+        self
+    }
+
+    /// Generate the lore of any item
+    ///
+    /// # Errors
+    /// Returns an error if the item can not be converted into the specific item type for its kind.
+    pub fn item(&self, item: &GenericItem) -> Result<ItemLore, ItemConvertError> {
+        Ok(match item.kind {
+            ItemType::Gear => self.gear(&GearItem::try_from(item.clone())?),
+            ItemType::Tome => self.tome(&TomeItem::try_from(item.clone())?),
+            ItemType::Charm => self.charm(&CharmItem::try_from(item.clone())?),
+            ItemType::CraftedGear => self.crafted_gear(&CraftedGear::try_from(item.clone())?),
+            ItemType::CraftedConsu => {
+                self.crafted_consumable(&CraftedConsumable::try_from(item.clone())?)
+            }
+        })
+    }
+
+    /// Generate the lore of a gear item
+    pub fn gear(&self, item: &GearItem) -> ItemLore {
+        self.identified(
+            &item.name,
+            ItemType::Gear,
+            item.identifications.as_ref(),
+            item.powders.as_ref(),
+            item.shiny.as_ref(),
+            item.rerolls.as_ref().map(|r| r.0),
+        )
+    }
+
+    /// Generate the lore of a tome item
+    pub fn tome(&self, item: &TomeItem) -> ItemLore {
+        self.identified(
+            &item.name,
+            ItemType::Tome,
+            item.identifications.as_ref(),
+            None,
+            None,
+            item.rerolls.as_ref().map(|r| r.0),
+        )
+    }
+
+    /// Generate the lore of a charm item
+    pub fn charm(&self, item: &CharmItem) -> ItemLore {
+        self.identified(
+            &item.name,
+            ItemType::Charm,
+            item.identifications.as_ref(),
+            None,
+            None,
+            item.rerolls.as_ref().map(|r| r.0),
+        )
+    }
+
+    /// Generate the lore of a crafted gear item
+    pub fn crafted_gear(&self, item: &CraftedGear) -> ItemLore {
+        let gear_type = names::gear_type_name(item.gear_type().0);
+        let name = item
+            .name()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Crafted {gear_type}"));
+        let durability = item.durability();
+
+        let mut lines = Vec::new();
+        let mut section = Vec::new();
+        if let Some(damage) = item.damage() {
+            damage_lines(damage, &mut section);
+        }
+        if let Some(defense) = item.defense() {
+            defense_lines(defense, &mut section);
+        }
+        push_section(&mut lines, section);
+
+        push_section(&mut lines, requirement_lines(item.requirements()));
+
+        if let Some(idents) = item.identifications() {
+            push_section(&mut lines, self.crafted_ident_lines(idents));
+        }
+
+        let mut section = Vec::new();
+        if let Some(powders) = item.powders() {
+            section.push(powder_line(powders));
+        }
+        section.push(line().append(TextComponent::colored(
+            format!(
+                "Durability: {}/{} [{}%]",
+                durability.current, durability.max, durability.effect_strenght
+            ),
+            Color::Gray,
+        )));
+        push_section(&mut lines, section);
+
+        lines.push(line().append(TextComponent::colored(
+            format!("Crafted {gear_type}"),
+            Color::DarkAqua,
+        )));
+
+        ItemLore {
+            name: line().append(TextComponent::colored(name, Color::DarkAqua)),
+            lines,
+        }
+    }
+
+    /// Generate the lore of a crafted consumable
+    pub fn crafted_consumable(&self, item: &CraftedConsumable) -> ItemLore {
+        let kind = names::consumable_type_name(item.consumable_type.0);
+        let name = item
+            .name()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Crafted {kind}"));
+
+        let mut lines = Vec::new();
+
+        if let Some(effects) = item.effects() {
+            let mut section = vec![line().append(TextComponent::colored("Effect:", Color::Gold))];
+            for effect in &effects.effects {
+                let value = match effect.kind {
+                    EffectType::Duration => format!("{} s", effect.value),
+                    _ => format!("{:+}", effect.value),
+                };
+                section.push(
+                    line()
+                        .append(TextComponent::colored(
+                            format!("- {}: ", names::effect_name(effect.kind)),
+                            Color::Gray,
+                        ))
+                        .append(TextComponent::colored(value, Color::Gold)),
+                );
+            }
+            push_section(&mut lines, section);
+        }
+
+        push_section(&mut lines, requirement_lines(&item.requirements));
+
+        if let Some(idents) = item.identifications() {
+            push_section(&mut lines, self.crafted_ident_lines(idents));
+        }
+
+        lines.push(line().append(TextComponent::colored(
+            format!("Crafted {kind} [{}/{}]", item.uses.current, item.uses.max),
+            Color::DarkAqua,
+        )));
+
+        ItemLore {
+            name: line().append(TextComponent::colored(name, Color::DarkAqua)),
+            lines,
+        }
+    }
+
+    fn identified(
+        &self,
+        name: &str,
+        kind: ItemType,
+        idents: Option<&IdentificationData>,
+        powders: Option<&PowderData>,
+        shiny: Option<&ShinyData>,
+        rerolls: Option<u8>,
+    ) -> ItemLore {
+        let (data, idents) = self.static_data(name, kind, idents);
+
+        let color = data.tier.map_or(Color::White, names::tier_color);
+        let mut title = line();
+        if shiny.is_some() {
+            title = title.append(TextComponent::colored("⬡ Shiny ", Color::LightPurple));
+        }
+        if idents.is_none() {
+            title = title.append(TextComponent::colored("Unidentified ", color));
+        }
+        title = title.append(TextComponent::colored(name, color));
+
+        let mut lines = Vec::new();
+        let mut section = Vec::new();
+        if let Some(damage) = data.damage {
+            damage_lines(damage, &mut section);
+        }
+        if let Some(defense) = &data.defense {
+            defense_lines(defense, &mut section);
+        }
+        push_section(&mut lines, section);
+
+        if let Some(reqs) = data.requirements {
+            push_section(&mut lines, requirement_lines(reqs));
+        }
+
+        if let Some(idents) = &idents {
+            push_section(
+                &mut lines,
+                idents
+                    .identifications
+                    .iter()
+                    .map(|s| self.stat_line(s))
+                    .collect(),
+            );
+        }
+
+        let mut section = Vec::new();
+        if let Some(powders) = powders {
+            section.push(powder_line(powders));
+        }
+        if let Some(shiny) = shiny {
+            let name = self
+                .shiny_name(shiny.id)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Shiny Stat #{}", shiny.id));
+            section.push(
+                line()
+                    .append(TextComponent::colored("⬡ ", Color::LightPurple))
+                    .append(TextComponent::colored(format!("{name}: "), Color::White))
+                    .append(TextComponent::colored(shiny.val.to_string(), Color::Gray)),
+            );
+        }
+        push_section(&mut lines, section);
+
+        let rerolls = rerolls.filter(|r| *r > 0);
+        match data.tier {
+            Some(tier) => {
+                let rerolls = rerolls.map(|r| format!(" [{r}]")).unwrap_or_default();
+                lines.push(line().append(TextComponent::colored(
+                    format!("{} Item{rerolls}", names::tier_name(tier)),
+                    color,
+                )));
+            }
+            None => {
+                if let Some(rerolls) = rerolls {
+                    lines.push(line().append(TextComponent::colored(
+                        format!("Rerolls: {rerolls}"),
+                        Color::DarkGray,
+                    )));
+                }
+            }
+        }
+
+        ItemLore { name: title, lines }
+    }
+
+    /// Look up the static data of an item and fill in the missing values of its identifications
+    #[cfg(feature = "database")]
+    fn static_data(
+        &self,
+        name: &str,
+        kind: ItemType,
+        idents: Option<&IdentificationData>,
+    ) -> (StaticData<'a>, Option<IdentificationData>) {
+        let mut idents = idents.cloned();
+
+        let Some(entry) = self
+            .db
+            .and_then(|db| db.get(name))
+            .filter(|e| e.kind == kind)
+        else {
+            return (StaticData::default(), idents);
+        };
+
+        if let Some(idents) = &mut idents {
+            // on failure the identifications are left as is and are displayed without their values
+            let _ = entry.hydrate(idents);
+        }
+
+        let data = StaticData {
+            tier: Some(entry.tier),
+            requirements: Some(&entry.requirements),
+            damage: entry.damage.as_ref(),
+            defense: (entry.health != 0 || !entry.defences.is_empty()).then(|| DefenseData {
+                health: entry.health,
+                defences: entry.defences.clone(),
+            }),
+        };
+
+        (data, idents)
+    }
+
+    #[cfg(not(feature = "database"))]
+    fn static_data(
+        &self,
+        _name: &str,
+        _kind: ItemType,
+        idents: Option<&IdentificationData>,
+    ) -> (StaticData<'a>, Option<IdentificationData>) {
+        (StaticData::default(), idents.cloned())
+    }
+
+    /// Get the display name of a shiny stat
+    #[cfg(feature = "database")]
+    fn shiny_name(&self, id: u8) -> Option<&'a str> {
+        self.db
+            .and_then(|db| db.shiny_stats().get(id))
+            .map(|s| s.display_name.as_str())
+    }
+
+    #[cfg(not(feature = "database"))]
+    fn shiny_name(&self, _id: u8) -> Option<&'a str> {
+        None
+    }
+
+    /// Get the display info of an identification
+    fn stat_display(&self, kind: u8) -> names::StatDisplay {
+        match self.id_keys.and_then(|k| k.name(kind)) {
+            Some(key) => names::stat_display(key),
+            None => names::StatDisplay {
+                name: format!("Stat #{kind}"),
+                suffix: "",
+                inverted: false,
+            },
+        }
+    }
+
+    fn stat_line(&self, stat: &Stat) -> TextComponent {
+        let display = self.stat_display(stat.kind);
+
+        let value = match stat.value() {
+            Some(value) => value,
+            None => {
+                // without the base value only the roll is known
+                let roll = match stat.roll {
+                    RollType::Value(roll) => format!("[{roll}%]"),
+                    RollType::PreIdentified => String::from("[?]"),
+                };
+                return line()
+                    .append(TextComponent::colored(roll, Color::DarkGray))
+                    .append(TextComponent::colored(
+                        format!(" {}", display.name),
+                        Color::Gray,
+                    ));
+            }
+        };
+
+        let stars = match stat.roll {
+            RollType::Value(130..) => "***",
+            RollType::Value(125..) => "**",
+            RollType::Value(101..) => "*",
+            _ => "",
+        };
+
+        let line = value_line(value, &display);
+        if stars.is_empty() {
+            line
+        } else {
+            line.append(TextComponent::colored(stars, Color::DarkGreen))
+        }
+    }
+
+    fn crafted_ident_lines(&self, idents: &CraftedIdentificationData) -> Vec<TextComponent> {
+        idents
+            .idents
+            .iter()
+            .map(|s| value_line(s.max, &self.stat_display(s.kind)))
+            .collect()
+    }
+}
+
+/// Create an empty line with the default lore formatting
+fn line() -> TextComponent {
+    TextComponent::text("").italic(false)
+}
+
+/// Append a section of lines separated from the previous section by an empty line
+fn push_section(lines: &mut Vec<TextComponent>, section: Vec<TextComponent>) {
+    if section.is_empty() {
+        return;
+    }
+    if !lines.is_empty() {
+        lines.push(line());
+    }
+    lines.extend(section);
+}
+
+/// A line displaying an identification value coloured by whether the value is beneficial
+fn value_line(value: i32, display: &names::StatDisplay) -> TextComponent {
+    let good = (value >= 0) != display.inverted;
+
+    line()
+        .append(TextComponent::colored(
+            format!("{value:+}{}", display.suffix),
+            if good { Color::Green } else { Color::Red },
+        ))
+        .append(TextComponent::colored(
+            format!(" {}", display.name),
+            Color::Gray,
+        ))
+}
+
+fn damage_lines(damage: &DamageData, lines: &mut Vec<TextComponent>) {
+    lines.push(line().append(TextComponent::colored(
+        format!(
+            "{} Attack Speed",
+            names::attack_speed_name(damage.attack_speed)
+        ),
+        Color::Gray,
+    )));
+
+    // items such as elemental weapons list their neutral damage as 0-0
+    for (elem, range) in damage.damages.iter().filter(|(_, r)| r.end != 0) {
+        let (glyph, name, color) = match elem {
+            Some(elem) => (
+                names::element_glyph(*elem),
+                names::element_name(*elem),
+                names::element_color(*elem),
+            ),
+            None => ("✣", "Neutral", Color::Gold),
+        };
+
+        lines.push(
+            line()
+                .append(TextComponent::colored(format!("{glyph} {name}"), color))
+                .append(TextComponent::colored(
+                    format!(" Damage: {}-{}", range.start, range.end),
+                    Color::Gray,
+                )),
+        );
+    }
+}
+
+fn defense_lines(defense: &DefenseData, lines: &mut Vec<TextComponent>) {
+    if defense.health != 0 {
+        lines.push(
+            line()
+                .append(TextComponent::colored("❤ Health", Color::DarkRed))
+                .append(TextComponent::colored(
+                    format!(": {:+}", defense.health),
+                    Color::Gray,
+                )),
+        );
+    }
+
+    for (elem, value) in &defense.defences {
+        lines.push(
+            line()
+                .append(TextComponent::colored(
+                    format!(
+                        "{} {}",
+                        names::element_glyph(*elem),
+                        names::element_name(*elem)
+                    ),
+                    names::element_color(*elem),
+                ))
+                .append(TextComponent::colored(
+                    format!(" Defence: {value:+}"),
+                    Color::Gray,
+                )),
+        );
+    }
+}
+
+fn requirement_lines(reqs: &RequirementsData) -> Vec<TextComponent> {
+    let mut lines = Vec::new();
+
+    if let Some(class) = reqs.class {
+        lines.push(line().append(TextComponent::colored(
+            format!("Class Req: {}", names::class_name(class)),
+            Color::Gray,
+        )));
+    }
+    if reqs.level > 0 {
+        lines.push(line().append(TextComponent::colored(
+            format!("Combat Lv. Min: {}", reqs.level),
+            Color::Gray,
+        )));
+    }
+    for (skill, value) in &reqs.skills {
+        lines.push(line().append(TextComponent::colored(
+            format!("{} Min: {value}", names::skill_name(*skill)),
+            Color::Gray,
+        )));
+    }
+
+    lines
+}
+
+/// The powder slot line, eg. `[2/3] Powder Slots [✹ ✹]`
+fn powder_line(powders: &PowderData) -> TextComponent {
+    let mut out = line().append(TextComponent::colored(
+        format!(
+            "[{}/{}] Powder Slots",
+            powders.powders.len(),
+            powders.powder_slots
+        ),
+        Color::Gray,
+    ));
+
+    if !powders.powders.is_empty() {
+        out = out.append(TextComponent::colored(" [", Color::Gray));
+        for (i, powder) in powders.powders.iter().enumerate() {
+            if i != 0 {
+                out = out.append(TextComponent::text(" "));
+            }
+            out = out.append(TextComponent::colored(
+                names::element_glyph(powder.element()),
+                names::element_color(powder.element()),
+            ));
+        }
+        out = out.append(TextComponent::colored("]", Color::Gray));
+    }
+
+    out
+}
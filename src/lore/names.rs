@@ -0,0 +1,180 @@
+//! Display names, glyphs and colours used in item lore
+
+use crate::types::{
+    AttackSpeed, ClassType, ConsumableType, CraftedGearType, EffectType, Element, ItemTier,
+    SkillType,
+};
+
+use super::Color;
+
+pub(crate) const fn element_name(elem: Element) -> &'static str {
+    match elem {
+        Element::Earth => "Earth",
+        Element::Thunder => "Thunder",
+        Element::Water => "Water",
+        Element::Fire => "Fire",
+        Element::Air => "Air",
+    }
+}
+
+/// The glyph used by Wynncraft for the element
+pub(crate) const fn element_glyph(elem: Element) -> &'static str {
+    match elem {
+        Element::Earth => "✤",
+        Element::Thunder => "✦",
+        Element::Water => "❉",
+        Element::Fire => "✹",
+        Element::Air => "❋",
+    }
+}
+
+pub(crate) const fn element_color(elem: Element) -> Color {
+    match elem {
+        Element::Earth => Color::DarkGreen,
+        Element::Thunder => Color::Yellow,
+        Element::Water => Color::Aqua,
+        Element::Fire => Color::Red,
+        Element::Air => Color::White,
+    }
+}
+
+pub(crate) const fn tier_name(tier: ItemTier) -> &'static str {
+    match tier {
+        ItemTier::Common => "Normal",
+        ItemTier::Unique => "Unique",
+        ItemTier::Rare => "Rare",
+        ItemTier::Legendary => "Legendary",
+        ItemTier::Fabled => "Fabled",
+        ItemTier::Mythic => "Mythic",
+        ItemTier::Set => "Set",
+    }
+}
+
+pub(crate) const fn tier_color(tier: ItemTier) -> Color {
+    match tier {
+        ItemTier::Common => Color::White,
+        ItemTier::Unique => Color::Yellow,
+        ItemTier::Rare => Color::LightPurple,
+        ItemTier::Legendary => Color::Aqua,
+        ItemTier::Fabled => Color::Red,
+        ItemTier::Mythic => Color::DarkPurple,
+        ItemTier::Set => Color::Green,
+    }
+}
+
+pub(crate) const fn skill_name(skill: SkillType) -> &'static str {
+    match skill {
+        SkillType::Strength => "Strength",
+        SkillType::Dexterity => "Dexterity",
+        SkillType::Intelligence => "Intelligence",
+        SkillType::Defence => "Defence",
+        SkillType::Agility => "Agility",
+    }
+}
+
+pub(crate) const fn class_name(class: ClassType) -> &'static str {
+    match class {
+        ClassType::Mage => "Mage/Dark Wizard",
+        ClassType::Archer => "Archer/Hunter",
+        ClassType::Warrior => "Warrior/Knight",
+        ClassType::Assasin => "Assassin/Ninja",
+        ClassType::Shaman => "Shaman/Skyseer",
+    }
+}
+
+pub(crate) const fn attack_speed_name(speed: AttackSpeed) -> &'static str {
+    match speed {
+        AttackSpeed::SuperFast => "Super Fast",
+        AttackSpeed::VeryFast => "Very Fast",
+        AttackSpeed::Fast => "Fast",
+        AttackSpeed::Normal => "Normal",
+        AttackSpeed::Slow => "Slow",
+        AttackSpeed::VerySlow => "Very Slow",
+        AttackSpeed::SuperSlow => "Super Slow",
+    }
+}
+
+pub(crate) const fn gear_type_name(gear: CraftedGearType) -> &'static str {
+    match gear {
+        CraftedGearType::Spear => "Spear",
+        CraftedGearType::Wand => "Wand",
+        CraftedGearType::Dagger => "Dagger",
+        CraftedGearType::Bow => "Bow",
+        CraftedGearType::Relik => "Relik",
+        CraftedGearType::Weapon => "Weapon",
+        CraftedGearType::Accessory => "Accessory",
+        CraftedGearType::Ring => "Ring",
+        CraftedGearType::Bracelet => "Bracelet",
+        CraftedGearType::Necklace => "Necklace",
+        CraftedGearType::Helmet => "Helmet",
+        CraftedGearType::Chestplate => "Chestplate",
+        CraftedGearType::Leggings => "Leggings",
+        CraftedGearType::Boots => "Boots",
+    }
+}
+
+pub(crate) const fn consumable_type_name(kind: ConsumableType) -> &'static str {
+    match kind {
+        ConsumableType::Potion => "Potion",
+        ConsumableType::Food => "Food",
+        ConsumableType::Scroll => "Scroll",
+    }
+}
+
+pub(crate) const fn effect_name(kind: EffectType) -> &'static str {
+    match kind {
+        EffectType::Heal => "Heal",
+        EffectType::Mana => "Mana",
+        EffectType::Duration => "Duration",
+    }
+}
+
+/// How an identification is displayed
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub(crate) struct StatDisplay {
+    /// Display name of the identification
+    pub name: String,
+    /// Unit displayed after the value
+    pub suffix: &'static str,
+    /// Whether negative values are beneficial, such as with spell costs
+    pub inverted: bool,
+}
+
+/// Work out the display name of an identification from its api name
+///
+/// The api names are camel case and raw values are marked with a `raw` prefix or suffix, other values are percentages.
+/// For example `rawStrength` is displayed as `Strength` and `airDamage` as `Air Damage` with a `%` suffix.
+pub(crate) fn stat_display(key: &str) -> StatDisplay {
+    let (stem, raw) = if let Some(stem) = key.strip_prefix("raw") {
+        (stem, true)
+    } else if let Some(stem) = key.strip_suffix("Raw") {
+        (stem, true)
+    } else {
+        (key, false)
+    };
+
+    let suffix = match key {
+        "lifeSteal" | "manaSteal" | "poison" => "/3s",
+        "manaRegen" => "/5s",
+        _ if raw => "",
+        _ => "%",
+    };
+
+    let mut name = String::with_capacity(stem.len() + 4);
+    for (i, c) in stem.chars().enumerate() {
+        if i == 0 {
+            name.extend(c.to_uppercase());
+        } else {
+            if c.is_uppercase() {
+                name.push(' ');
+            }
+            name.push(c);
+        }
+    }
+
+    StatDisplay {
+        name,
+        suffix,
+        inverted: key.contains("SpellCost"),
+    }
+}
@@ -22,13 +22,18 @@
 /// }
 /// ```
 ///
+/// A variant may have a `#[cfg(...)]` attribute after its documentation. The variant is always declared so the enum is the same in every build,
+/// only converting its number into the enum is limited to builds where the cfg is enabled.
+///
 macro_rules! numbered_enum {
     {
         $(#[doc = $enumdoc:expr])*
         #[repr($enum_type:ty)]
+        $(#[$enummeta:meta])*
         $enumvis:vis enum $name:ident {
             $(
                 $(#[doc = $vardoc:expr])*
+                $(#[cfg($varcfg:meta)])?
                 $variant:ident = $value:expr,
             )+
         }
@@ -41,6 +46,7 @@ macro_rules! numbered_enum {
         #[repr($enum_type)]
         #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        $(#[$enummeta])*
         $enumvis enum $name {
 
             $(
@@ -65,7 +71,10 @@ macro_rules! numbered_enum {
 
             fn try_from(value: $enum_type) -> Result<Self, Self::Error> {
                 match value {
-                    $($value => Ok($name::$variant),)*
+                    $(
+                        $(#[cfg($varcfg)])?
+                        $value => Ok($name::$variant),
+                    )*
                     _ => Err($etype(value)),
                 }
             }
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+use crate::item::error::ItemConvertError;
+
+/// Errors that can occur when rendering a tooltip
+#[derive(Error, Debug)]
+pub enum RenderError {
+    /// The item could not be turned into lore
+    #[error("Invalid item: {0}")]
+    InvalidItem(#[from] ItemConvertError),
+
+    /// The image could not be encoded as a png
+    #[error("Failed to encode png: {0}")]
+    Png(#[from] png::EncodingError),
+}
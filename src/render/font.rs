@@ -0,0 +1,169 @@
+//! The bitmap font bundled for rendering tooltips
+//!
+//! Glyphs are 5 pixels wide and 8 pixels tall with the last row used for descenders.
+//! Each row is stored as a bitmask with the leftmost pixel in the highest bit.
+
+/// A single glyph of the font
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Glyph {
+    /// Width of the glyph in pixels
+    pub width: u32,
+    /// The rows of the glyph
+    pub rows: [u8; 8],
+}
+
+/// Height of the glyphs in pixels
+pub(crate) const GLYPH_HEIGHT: u32 = 8;
+
+/// Width of the glyph bitmasks in pixels
+const GLYPH_BITS: u32 = 5;
+
+/// Glyph used for characters missing from the font
+const MISSING: Glyph = Glyph {
+    width: 5,
+    rows: [
+        0b11111, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11111, 0b00000,
+    ],
+};
+
+/// The glyphs of the font as `(character, width, rows)`
+///
+/// Contains the printable ascii characters and the symbols used in item lore.
+#[rustfmt::skip]
+const GLYPHS: [(char, u32, [u8; 8]); 103] = [
+    (' ', 3, [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('!', 1, [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b00000, 0b10000, 0b00000]),
+    ('"', 3, [0b10100, 0b10100, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('#', 5, [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010, 0b00000]),
+    ('$', 5, [0b00100, 0b01111, 0b10000, 0b01110, 0b00001, 0b11110, 0b00100, 0b00000]),
+    ('%', 5, [0b11001, 0b11010, 0b00010, 0b00100, 0b01000, 0b01011, 0b10011, 0b00000]),
+    ('&', 5, [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101, 0b00000]),
+    ('\'', 1, [0b10000, 0b10000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('(', 3, [0b00100, 0b01000, 0b10000, 0b10000, 0b10000, 0b01000, 0b00100, 0b00000]),
+    (')', 3, [0b10000, 0b01000, 0b00100, 0b00100, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('*', 3, [0b00000, 0b10100, 0b01000, 0b10100, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('+', 5, [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000, 0b00000]),
+    (',', 1, [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b10000, 0b10000, 0b10000]),
+    ('-', 5, [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('.', 1, [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b10000, 0b00000]),
+    ('/', 5, [0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000, 0b00000]),
+    ('0', 5, [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110, 0b00000]),
+    ('1', 5, [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b11111, 0b00000]),
+    ('2', 5, [0b01110, 0b10001, 0b00001, 0b00110, 0b01000, 0b10001, 0b11111, 0b00000]),
+    ('3', 5, [0b01110, 0b10001, 0b00001, 0b00110, 0b00001, 0b10001, 0b01110, 0b00000]),
+    ('4', 5, [0b00011, 0b00101, 0b01001, 0b10001, 0b11111, 0b00001, 0b00001, 0b00000]),
+    ('5', 5, [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110, 0b00000]),
+    ('6', 5, [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('7', 5, [0b11111, 0b10001, 0b00001, 0b00010, 0b00100, 0b00100, 0b00100, 0b00000]),
+    ('8', 5, [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('9', 5, [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100, 0b00000]),
+    (':', 1, [0b00000, 0b10000, 0b10000, 0b00000, 0b00000, 0b10000, 0b10000, 0b00000]),
+    (';', 1, [0b00000, 0b10000, 0b10000, 0b00000, 0b00000, 0b10000, 0b10000, 0b10000]),
+    ('<', 4, [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00000]),
+    ('=', 5, [0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('>', 4, [0b10000, 0b01000, 0b00100, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('?', 5, [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100, 0b00000]),
+    ('@', 5, [0b01110, 0b10001, 0b10111, 0b10101, 0b10111, 0b10000, 0b01111, 0b00000]),
+    ('A', 5, [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('B', 5, [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110, 0b00000]),
+    ('C', 5, [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110, 0b00000]),
+    ('D', 5, [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110, 0b00000]),
+    ('E', 5, [0b11111, 0b10000, 0b10000, 0b11100, 0b10000, 0b10000, 0b11111, 0b00000]),
+    ('F', 5, [0b11111, 0b10000, 0b10000, 0b11100, 0b10000, 0b10000, 0b10000, 0b00000]),
+    ('G', 5, [0b01111, 0b10000, 0b10000, 0b10011, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('H', 5, [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('I', 3, [0b11100, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b11100, 0b00000]),
+    ('J', 5, [0b00001, 0b00001, 0b00001, 0b00001, 0b00001, 0b10001, 0b01110, 0b00000]),
+    ('K', 5, [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001, 0b00000]),
+    ('L', 5, [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111, 0b00000]),
+    ('M', 5, [0b10001, 0b11011, 0b10101, 0b10001, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('N', 5, [0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('O', 5, [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('P', 5, [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000, 0b00000]),
+    ('Q', 5, [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10010, 0b01101, 0b00000]),
+    ('R', 5, [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('S', 5, [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110, 0b00000]),
+    ('T', 5, [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000]),
+    ('U', 5, [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('V', 5, [0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b01010, 0b00100, 0b00000]),
+    ('W', 5, [0b10001, 0b10001, 0b10001, 0b10001, 0b10101, 0b11011, 0b10001, 0b00000]),
+    ('X', 5, [0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b01010, 0b10001, 0b00000]),
+    ('Y', 5, [0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000]),
+    ('Z', 5, [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111, 0b00000]),
+    ('[', 3, [0b11100, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11100, 0b00000]),
+    ('\\', 5, [0b10000, 0b01000, 0b01000, 0b00100, 0b00010, 0b00010, 0b00001, 0b00000]),
+    (']', 3, [0b11100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b11100, 0b00000]),
+    ('^', 5, [0b00100, 0b01010, 0b10001, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('_', 5, [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111]),
+    ('`', 2, [0b10000, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('a', 5, [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111, 0b00000]),
+    ('b', 5, [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110, 0b00000]),
+    ('c', 5, [0b00000, 0b00000, 0b01110, 0b10001, 0b10000, 0b10001, 0b01110, 0b00000]),
+    ('d', 5, [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111, 0b00000]),
+    ('e', 5, [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01111, 0b00000]),
+    ('f', 4, [0b00110, 0b01000, 0b11110, 0b01000, 0b01000, 0b01000, 0b01000, 0b00000]),
+    ('g', 5, [0b00000, 0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b11110]),
+    ('h', 5, [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('i', 1, [0b10000, 0b00000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b00000]),
+    ('j', 5, [0b00001, 0b00000, 0b00001, 0b00001, 0b00001, 0b10001, 0b10001, 0b01110]),
+    ('k', 4, [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b00000]),
+    ('l', 2, [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b01000, 0b00000]),
+    ('m', 5, [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001, 0b00000]),
+    ('n', 5, [0b00000, 0b00000, 0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b00000]),
+    ('o', 5, [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000]),
+    ('p', 5, [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b11110, 0b10000, 0b10000]),
+    ('q', 5, [0b00000, 0b00000, 0b01101, 0b10011, 0b10001, 0b01111, 0b00001, 0b00001]),
+    ('r', 5, [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000, 0b00000]),
+    ('s', 5, [0b00000, 0b00000, 0b01111, 0b10000, 0b01110, 0b00001, 0b11110, 0b00000]),
+    ('t', 3, [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00000]),
+    ('u', 5, [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10001, 0b01111, 0b00000]),
+    ('v', 5, [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00000]),
+    ('w', 5, [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01111, 0b00000]),
+    ('x', 5, [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b00000]),
+    ('y', 5, [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01111, 0b00001, 0b11110]),
+    ('z', 5, [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111, 0b00000]),
+    ('{', 4, [0b00110, 0b01000, 0b01000, 0b10000, 0b01000, 0b01000, 0b00110, 0b00000]),
+    ('|', 1, [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000]),
+    ('}', 4, [0b11000, 0b00100, 0b00100, 0b00010, 0b00100, 0b00100, 0b11000, 0b00000]),
+    ('~', 5, [0b00000, 0b00000, 0b01000, 0b10101, 0b00010, 0b00000, 0b00000, 0b00000]),
+    ('✤', 5, [0b00000, 0b01010, 0b11111, 0b01110, 0b11111, 0b01010, 0b00000, 0b00000]),
+    ('✦', 5, [0b00100, 0b00100, 0b01110, 0b11111, 0b01110, 0b00100, 0b00100, 0b00000]),
+    ('❉', 5, [0b00000, 0b10101, 0b01110, 0b11011, 0b01110, 0b10101, 0b00000, 0b00000]),
+    ('✹', 5, [0b00100, 0b10101, 0b01110, 0b11111, 0b01110, 0b10101, 0b00100, 0b00000]),
+    ('❋', 5, [0b00000, 0b01010, 0b10101, 0b01010, 0b10101, 0b01010, 0b00000, 0b00000]),
+    ('✣', 5, [0b00000, 0b00100, 0b01110, 0b11111, 0b01110, 0b00100, 0b00000, 0b00000]),
+    ('❤', 5, [0b00000, 0b11011, 0b11111, 0b11111, 0b01110, 0b00100, 0b00000, 0b00000]),
+    ('⬡', 5, [0b00100, 0b01010, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00000]),
+];
+
+impl Glyph {
+    /// Check if the pixel at the given position of the glyph is set
+    pub fn pixel(&self, x: u32, y: u32) -> bool {
+        x < GLYPH_BITS && self.rows[y as usize] & (1 << (GLYPH_BITS - 1 - x)) != 0
+    }
+
+    /// The horizontal space taken by the glyph including the spacing after it
+    pub fn advance(&self) -> u32 {
+        self.width + 1
+    }
+}
+
+/// Get the glyph of a character
+///
+/// Characters missing from the font are drawn as a box.
+pub(crate) fn glyph(c: char) -> Glyph {
+    GLYPHS
+        .iter()
+        .find(|(ch, _, _)| *ch == c)
+        .map_or(MISSING, |(_, width, rows)| Glyph {
+            width: *width,
+            rows: *rows,
+        })
+}
+
+/// Width of the given text in pixels
+pub(crate) fn text_width(text: &str, bold: bool) -> u32 {
+    text.chars()
+        .map(|c| glyph(c).advance() + u32::from(bold))
+        .sum()
+}
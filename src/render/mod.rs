@@ -0,0 +1,301 @@
+//! Module for rendering item tooltips as images
+//!
+//! Tooltips are drawn from the [`ItemLore`] generated by the [`lore`](crate::lore) module in the style of the Minecraft item tooltip.
+//! Rendering is done entirely on the cpu using a bitmap font bundled with the library, so no gpu or system fonts are needed.
+//!
+//! ```
+//! use idmangler_lib::{item::GenericItem, lore::LoreRenderer, render::Tooltip};
+//!
+//! let item = GenericItem::decode_string("󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿").unwrap();
+//! let png = Tooltip::new().render_png(&item, &LoreRenderer::new()).unwrap();
+//!
+//! assert_eq!(&png[1..4], b"PNG");
+//! ```
+//!
+//! This module is only available with the `render` feature enabled.
+
+use crate::{
+    item::GenericItem,
+    lore::{ItemLore, LoreRenderer, TextSpan},
+};
+
+mod error;
+#[doc(inline)]
+pub use error::RenderError;
+
+mod font;
+use font::GLYPH_HEIGHT;
+
+/// Height of a line of text including the spacing between lines
+const LINE_HEIGHT: u32 = 10;
+/// Extra space between the name and the lore lines
+const NAME_GAP: u32 = 2;
+/// Space between the edge of the tooltip and the text
+const PADDING: u32 = 4;
+
+const BACKGROUND: [u8; 4] = [0x10, 0x00, 0x10, 0xF0];
+const BORDER_TOP: [u8; 4] = [0x50, 0x00, 0xFF, 0x50];
+const BORDER_BOTTOM: [u8; 4] = [0x28, 0x00, 0x7F, 0x50];
+
+/// Tooltip renderer
+#[derive(Clone, Copy, Debug)]
+pub struct Tooltip {
+    scale: u32,
+}
+
+/// An rgba image
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self { scale: 2 }
+    }
+}
+
+impl Tooltip {
+    /// The largest supported scale
+    pub const MAX_SCALE: u32 = 16;
+
+    /// Create a new tooltip renderer with the default scale of 2
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size of a font pixel in image pixels
+    ///
+    /// A scale of 0 is treated as 1 and scales above [`Tooltip::MAX_SCALE`] are capped to it.
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale.clamp(1, Self::MAX_SCALE);
+        self
+    }
+
+    /// Render the tooltip of an item into a png
+    ///
+    /// The lore of the item is generated using the given [`LoreRenderer`].
+    pub fn render_png(
+        &self,
+        item: &GenericItem,
+        lore: &LoreRenderer,
+    ) -> Result<Vec<u8>, RenderError> {
+        self.render_lore(&lore.item(item)?).to_png()
+    }
+
+    /// Render the tooltip of an item into an image
+    ///
+    /// See [`Tooltip::render_png`]
+    pub fn render(&self, item: &GenericItem, lore: &LoreRenderer) -> Result<Image, RenderError> {
+        Ok(self.render_lore(&lore.item(item)?))
+    }
+
+    /// Render the given lore into an image
+    ///
+    /// Bold text is supported while italic text is drawn upright.
+    pub fn render_lore(&self, lore: &ItemLore) -> Image {
+        let lines: Vec<Vec<TextSpan>> = std::iter::once(&lore.name)
+            .chain(&lore.lines)
+            .map(|l| l.spans())
+            .collect();
+
+        let text_width = lines
+            .iter()
+            .map(|spans| {
+                spans
+                    .iter()
+                    .map(|s| font::text_width(s.text, s.bold))
+                    .sum::<u32>()
+            })
+            .max()
+            .unwrap_or_default();
+        let text_height = lines.len() as u32 * LINE_HEIGHT - (LINE_HEIGHT - GLYPH_HEIGHT)
+            + if lines.len() > 1 { NAME_GAP } else { 0 };
+
+        let width = text_width + PADDING * 2;
+        let height = text_height + PADDING * 2;
+        let mut canvas = Canvas {
+            image: Image::new(width * self.scale, height * self.scale),
+            scale: self.scale,
+        };
+
+        canvas.draw_frame(width, height);
+
+        let mut y = PADDING;
+        for (i, spans) in lines.iter().enumerate() {
+            let mut x = PADDING;
+            for span in spans {
+                x = canvas.draw_text(x, y, span);
+            }
+
+            y += LINE_HEIGHT;
+            if i == 0 {
+                y += NAME_GAP;
+            }
+        }
+
+        canvas.image
+    }
+}
+
+impl Image {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Width of the image in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height of the image in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The pixels of the image as rgba, row by row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Get the rgba value of the pixel at the given position
+    ///
+    /// # Panics
+    /// Panics if the position is outside of the image
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+
+        let i = self.index(x, y);
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Encode the image as a png
+    pub fn to_png(&self) -> Result<Vec<u8>, RenderError> {
+        let mut out = Vec::new();
+
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(out)
+    }
+
+    /// Index of the first byte of a pixel
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    /// Draw a pixel blending it with the existing pixel
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let i = self.index(x, y);
+        let dst = &mut self.pixels[i..i + 4];
+
+        let src_a = color[3] as u32;
+        let dst_a = dst[3] as u32 * (255 - src_a) / 255;
+        let out_a = src_a + dst_a;
+        if out_a == 0 {
+            return;
+        }
+
+        for c in 0..3 {
+            dst[c] = ((color[c] as u32 * src_a + dst[c] as u32 * dst_a) / out_a) as u8;
+        }
+        dst[3] = out_a as u8;
+    }
+}
+
+/// An image which is drawn on using font pixels
+struct Canvas {
+    image: Image,
+    scale: u32,
+}
+
+impl Canvas {
+    /// Fill a single font pixel
+    fn fill(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        for dy in 0..self.scale {
+            for dx in 0..self.scale {
+                self.image
+                    .blend(x * self.scale + dx, y * self.scale + dy, color);
+            }
+        }
+    }
+
+    /// Draw the background and the border of the tooltip
+    fn draw_frame(&mut self, width: u32, height: u32) {
+        // the corners are left empty the same way Minecraft draws tooltips
+        for y in 0..height {
+            for x in 0..width {
+                let corner = (x == 0 || x == width - 1) && (y == 0 || y == height - 1);
+                if !corner {
+                    self.fill(x, y, BACKGROUND);
+                }
+            }
+        }
+
+        for y in 1..height - 1 {
+            let color = gradient(BORDER_TOP, BORDER_BOTTOM, y - 1, height - 3);
+            if y == 1 || y == height - 2 {
+                for x in 1..width - 1 {
+                    self.fill(x, y, color);
+                }
+            } else {
+                self.fill(1, y, color);
+                self.fill(width - 2, y, color);
+            }
+        }
+    }
+
+    /// Draw a span of text with a shadow and return the x position after it
+    fn draw_text(&mut self, mut x: u32, y: u32, span: &TextSpan) -> u32 {
+        let [r, g, b] = span.color.rgb();
+        let shadow = [r / 4, g / 4, b / 4, 0xFF];
+        let color = [r, g, b, 0xFF];
+
+        for c in span.text.chars() {
+            let glyph = font::glyph(c);
+
+            for (offset, color) in [(1, shadow), (0, color)] {
+                for gy in 0..GLYPH_HEIGHT {
+                    for gx in 0..glyph.width {
+                        if glyph.pixel(gx, gy) {
+                            self.fill(x + gx + offset, y + gy + offset, color);
+                            if span.bold {
+                                self.fill(x + gx + offset + 1, y + gy + offset, color);
+                            }
+                        }
+                    }
+                }
+            }
+
+            x += glyph.advance() + u32::from(span.bold);
+        }
+
+        x
+    }
+}
+
+/// Linearly interpolate between two colours
+fn gradient(from: [u8; 4], to: [u8; 4], step: u32, steps: u32) -> [u8; 4] {
+    let mut out = [0; 4];
+    for i in 0..4 {
+        let (a, b) = (from[i] as u32, to[i] as u32);
+        out[i] = ((a * (steps - step) + b * step) / steps.max(1)) as u8;
+    }
+    out
+}
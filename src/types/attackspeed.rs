@@ -16,6 +16,42 @@ numbered_enum! {
     etype BadAttackSpeed;
 }
 
+impl AttackSpeed {
+    /// All of the attack speeds from the fastest to the slowest
+    pub const ALL: [AttackSpeed; 7] = [
+        AttackSpeed::SuperFast,
+        AttackSpeed::VeryFast,
+        AttackSpeed::Fast,
+        AttackSpeed::Normal,
+        AttackSpeed::Slow,
+        AttackSpeed::VerySlow,
+        AttackSpeed::SuperSlow,
+    ];
+
+    /// The damage multiplier of the attack speed
+    ///
+    /// This is the number of melee attacks per second, and the multiplier applied to the weapon damage of spells.
+    pub const fn multiplier(&self) -> f64 {
+        match self {
+            AttackSpeed::SuperFast => 4.3,
+            AttackSpeed::VeryFast => 3.1,
+            AttackSpeed::Fast => 2.5,
+            AttackSpeed::Normal => 2.05,
+            AttackSpeed::Slow => 1.5,
+            AttackSpeed::VerySlow => 0.83,
+            AttackSpeed::SuperSlow => 0.51,
+        }
+    }
+
+    /// Shift the attack speed by the given number of tiers, positive values being faster
+    ///
+    /// The result is clamped between [`AttackSpeed::SuperSlow`] and [`AttackSpeed::SuperFast`].
+    pub fn shifted(&self, tiers: i32) -> AttackSpeed {
+        let idx = (*self as i32 - tiers).clamp(0, Self::ALL.len() as i32 - 1);
+        Self::ALL[idx as usize]
+    }
+}
+
 impl PartialOrd for AttackSpeed {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -9,4 +9,5 @@ pub use super::geartype::BadGearType;
 pub use super::itemtype::BadItemType;
 pub use super::powder::InvalidPowderTier;
 pub use super::skilltype::BadSkillType;
+pub use super::tier::BadItemTier;
 pub use super::version::UnknownEncodingVersion;
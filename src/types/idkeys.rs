@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+/// Table mapping identification names to the ids used within the wynntils format
+///
+/// The wynntils format only stores the numeric id of an identification (see [`Stat::kind`](super::Stat::kind)).
+/// The names of these ids are the same as the identification names used by the Wynncraft api.
+///
+/// The table used by wynntils can be found on <https://github.com/Wynntils/Static-Storage/blob/main/Data-Storage/id_keys.json>
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct IdKeys {
+    by_name: HashMap<String, u8>,
+    by_id: HashMap<u8, String>,
+}
+
+impl IdKeys {
+    /// Create an empty id table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an identification name with the given id to the table
+    ///
+    /// Any previous mapping for the name or the id is replaced.
+    pub fn insert(&mut self, name: impl Into<String>, id: u8) {
+        let name = name.into();
+
+        if let Some(old) = self.by_id.insert(id, name.clone()) {
+            self.by_name.remove(&old);
+        }
+        if let Some(old) = self.by_name.insert(name, id) {
+            if old != id {
+                self.by_id.remove(&old);
+            }
+        }
+    }
+
+    /// Get the id of the identification with the given name
+    pub fn id(&self, name: &str) -> Option<u8> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Get the name of the identification with the given id
+    pub fn name(&self, id: u8) -> Option<&str> {
+        self.by_id.get(&id).map(String::as_str)
+    }
+
+    /// Number of identifications in the table
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    /// Iterate over the names and ids in the table
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.by_name.iter().map(|(name, id)| (name.as_str(), *id))
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u8)> for IdKeys {
+    fn from_iter<T: IntoIterator<Item = (S, u8)>>(iter: T) -> Self {
+        let mut out = Self::new();
+        for (name, id) in iter {
+            out.insert(name, id);
+        }
+        out
+    }
+}
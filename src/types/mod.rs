@@ -7,15 +7,25 @@ mod effect;
 mod element;
 pub mod errors;
 mod geartype;
+mod idkeys;
 mod itemtype;
 mod powder;
 mod skilltype;
+mod special;
 mod stat;
+mod tier;
 mod version;
 
 #[doc(inline)]
 pub use itemtype::ItemType;
 
+#[doc(inline)]
+pub use tier::ItemTier;
+
+#[doc(inline)]
+pub use idkeys::IdKeys;
+
+pub(crate) use stat::scale_by_effectiveness;
 #[doc(inline)]
 pub use stat::CraftedStat;
 #[doc(inline)]
@@ -24,7 +34,7 @@ pub use stat::RollType;
 pub use stat::Stat;
 
 #[doc(inline)]
-pub use version::EncodingVersion;
+pub use version::{EncodingVersion, VersionFeature};
 
 #[doc(inline)]
 pub use geartype::CraftedGearType;
@@ -33,11 +43,14 @@ pub use geartype::CraftedGearType;
 pub use classtype::ClassType;
 
 #[doc(inline)]
-pub use powder::Powder;
+pub use powder::{Powder, PowderStats};
 
 #[doc(inline)]
 pub use skilltype::SkillType;
 
+#[doc(inline)]
+pub use special::{ActiveSpecial, PowderSpecial, MAX_SPECIAL_LEVEL};
+
 #[doc(inline)]
 pub use attackspeed::AttackSpeed;
 
@@ -54,6 +54,107 @@ impl Powder {
     }
 }
 
+/// The effects of a powder when applied to gear
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowderStats {
+    /// Minimum elemental damage added to weapons
+    pub min: i32,
+    /// Maximum elemental damage added to weapons
+    pub max: i32,
+    /// Percentage of the neutral damage of a weapon converted into the element of the powder
+    pub conversion: i32,
+    /// Defence added to the element of the powder on armour
+    pub defence_bonus: i32,
+    /// Defence removed from the element the powder is strong against on armour
+    pub defence_penalty: i32,
+}
+
+const fn stats(
+    min: i32,
+    max: i32,
+    conversion: i32,
+    defence_bonus: i32,
+    defence_penalty: i32,
+) -> PowderStats {
+    PowderStats {
+        min,
+        max,
+        conversion,
+        defence_bonus,
+        defence_penalty,
+    }
+}
+
+/// Stats of the powders indexed by element and tier - 1
+const POWDER_STATS: [[PowderStats; 6]; 5] = [
+    // Earth
+    [
+        stats(3, 6, 17, 2, 1),
+        stats(5, 8, 21, 4, 2),
+        stats(6, 10, 25, 8, 3),
+        stats(7, 10, 31, 14, 5),
+        stats(9, 11, 38, 22, 9),
+        stats(11, 13, 45, 30, 12),
+    ],
+    // Thunder
+    [
+        stats(1, 8, 9, 3, 1),
+        stats(1, 12, 11, 5, 1),
+        stats(2, 15, 13, 9, 2),
+        stats(3, 15, 17, 14, 4),
+        stats(4, 17, 22, 20, 7),
+        stats(5, 20, 28, 28, 10),
+    ],
+    // Water
+    [
+        stats(3, 4, 13, 3, 1),
+        stats(4, 6, 15, 6, 1),
+        stats(5, 8, 17, 11, 2),
+        stats(6, 8, 21, 18, 4),
+        stats(7, 10, 26, 28, 7),
+        stats(9, 11, 32, 40, 10),
+    ],
+    // Fire
+    [
+        stats(2, 5, 14, 3, 1),
+        stats(4, 8, 16, 5, 2),
+        stats(5, 9, 19, 9, 3),
+        stats(6, 9, 24, 16, 5),
+        stats(8, 10, 30, 25, 9),
+        stats(10, 12, 37, 36, 12),
+    ],
+    // Air
+    [
+        stats(2, 6, 11, 3, 1),
+        stats(3, 10, 14, 6, 2),
+        stats(4, 11, 17, 10, 3),
+        stats(5, 11, 22, 16, 5),
+        stats(7, 12, 28, 24, 9),
+        stats(8, 14, 35, 34, 11),
+    ],
+];
+
+impl Powder {
+    /// Get the stats of this powder
+    pub const fn stats(&self) -> PowderStats {
+        POWDER_STATS[self.element as usize][self.tier as usize - 1]
+    }
+
+    /// Get the element whose defence this powder lowers when applied to armour
+    ///
+    /// Each element is strong against the element before it: earth against air, thunder against earth and so on.
+    pub const fn weakened_element(&self) -> Element {
+        match self.element {
+            Element::Earth => Element::Air,
+            Element::Thunder => Element::Earth,
+            Element::Water => Element::Thunder,
+            Element::Fire => Element::Water,
+            Element::Air => Element::Fire,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 #[error("Invalid powder tier: {0}")]
 pub struct InvalidPowderTier(pub u8);
@@ -0,0 +1,146 @@
+use super::{CraftedGearType, Element, Powder};
+
+/// The highest level a powder special can reach
+pub const MAX_SPECIAL_LEVEL: u8 = 5;
+
+/// Enum representing the powder specials unlocked by powdering gear with high tier powders
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowderSpecial {
+    // weapon specials
+    Quake,
+    ChainLightning,
+    Curse,
+    Courage,
+    WindPrison,
+
+    // armour specials
+    Rage,
+    KillStreak,
+    Concentration,
+    Endurance,
+    Dodge,
+}
+
+/// A powder special active on an item along with its level
+///
+/// See [`PowderSpecial::detect`]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActiveSpecial {
+    /// The powder special
+    pub special: PowderSpecial,
+    /// The level of the special, between 1 and [`MAX_SPECIAL_LEVEL`]
+    pub level: u8,
+}
+
+impl PowderSpecial {
+    /// Get the weapon special of the given element
+    pub const fn weapon(element: Element) -> Self {
+        match element {
+            Element::Earth => PowderSpecial::Quake,
+            Element::Thunder => PowderSpecial::ChainLightning,
+            Element::Water => PowderSpecial::Curse,
+            Element::Fire => PowderSpecial::Courage,
+            Element::Air => PowderSpecial::WindPrison,
+        }
+    }
+
+    /// Get the armour special of the given element
+    pub const fn armour(element: Element) -> Self {
+        match element {
+            Element::Earth => PowderSpecial::Rage,
+            Element::Thunder => PowderSpecial::KillStreak,
+            Element::Water => PowderSpecial::Concentration,
+            Element::Fire => PowderSpecial::Endurance,
+            Element::Air => PowderSpecial::Dodge,
+        }
+    }
+
+    /// Get the element of this special
+    pub const fn element(&self) -> Element {
+        match self {
+            PowderSpecial::Quake | PowderSpecial::Rage => Element::Earth,
+            PowderSpecial::ChainLightning | PowderSpecial::KillStreak => Element::Thunder,
+            PowderSpecial::Curse | PowderSpecial::Concentration => Element::Water,
+            PowderSpecial::Courage | PowderSpecial::Endurance => Element::Fire,
+            PowderSpecial::WindPrison | PowderSpecial::Dodge => Element::Air,
+        }
+    }
+
+    /// Check if this is a weapon special
+    pub const fn is_weapon_special(&self) -> bool {
+        matches!(
+            self,
+            PowderSpecial::Quake
+                | PowderSpecial::ChainLightning
+                | PowderSpecial::Curse
+                | PowderSpecial::Courage
+                | PowderSpecial::WindPrison
+        )
+    }
+
+    /// Get the display name of this special
+    pub const fn name(&self) -> &'static str {
+        match self {
+            PowderSpecial::Quake => "Quake",
+            PowderSpecial::ChainLightning => "Chain Lightning",
+            PowderSpecial::Curse => "Curse",
+            PowderSpecial::Courage => "Courage",
+            PowderSpecial::WindPrison => "Wind Prison",
+            PowderSpecial::Rage => "Rage",
+            PowderSpecial::KillStreak => "Kill Streak",
+            PowderSpecial::Concentration => "Concentration",
+            PowderSpecial::Endurance => "Endurance",
+            PowderSpecial::Dodge => "Dodge",
+        }
+    }
+
+    /// Work out which powder special is active on gear of the given type with the given powders
+    ///
+    /// Only powders of tier 4 and above count towards a special, each adding its tier minus 3 to the level of the special of its element.
+    /// The element with the highest level is active, with ties going to the element applied first. The level is capped at [`MAX_SPECIAL_LEVEL`].
+    ///
+    /// Returns [`None`] if no special is active or if the gear type can not hold powders.
+    pub fn detect(gear_type: CraftedGearType, powders: &[Powder]) -> Option<ActiveSpecial> {
+        let special: fn(Element) -> PowderSpecial = match gear_type {
+            CraftedGearType::Spear
+            | CraftedGearType::Wand
+            | CraftedGearType::Dagger
+            | CraftedGearType::Bow
+            | CraftedGearType::Relik
+            | CraftedGearType::Weapon => PowderSpecial::weapon,
+            CraftedGearType::Helmet
+            | CraftedGearType::Chestplate
+            | CraftedGearType::Leggings
+            | CraftedGearType::Boots => PowderSpecial::armour,
+            CraftedGearType::Accessory
+            | CraftedGearType::Ring
+            | CraftedGearType::Bracelet
+            | CraftedGearType::Necklace => return None,
+        };
+
+        // levels in the order the elements were first applied in
+        let mut levels: Vec<(Element, u8)> = Vec::new();
+        for powder in powders.iter().filter(|p| p.tier() >= 4) {
+            let level = powder.tier() - 3;
+
+            match levels.iter_mut().find(|(e, _)| *e == powder.element()) {
+                Some((_, total)) => *total += level,
+                None => levels.push((powder.element(), level)),
+            }
+        }
+
+        let mut best: Option<(Element, u8)> = None;
+        for (elem, level) in levels {
+            if best.is_none_or(|(_, best)| level > best) {
+                best = Some((elem, level));
+            }
+        }
+
+        best.map(|(elem, level)| ActiveSpecial {
+            special: special(elem),
+            level: level.min(MAX_SPECIAL_LEVEL),
+        })
+    }
+}
@@ -36,6 +36,19 @@ impl Stat {
     pub const fn contains_extended(&self) -> bool {
         self.base.is_some()
     }
+
+    /// Calculate the final value of this identification
+    ///
+    /// The value is the base value multiplied by the roll percentage, rounded the same way wynntils rounds it.
+    /// Returns [`None`] if the base value is not known.
+    pub fn value(&self) -> Option<i32> {
+        let base = self.base?;
+
+        Some(match self.roll {
+            RollType::PreIdentified => base,
+            RollType::Value(roll) => (base as f64 * (roll as f64 / 100.0) + 0.5).floor() as i32,
+        })
+    }
 }
 
 impl RollType {
@@ -54,3 +67,18 @@ pub struct CraftedStat {
     /// value of the identification while at full durability
     pub max: i32,
 }
+
+impl CraftedStat {
+    /// Calculate the value of this identification at the given effectiveness
+    ///
+    /// The effectiveness is a percentage, see [`DurabilityData::effectiveness`](crate::block::DurabilityData::effectiveness).
+    /// The value is rounded the same way wynntils rounds it.
+    pub fn value_at(&self, effectiveness: u8) -> i32 {
+        scale_by_effectiveness(self.max, effectiveness)
+    }
+}
+
+/// Scale a value of a crafted item by its effectiveness
+pub(crate) fn scale_by_effectiveness(value: i32, effectiveness: u8) -> i32 {
+    (value as f64 * (effectiveness as f64 / 100.0) + 0.5).floor() as i32
+}
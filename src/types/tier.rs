@@ -0,0 +1,18 @@
+use crate::macros::numbered_enum;
+
+numbered_enum! {
+    /// Enum representing the rarity tiers of items
+    #[repr(u8)]
+    pub enum ItemTier {
+        Common = 0,
+        Unique = 1,
+        Rare = 2,
+        Legendary = 3,
+        Fabled = 4,
+        Mythic = 5,
+        Set = 6,
+    }
+
+    #[error("Invalid item tier id:`{0}`")]
+    etype BadItemTier;
+}
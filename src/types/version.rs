@@ -1,16 +1,162 @@
-use crate::macros::numbered_enum;
+use std::cmp::Ordering;
+
+use crate::{block::DataBlockId, macros::numbered_enum};
 
 numbered_enum! {
     /// This enum represents the version of the encoding being used
     ///
-    /// At the current time the only version of the encoding is the first version
+    /// What each version is able to express can be queried using [`EncodingVersion::supports`], see [`VersionFeature`] for the full list of differences.
+    /// Versions are ordered from oldest to newest.
+    ///
+    /// | Block | V1 | V2 | V3 (draft) |
+    /// |-------|----|----|------------|
+    /// | [`ShinyData`](crate::block::ShinyData) | no rerolls | rerolls | rerolls |
+    /// | [`PowderData`](crate::block::PowderData) | 5 bits per powder | 5 bits per powder | runs of identical powders or 5 bits per powder, whichever is shorter |
+    /// | [`TierData`](crate::block::TierData) | - | - | item tier |
+    /// | blocks with ids in [`FRAMED_IDS`](crate::block::FRAMED_IDS) | length prefixed | length prefixed | length prefixed |
+    /// | all other blocks | unframed | unframed | length prefixed |
+    ///
+    /// The draft `V3` is declared in every build so enabling the `v3-draft` feature does not change this enum,
+    /// without the feature it can not be encoded or decoded, see [`EncodingVersion::is_available`].
+    /// Adding `V3` is a breaking change for exhaustive matches on this enum.
     #[repr(u8)]
     pub enum EncodingVersion {
         /// Represents the version 1 of the wynntils encoding scheme
         V1 = 0,
+        /// Represents the version 2 of the wynntils encoding scheme
         V2 = 1,
+        /// Draft of a third version of the encoding scheme, this version is not supported by wynntils and may change at any time
+        ///
+        /// Encoding and decoding this version requires the `v3-draft` feature.
+        #[cfg(feature = "v3-draft")]
+        V3 = 2,
     }
 
     #[error("Unknown encoding version: {0}")]
     etype UnknownEncodingVersion;
 }
+
+/// Features of the encoding which are not available in every version
+///
+/// Together with [`VersionFeature::since`], [`VersionFeature::block`] and [`VersionFeature::field`] this forms a table of which block fields
+/// each version is able to encode.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum VersionFeature {
+    /// The shiny tracker reroll count of [`ShinyData`](crate::block::ShinyData)
+    ShinyRerolls,
+    /// The rarity tier of an item
+    ItemTier,
+    /// Powders packed as runs of identical powders when that is shorter than packing every powder into 5 bits
+    PackedPowders,
+    /// Every block after the start block is length prefixed, allowing decoders to skip blocks they do not know
+    BlockLengths,
+}
+
+impl VersionFeature {
+    /// All features of the encoding
+    pub const ALL: [VersionFeature; 4] = [
+        Self::ShinyRerolls,
+        Self::ItemTier,
+        Self::PackedPowders,
+        Self::BlockLengths,
+    ];
+
+    /// The first version supporting this feature
+    ///
+    /// Returns `None` if no version available in this build supports the feature.
+    pub const fn since(self) -> Option<EncodingVersion> {
+        match self {
+            Self::ShinyRerolls => Some(EncodingVersion::V2),
+            #[cfg(feature = "v3-draft")]
+            Self::ItemTier | Self::PackedPowders | Self::BlockLengths => Some(EncodingVersion::V3),
+            #[cfg(not(feature = "v3-draft"))]
+            Self::ItemTier | Self::PackedPowders | Self::BlockLengths => None,
+        }
+    }
+
+    /// The block affected by this feature
+    ///
+    /// Returns `None` for features affecting every block.
+    pub const fn block(self) -> Option<DataBlockId> {
+        match self {
+            Self::ShinyRerolls => Some(DataBlockId::ShinyData),
+            Self::ItemTier => Some(DataBlockId::TierData),
+            Self::PackedPowders => Some(DataBlockId::PowderData),
+            Self::BlockLengths => None,
+        }
+    }
+
+    /// The name of the field of [`VersionFeature::block`] affected by this feature
+    ///
+    /// Returns `None` if the feature affects the whole block.
+    pub const fn field(self) -> Option<&'static str> {
+        match self {
+            Self::ShinyRerolls => Some("rr"),
+            Self::PackedPowders => Some("powders"),
+            Self::ItemTier | Self::BlockLengths => None,
+        }
+    }
+
+    /// Whether encoding in a version without this feature loses data
+    ///
+    /// Features which only change how data is laid out, like [`VersionFeature::PackedPowders`], do not lose data.
+    pub const fn is_lossy(self) -> bool {
+        matches!(self, Self::ShinyRerolls | Self::ItemTier)
+    }
+}
+
+impl EncodingVersion {
+    /// All versions available in this build, from oldest to newest
+    pub const ALL: &'static [EncodingVersion] = &[
+        Self::V1,
+        Self::V2,
+        #[cfg(feature = "v3-draft")]
+        Self::V3,
+    ];
+
+    /// The newest stable version, which wynntils is able to decode
+    pub const fn latest() -> Self {
+        Self::V2
+    }
+
+    /// The newest version available in this build, including draft versions
+    ///
+    /// With the `v3-draft` feature enabled this is the draft `V3`, which wynntils is not able to decode.
+    pub const fn latest_draft() -> Self {
+        Self::ALL[Self::ALL.len() - 1]
+    }
+
+    /// Check if this version can be encoded and decoded in this build
+    ///
+    /// This is `false` for the draft `V3` without the `v3-draft` feature.
+    pub const fn is_available(self) -> bool {
+        !matches!(self, Self::V3) || cfg!(feature = "v3-draft")
+    }
+
+    /// Check if this version supports the given feature
+    pub const fn supports(self, feature: VersionFeature) -> bool {
+        match feature.since() {
+            Some(since) => self as u8 >= since as u8,
+            None => false,
+        }
+    }
+
+    /// Iterate over the features supported by this version
+    pub fn features(self) -> impl Iterator<Item = VersionFeature> {
+        VersionFeature::ALL
+            .into_iter()
+            .filter(move |f| self.supports(*f))
+    }
+}
+
+impl PartialOrd for EncodingVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EncodingVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self as u8).cmp(&(*other as u8))
+    }
+}
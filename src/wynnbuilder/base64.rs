@@ -0,0 +1,81 @@
+//! The base64 variant used by WynnBuilder
+//!
+//! Numbers are written big endian using a fixed amount of digits, 6 bits per digit.
+
+use super::WynnBuilderError;
+
+/// The digits of the WynnBuilder base64 alphabet
+const DIGITS: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+-";
+
+/// Get the value of a single digit
+fn digit(c: u8) -> Result<i64, WynnBuilderError> {
+    DIGITS
+        .iter()
+        .position(|d| *d == c)
+        .map(|v| v as i64)
+        .ok_or(WynnBuilderError::InvalidCharacter(c as char))
+}
+
+/// Write a number using exactly `n` digits
+///
+/// Bits which do not fit are dropped, negative numbers are written in two's complement.
+pub(crate) fn write_int(value: i64, n: usize, out: &mut String) {
+    for i in (0..n).rev() {
+        out.push(DIGITS[((value >> (i * 6)) & 0x3f) as usize] as char);
+    }
+}
+
+/// Reader over a WynnBuilder hash
+pub(crate) struct Reader<'a> {
+    data: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a str) -> Self {
+        Self { data }
+    }
+
+    /// Take the next `n` characters
+    pub fn take(&mut self, n: usize) -> Result<&'a str, WynnBuilderError> {
+        let head = self.data.get(..n).ok_or(WynnBuilderError::UnexpectedEnd)?;
+        self.data = &self.data[n..];
+
+        Ok(head)
+    }
+
+    /// Check if the remaining data starts with the given string
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.data.starts_with(prefix)
+    }
+
+    /// Check if the data at the given offset starts with the given string
+    pub fn starts_with_at(&self, offset: usize, prefix: &str) -> bool {
+        self.data
+            .get(offset..)
+            .is_some_and(|rest| rest.starts_with(prefix))
+    }
+
+    /// Read an unsigned number made out of `n` digits
+    pub fn int(&mut self, n: usize) -> Result<i64, WynnBuilderError> {
+        self.take(n)?
+            .bytes()
+            .try_fold(0, |acc, c| Ok((acc << 6) + digit(c)?))
+    }
+
+    /// Read a two's complement signed number made out of `n` digits
+    pub fn signed(&mut self, n: usize) -> Result<i64, WynnBuilderError> {
+        let value = self.int(n)?;
+        let bits = n * 6;
+
+        if n > 0 && value & (1 << (bits - 1)) != 0 {
+            Ok(value - (1 << bits))
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// The data which has not been read yet
+    pub fn rest(&self) -> &'a str {
+        self.data
+    }
+}
@@ -0,0 +1,479 @@
+use crate::{
+    block::{
+        CraftedGearTypeData, CraftedIdentificationData, DamageData, DefenseData, DurabilityData,
+        PowderData, RequirementsData,
+    },
+    build::{Build, BuildPiece, Slot},
+    craft::{Craft, CraftError, IngredientDatabase, Recipe, GRID_SIZE},
+    database::{
+        schema::{
+            invalid, parse_name, ATTACK_SPEED_NAMES, CLASS_NAMES, CONSUMABLE_TYPE_NAMES,
+            GEAR_TYPE_NAMES, SKILL_NAMES,
+        },
+        DatabaseError, ItemDatabase, RollMode,
+    },
+    item::{CraftedGear, GenericItem},
+    types::{AttackSpeed, CraftedStat, Element, ItemType, Powder},
+};
+
+use super::{
+    CraftHash, CustomItem, CustomStat, HashItem, ItemIds, WynnBuilderError, WynnBuilderHash,
+    NO_INGREDIENT_ID, POWDER_SLOTS,
+};
+
+/// WynnBuilder names of the identifications of custom items and the names used by the api
+const IDENTIFICATION_NAMES: [(&str, &str); 50] = [
+    ("str", "rawStrength"),
+    ("dex", "rawDexterity"),
+    ("int", "rawIntelligence"),
+    ("def", "rawDefence"),
+    ("agi", "rawAgility"),
+    ("hprPct", "healthRegen"),
+    ("mr", "manaRegen"),
+    ("sdPct", "spellDamage"),
+    ("mdPct", "mainAttackDamage"),
+    ("ls", "lifeSteal"),
+    ("ms", "manaSteal"),
+    ("xpb", "xpBonus"),
+    ("lb", "lootBonus"),
+    ("ref", "reflection"),
+    ("thorns", "thorns"),
+    ("expd", "exploding"),
+    ("spd", "walkSpeed"),
+    ("atkTier", "rawAttackSpeed"),
+    ("poison", "poison"),
+    ("hpBonus", "rawHealth"),
+    ("spRegen", "soulPointRegen"),
+    ("eSteal", "stealing"),
+    ("hprRaw", "healthRegenRaw"),
+    ("sdRaw", "rawSpellDamage"),
+    ("mdRaw", "rawMainAttackDamage"),
+    ("fDamPct", "fireDamage"),
+    ("wDamPct", "waterDamage"),
+    ("aDamPct", "airDamage"),
+    ("tDamPct", "thunderDamage"),
+    ("eDamPct", "earthDamage"),
+    ("fDefPct", "fireDefence"),
+    ("wDefPct", "waterDefence"),
+    ("aDefPct", "airDefence"),
+    ("tDefPct", "thunderDefence"),
+    ("eDefPct", "earthDefence"),
+    ("spPct1", "1stSpellCost"),
+    ("spRaw1", "raw1stSpellCost"),
+    ("spPct2", "2ndSpellCost"),
+    ("spRaw2", "raw2ndSpellCost"),
+    ("spPct3", "3rdSpellCost"),
+    ("spRaw3", "raw3rdSpellCost"),
+    ("spPct4", "4thSpellCost"),
+    ("spRaw4", "raw4thSpellCost"),
+    ("rainbowRaw", "rawElementalDamage"),
+    ("sprint", "sprint"),
+    ("sprintReg", "sprintRegen"),
+    ("jh", "jumpHeight"),
+    ("lq", "lootQuality"),
+    ("gXp", "gatherXpBonus"),
+    ("gSpd", "gatherSpeed"),
+];
+
+/// WynnBuilder names of the damages and defences of custom items by element
+const ELEMENT_STATS: [(Element, &str, &str); 5] = [
+    (Element::Earth, "eDam", "eDef"),
+    (Element::Thunder, "tDam", "tDef"),
+    (Element::Water, "wDam", "wDef"),
+    (Element::Fire, "fDam", "fDef"),
+    (Element::Air, "aDam", "aDef"),
+];
+
+/// WynnBuilder names of the skill point requirements of custom items in the order of [`SKILL_NAMES`]
+const SKILL_REQS: [&str; 5] = ["strReq", "dexReq", "intReq", "defReq", "agiReq"];
+
+/// An ingredient or recipe as stored in the json used by WynnBuilder
+#[derive(serde::Deserialize)]
+struct Named {
+    name: String,
+    id: u16,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRecipe {
+    #[serde(rename = "type")]
+    kind: String,
+    lvl: JsonRange,
+    #[serde(default)]
+    health_or_damage: JsonRange,
+    #[serde(default)]
+    durability: JsonRange,
+    #[serde(default)]
+    materials: Vec<JsonMaterial>,
+    name: String,
+    id: u16,
+}
+
+#[derive(serde::Deserialize, Default)]
+struct JsonRange {
+    minimum: i32,
+    maximum: i32,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonMaterial {
+    amount: u8,
+}
+
+/// Json which is either an array or an object holding the array under a single key
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum List<T> {
+    List(Vec<T>),
+    Object(std::collections::HashMap<String, Vec<T>>),
+}
+
+impl<T> List<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::List(items) => items,
+            Self::Object(map) => map.into_values().flatten().collect(),
+        }
+    }
+}
+
+impl ItemIds {
+    /// Load an id table from the item json used by WynnBuilder
+    ///
+    /// The json is expected to be either an array of items or an object with an `items` array, such as the `compress.json` file of WynnBuilder.
+    /// Each item needs a `name` and an `id` field, other fields are ignored.
+    pub fn from_json(json: &str) -> Result<Self, DatabaseError> {
+        #[derive(serde::Deserialize)]
+        struct Item {
+            name: String,
+            id: u32,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Items {
+            List(Vec<Item>),
+            Object { items: Vec<Item> },
+        }
+
+        let items = match serde_json::from_str(json)? {
+            Items::List(items) | Items::Object { items } => items,
+        };
+
+        Ok(items.into_iter().map(|i| (i.name, i.id)).collect())
+    }
+
+    /// Add the ingredients from the ingredient json used by WynnBuilder to the table
+    ///
+    /// The json is expected to be either an array of ingredients or an object holding the array, such as the `ingreds_compress.json` file of WynnBuilder.
+    /// Each ingredient needs a `name` and an `id` field, other fields are ignored.
+    pub fn add_ingredients_json(&mut self, json: &str) -> Result<(), DatabaseError> {
+        let ingredients: List<Named> = serde_json::from_str(json)?;
+
+        for ingredient in ingredients.into_vec() {
+            self.insert_ingredient(ingredient.name, ingredient.id);
+        }
+
+        Ok(())
+    }
+
+    /// Add the recipes from the recipe json used by WynnBuilder to the table
+    ///
+    /// The json is expected to be either an array of recipes or an object holding the array, such as the `recipes_compress.json` file of WynnBuilder.
+    /// Recipes for consumables are not added as they can not be crafted, see [`CraftError::ConsumableRecipe`].
+    pub fn add_recipes_json(&mut self, json: &str) -> Result<(), DatabaseError> {
+        let recipes: List<JsonRecipe> = serde_json::from_str(json)?;
+
+        for recipe in recipes.into_vec() {
+            let kind = recipe.kind.to_lowercase();
+            let Some(gear_type) = GEAR_TYPE_NAMES
+                .iter()
+                .find(|(_, name)| *name == kind)
+                .map(|(t, _)| *t)
+            else {
+                if let Some(consumable) = CONSUMABLE_TYPE_NAMES
+                    .iter()
+                    .find(|(_, name)| *name == kind)
+                    .map(|(t, _)| *t)
+                {
+                    self.consumable_recipes.insert(recipe.id, consumable);
+                }
+                continue;
+            };
+
+            let mut material_amounts = [0; 2];
+            for (amount, material) in material_amounts.iter_mut().zip(&recipe.materials) {
+                *amount = material.amount;
+            }
+
+            let level =
+                |v: i32| u8::try_from(v).map_err(|_| invalid(&recipe.name, "lvl", &v.to_string()));
+
+            self.insert_recipe(
+                Recipe {
+                    gear_type,
+                    min_level: level(recipe.lvl.minimum)?,
+                    max_level: level(recipe.lvl.maximum)?,
+                    health_or_damage: recipe.health_or_damage.minimum
+                        ..recipe.health_or_damage.maximum,
+                    durability: recipe.durability.minimum..recipe.durability.maximum,
+                    material_amounts,
+                },
+                recipe.id,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl CraftHash {
+    /// Craft the item using the recipe and ingredients from the id table
+    ///
+    /// The ingredients are looked up by name from the ingredient database.
+    pub fn to_item(
+        &self,
+        ids: &ItemIds,
+        ingredients: &IngredientDatabase,
+    ) -> Result<CraftedGear, WynnBuilderError> {
+        if let Some(consumable) = ids.consumable_recipes.get(&self.recipe) {
+            return Err(CraftError::ConsumableRecipe(*consumable).into());
+        }
+        let recipe = ids
+            .recipe(self.recipe)
+            .ok_or(WynnBuilderError::UnknownRecipeId(self.recipe))?;
+
+        let mut grid = [None; GRID_SIZE];
+        for (slot, id) in grid.iter_mut().zip(self.ingredients) {
+            if id == NO_INGREDIENT_ID {
+                continue;
+            }
+            let name = ids
+                .ingredient(id)
+                .ok_or(WynnBuilderError::UnknownIngredientId(id))?;
+            *slot = Some(ingredients.entry(name)?);
+        }
+
+        Ok(Craft::new(recipe)
+            .with_material_tiers(self.material_tiers)
+            .with_attack_speed(self.attack_speed)
+            .with_ingredients(grid)
+            .craft()?)
+    }
+}
+
+impl CustomItem {
+    /// Convert the custom item into a crafted gear item
+    ///
+    /// Identifications use their highest roll. Custom items have no durability, the crafted item gets a durability of 1 so that it is at full effectiveness.
+    pub fn to_item(&self, db: &ItemDatabase) -> Result<CraftedGear, WynnBuilderError> {
+        let name = self.text("name").unwrap_or_default();
+
+        let gear_type = self
+            .text("type")
+            .map(|t| parse_name(&GEAR_TYPE_NAMES, name, "type", &t.to_lowercase()))
+            .transpose()?;
+
+        let level = self.number("lvl");
+        let requirements = RequirementsData {
+            level: u8::try_from(level).map_err(|_| invalid(name, "lvl", &level.to_string()))?,
+            class: self
+                .text("classReq")
+                .map(|c| parse_name(&CLASS_NAMES, name, "classReq", &c.to_lowercase()))
+                .transpose()?,
+            skills: SKILL_NAMES
+                .iter()
+                .zip(SKILL_REQS)
+                .map(|((skill, _), field)| (*skill, self.number(field)))
+                .filter(|(_, v)| *v != 0)
+                .collect(),
+        };
+
+        let mut damages = Vec::new();
+        for (element, field) in std::iter::once((None, "nDam"))
+            .chain(ELEMENT_STATS.iter().map(|(e, dam, _)| (Some(*e), *dam)))
+        {
+            let Some(text) = self.text(field) else {
+                continue;
+            };
+            let range = text
+                .split_once('-')
+                .and_then(|(min, max)| Some(min.parse().ok()?..max.parse().ok()?))
+                .ok_or_else(|| invalid(name, "damage", text))?;
+            if range != (0..0) {
+                damages.push((element, range));
+            }
+        }
+        let damage = match damages.is_empty() {
+            true => None,
+            false => Some(DamageData {
+                attack_speed: self
+                    .text("atkSpd")
+                    .map(|s| parse_name(&ATTACK_SPEED_NAMES, name, "atkSpd", &s.to_lowercase()))
+                    .transpose()?
+                    .unwrap_or(AttackSpeed::Normal),
+                damages,
+            }),
+        };
+
+        let defences: Vec<_> = ELEMENT_STATS
+            .iter()
+            .map(|(element, _, field)| (*element, self.number(field)))
+            .filter(|(_, v)| *v != 0)
+            .collect();
+        let defense = (self.number("hp") != 0 || !defences.is_empty()).then(|| DefenseData {
+            health: self.number("hp"),
+            defences,
+        });
+
+        let mut idents = Vec::new();
+        for (field, stat) in &self.stats {
+            let max = match stat {
+                CustomStat::Number(v) | CustomStat::Rolled { max: v, .. } => *v,
+                CustomStat::Text(_) => continue,
+            };
+            let Some((_, api_name)) = IDENTIFICATION_NAMES.iter().find(|(n, _)| n == field) else {
+                continue;
+            };
+            let kind = db
+                .id_keys()
+                .id(api_name)
+                .ok_or_else(|| DatabaseError::UnknownStatName {
+                    item: name.to_string(),
+                    name: api_name.to_string(),
+                })?;
+
+            if max != 0 {
+                idents.push(CraftedStat { kind, max });
+            }
+        }
+        idents.sort_by_key(|s| s.kind);
+
+        Ok(CraftedGear::try_from(GenericItem {
+            kind: ItemType::CraftedGear,
+            name: self.text("name").map(str::to_string),
+            crafted_type: gear_type.map(CraftedGearTypeData),
+            crafted_durability: Some(DurabilityData {
+                effect_strenght: 100,
+                current: 1,
+                max: 1,
+            }),
+            crafted_reqs: Some(requirements),
+            crafted_damage: damage,
+            crafted_defense: defense,
+            crafted_identifications: (!idents.is_empty())
+                .then_some(CraftedIdentificationData { idents }),
+            ..Default::default()
+        })?)
+    }
+}
+
+impl WynnBuilderHash {
+    /// Convert the items of the hash into items
+    ///
+    /// Items are created from the database with their highest possible rolls the same way WynnBuilder displays them, along with their powders.
+    /// Crafted items are crafted with [`CraftHash::to_item`] and custom items are converted with [`CustomItem::to_item`].
+    /// Empty slots are skipped.
+    pub fn to_items(
+        &self,
+        ids: &ItemIds,
+        db: &ItemDatabase,
+        ingredients: &IngredientDatabase,
+    ) -> Result<Vec<(Slot, GenericItem)>, WynnBuilderError> {
+        let mut out = Vec::new();
+
+        for (slot, item) in Slot::ALL.into_iter().zip(&self.items) {
+            let powders = self.powders(slot);
+
+            let item = match item {
+                HashItem::None => continue,
+                HashItem::Item(id) => {
+                    let name = ids.name(*id).ok_or(WynnBuilderError::UnknownItemId(*id))?;
+                    let entry = db.entry(name)?;
+                    let mut item = entry.to_gear(RollMode::Max)?;
+
+                    item.powders = powder_data(entry.powder_slots, powders);
+                    item.into()
+                }
+                HashItem::Crafted(craft) => {
+                    let mut item = GenericItem::from(craft.to_item(ids, ingredients)?);
+
+                    // crafted items get their powder slots from the level, which the hash does not store
+                    item.powders = powder_data(powders.len() as u8, powders);
+                    item
+                }
+                HashItem::Custom(custom) => {
+                    let mut item = GenericItem::from(custom.to_item(db)?);
+
+                    let slots = u8::try_from(custom.number("slots")).unwrap_or_default();
+                    item.powders = powder_data(slots, powders);
+                    item
+                }
+            };
+
+            out.push((slot, item));
+        }
+
+        Ok(out)
+    }
+
+    /// Convert the hash into a build
+    ///
+    /// See [`WynnBuilderHash::to_items`]
+    pub fn to_build(
+        &self,
+        ids: &ItemIds,
+        db: &ItemDatabase,
+        ingredients: &IngredientDatabase,
+    ) -> Result<Build, WynnBuilderError> {
+        let mut build = Build::new();
+
+        for (slot, item) in self.to_items(ids, db, ingredients)? {
+            build.set(slot, BuildPiece::from_item(&item, db)?)?;
+        }
+
+        Ok(build)
+    }
+
+    /// Create a hash from the items of a build
+    ///
+    /// Only the names and powders of the items are stored, WynnBuilder always uses the highest possible rolls of the items.
+    /// The skill points and level are left at their defaults.
+    ///
+    /// # Errors
+    /// Returns [`WynnBuilderError::UnsupportedItem`] for items which are not gear.
+    pub fn from_items<'a>(
+        items: impl IntoIterator<Item = (Slot, &'a GenericItem)>,
+        ids: &ItemIds,
+    ) -> Result<Self, WynnBuilderError> {
+        let mut out = Self::default();
+
+        for (slot, item) in items {
+            let name = match (item.kind, &item.name) {
+                (ItemType::Gear, Some(name)) => name,
+                _ => return Err(WynnBuilderError::UnsupportedItem(slot)),
+            };
+
+            out.items[slot.index()] = HashItem::Item(
+                ids.id(name)
+                    .ok_or_else(|| WynnBuilderError::UnknownItemName(name.clone()))?,
+            );
+
+            if let (Some(i), Some(powders)) =
+                (POWDER_SLOTS.iter().position(|s| *s == slot), &item.powders)
+            {
+                out.powders[i] = powders.powders.clone();
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+fn powder_data(powder_slots: u8, powders: &[Powder]) -> Option<PowderData> {
+    (powder_slots > 0 || !powders.is_empty()).then(|| PowderData {
+        powder_slots,
+        powders: powders.to_vec(),
+    })
+}
@@ -0,0 +1,359 @@
+//! The custom item format of WynnBuilder
+//!
+//! A custom item starts with the format version and a flag for fixed identifications, followed by a list of stats.
+//! Each stat starts with its index in [`CUSTOM_STATS`] and is followed by a value depending on the format of the stat.
+
+use super::{base64::Reader, write_checked, write_int, WynnBuilderError};
+
+/// Version of the custom item format
+const CUSTOM_VERSION: &str = "1";
+
+/// Tiers in the order WynnBuilder stores them
+const TIERS: &[&str] = &[
+    "Normal",
+    "Unique",
+    "Rare",
+    "Legendary",
+    "Fabled",
+    "Mythic",
+    "Set",
+    "Crafted",
+];
+
+/// Item types in the order WynnBuilder stores them
+const TYPES: &[&str] = &[
+    "Helmet",
+    "Chestplate",
+    "Leggings",
+    "Boots",
+    "Ring",
+    "Bracelet",
+    "Necklace",
+    "Wand",
+    "Spear",
+    "Bow",
+    "Dagger",
+    "Relik",
+    "Potion",
+    "Scroll",
+    "Food",
+];
+
+/// Attack speeds in the order WynnBuilder stores them
+const ATTACK_SPEEDS: &[&str] = &[
+    "SUPER_SLOW",
+    "VERY_SLOW",
+    "SLOW",
+    "NORMAL",
+    "FAST",
+    "VERY_FAST",
+    "SUPER_FAST",
+];
+
+/// Classes in the order WynnBuilder stores them
+const CLASSES: &[&str] = &["Warrior", "Assassin", "Mage", "Archer", "Shaman"];
+
+/// How the value of a custom item stat is stored
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StatFormat {
+    /// Text prefixed by its length, spaces are written as `%20`
+    Text,
+    /// One of the given values, stored as its index
+    Choice(&'static [&'static str]),
+    /// A single signed number
+    Number,
+    /// A signed range, or a single number if the item has fixed identifications
+    Rolled,
+}
+
+/// The stats of custom items by their WynnBuilder names, in the order of their indices
+pub const CUSTOM_STATS: &[(&str, StatFormat)] = &[
+    ("name", StatFormat::Text),
+    ("lore", StatFormat::Text),
+    ("tier", StatFormat::Choice(TIERS)),
+    ("set", StatFormat::Text),
+    ("slots", StatFormat::Number),
+    ("type", StatFormat::Choice(TYPES)),
+    ("material", StatFormat::Text),
+    ("drop", StatFormat::Text),
+    ("quest", StatFormat::Text),
+    ("nDam", StatFormat::Text),
+    ("fDam", StatFormat::Text),
+    ("wDam", StatFormat::Text),
+    ("aDam", StatFormat::Text),
+    ("tDam", StatFormat::Text),
+    ("eDam", StatFormat::Text),
+    ("atkSpd", StatFormat::Choice(ATTACK_SPEEDS)),
+    ("hp", StatFormat::Number),
+    ("fDef", StatFormat::Number),
+    ("wDef", StatFormat::Number),
+    ("aDef", StatFormat::Number),
+    ("tDef", StatFormat::Number),
+    ("eDef", StatFormat::Number),
+    ("lvl", StatFormat::Number),
+    ("classReq", StatFormat::Choice(CLASSES)),
+    ("strReq", StatFormat::Number),
+    ("dexReq", StatFormat::Number),
+    ("intReq", StatFormat::Number),
+    ("defReq", StatFormat::Number),
+    ("agiReq", StatFormat::Number),
+    ("str", StatFormat::Number),
+    ("dex", StatFormat::Number),
+    ("int", StatFormat::Number),
+    ("agi", StatFormat::Number),
+    ("def", StatFormat::Number),
+    ("id", StatFormat::Number),
+    ("skillpoints", StatFormat::Number),
+    ("reqs", StatFormat::Number),
+    ("nDam_", StatFormat::Text),
+    ("fDam_", StatFormat::Text),
+    ("wDam_", StatFormat::Text),
+    ("aDam_", StatFormat::Text),
+    ("tDam_", StatFormat::Text),
+    ("eDam_", StatFormat::Text),
+    ("majorIds", StatFormat::Text),
+    ("hprPct", StatFormat::Rolled),
+    ("mr", StatFormat::Rolled),
+    ("sdPct", StatFormat::Rolled),
+    ("mdPct", StatFormat::Rolled),
+    ("ls", StatFormat::Rolled),
+    ("ms", StatFormat::Rolled),
+    ("xpb", StatFormat::Rolled),
+    ("lb", StatFormat::Rolled),
+    ("ref", StatFormat::Rolled),
+    ("thorns", StatFormat::Rolled),
+    ("expd", StatFormat::Rolled),
+    ("spd", StatFormat::Rolled),
+    ("atkTier", StatFormat::Rolled),
+    ("poison", StatFormat::Rolled),
+    ("hpBonus", StatFormat::Rolled),
+    ("spRegen", StatFormat::Rolled),
+    ("eSteal", StatFormat::Rolled),
+    ("hprRaw", StatFormat::Rolled),
+    ("sdRaw", StatFormat::Rolled),
+    ("mdRaw", StatFormat::Rolled),
+    ("fDamPct", StatFormat::Rolled),
+    ("wDamPct", StatFormat::Rolled),
+    ("aDamPct", StatFormat::Rolled),
+    ("tDamPct", StatFormat::Rolled),
+    ("eDamPct", StatFormat::Rolled),
+    ("fDefPct", StatFormat::Rolled),
+    ("wDefPct", StatFormat::Rolled),
+    ("aDefPct", StatFormat::Rolled),
+    ("tDefPct", StatFormat::Rolled),
+    ("eDefPct", StatFormat::Rolled),
+    ("spPct1", StatFormat::Rolled),
+    ("spRaw1", StatFormat::Rolled),
+    ("spPct2", StatFormat::Rolled),
+    ("spRaw2", StatFormat::Rolled),
+    ("spPct3", StatFormat::Rolled),
+    ("spRaw3", StatFormat::Rolled),
+    ("spPct4", StatFormat::Rolled),
+    ("spRaw4", StatFormat::Rolled),
+    ("rainbowRaw", StatFormat::Rolled),
+    ("sprint", StatFormat::Rolled),
+    ("sprintReg", StatFormat::Rolled),
+    ("jh", StatFormat::Rolled),
+    ("lq", StatFormat::Rolled),
+    ("gXp", StatFormat::Rolled),
+    ("gSpd", StatFormat::Rolled),
+];
+
+/// A custom item made with the custom item editor of WynnBuilder
+///
+/// Custom items store all of their stats in the hash, using the stat names of WynnBuilder listed in [`CUSTOM_STATS`].
+#[derive(PartialEq, Eq, Clone, Hash, Debug, Default)]
+pub struct CustomItem {
+    /// Identifications have a single value instead of a range
+    pub fixed_ids: bool,
+    /// The stats of the item in the order they are stored in
+    pub stats: Vec<(String, CustomStat)>,
+}
+
+/// The value of a stat of a [`CustomItem`]
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub enum CustomStat {
+    /// Text, also used for [`StatFormat::Choice`] stats and damage ranges such as `10-20`
+    Text(String),
+    /// A number
+    Number(i32),
+    /// A range of rolls. Both values are the same for items with fixed identifications
+    Rolled {
+        /// The lowest roll
+        min: i32,
+        /// The highest roll
+        max: i32,
+    },
+}
+
+impl CustomItem {
+    /// Decode a custom item from its hash, without the `CI-` prefix
+    pub fn decode(hash: &str) -> Result<Self, WynnBuilderError> {
+        let invalid = || WynnBuilderError::InvalidCustom(hash.to_string());
+
+        let mut reader = Reader::new(hash);
+        if reader.take(1)? != CUSTOM_VERSION {
+            return Err(invalid());
+        }
+        let fixed_ids = match reader.take(1)? {
+            "0" => false,
+            "1" => true,
+            _ => return Err(invalid()),
+        };
+
+        let mut stats = Vec::new();
+        while !reader.rest().is_empty() {
+            let (name, format) = *CUSTOM_STATS
+                .get(reader.int(2)? as usize)
+                .ok_or_else(invalid)?;
+
+            let stat = match format {
+                StatFormat::Choice(values) => CustomStat::Text(
+                    values
+                        .get(reader.int(1)? as usize)
+                        .ok_or_else(invalid)?
+                        .to_string(),
+                ),
+                StatFormat::Text => {
+                    let len = reader.int(2)? as usize;
+                    CustomStat::Text(reader.take(len)?.replace("%20", " "))
+                }
+                StatFormat::Number => {
+                    let (len, sign) = read_header(&mut reader, invalid)?;
+                    CustomStat::Number(read_number(&mut reader, len, sign != 0, invalid)?)
+                }
+                StatFormat::Rolled => {
+                    let (len, sign) = read_header(&mut reader, invalid)?;
+                    if fixed_ids {
+                        let value = read_number(&mut reader, len, sign != 0, invalid)?;
+                        CustomStat::Rolled {
+                            min: value,
+                            max: value,
+                        }
+                    } else {
+                        CustomStat::Rolled {
+                            min: read_number(&mut reader, len, sign % 2 == 1, invalid)?,
+                            max: read_number(&mut reader, len, sign > 1, invalid)?,
+                        }
+                    }
+                }
+            };
+
+            stats.push((name.to_string(), stat));
+        }
+
+        Ok(Self { fixed_ids, stats })
+    }
+
+    /// Encode the custom item into a hash, without the `CI-` prefix
+    pub fn encode(&self) -> Result<String, WynnBuilderError> {
+        let mut out = String::new();
+        self.write(&mut out)?;
+        Ok(out)
+    }
+
+    fn write(&self, out: &mut String) -> Result<(), WynnBuilderError> {
+        out.push_str(CUSTOM_VERSION);
+        out.push(if self.fixed_ids { '1' } else { '0' });
+
+        for (name, stat) in &self.stats {
+            let invalid = || WynnBuilderError::InvalidCustomStat(name.clone());
+
+            let index = CUSTOM_STATS
+                .iter()
+                .position(|(n, _)| n == name)
+                .ok_or_else(invalid)?;
+            write_int(index as i64, 2, out);
+
+            match (CUSTOM_STATS[index].1, stat) {
+                (StatFormat::Choice(values), CustomStat::Text(value)) => {
+                    let i = values.iter().position(|v| v == value).ok_or_else(invalid)?;
+                    write_int(i as i64, 1, out);
+                }
+                (StatFormat::Text, CustomStat::Text(value)) => {
+                    let value = value.replace(' ', "%20");
+                    write_checked("custom item text length", value.len() as i64, 2, out)?;
+                    out.push_str(&value);
+                }
+                (StatFormat::Number, CustomStat::Number(value)) => {
+                    write_header(digits(*value), (*value < 0) as u8, out);
+                    write_int(value.unsigned_abs() as i64, digits(*value), out);
+                }
+                (StatFormat::Rolled, CustomStat::Rolled { min, max }) => {
+                    let len = digits(*min).max(digits(*max));
+                    write_header(len, (*min < 0) as u8 + 2 * (*max < 0) as u8, out);
+                    write_int(min.unsigned_abs() as i64, len, out);
+                    if !self.fixed_ids {
+                        write_int(max.unsigned_abs() as i64, len, out);
+                    }
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the value of a stat by its WynnBuilder name
+    pub fn get(&self, name: &str) -> Option<&CustomStat> {
+        self.stats.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Get the value of a text stat
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name) {
+            Some(CustomStat::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a number stat, returning 0 for missing stats the same as WynnBuilder
+    pub fn number(&self, name: &str) -> i32 {
+        match self.get(name) {
+            Some(CustomStat::Number(value)) => *value,
+            _ => 0,
+        }
+    }
+}
+
+/// Read the length and sign digit preceding a number
+fn read_header(
+    reader: &mut Reader,
+    invalid: impl Fn() -> WynnBuilderError,
+) -> Result<(usize, i64), WynnBuilderError> {
+    let len = reader.int(2)? as usize;
+    let sign = reader.int(1)?;
+
+    // longer numbers do not fit into an i32
+    if len > 6 || sign > 3 {
+        return Err(invalid());
+    }
+    Ok((len, sign))
+}
+
+fn read_number(
+    reader: &mut Reader,
+    len: usize,
+    negative: bool,
+    invalid: impl Fn() -> WynnBuilderError,
+) -> Result<i32, WynnBuilderError> {
+    let value = i32::try_from(reader.int(len)?).map_err(|_| invalid())?;
+
+    Ok(if negative { -value } else { value })
+}
+
+fn write_header(len: usize, sign: u8, out: &mut String) {
+    write_int(len as i64, 2, out);
+    write_int(sign as i64, 1, out);
+}
+
+/// Number of digits needed for the absolute value of a number, at least one
+fn digits(value: i32) -> usize {
+    let mut value = value.unsigned_abs();
+    let mut n = 1;
+    while value >= 64 {
+        value >>= 6;
+        n += 1;
+    }
+    n
+}
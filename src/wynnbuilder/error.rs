@@ -0,0 +1,92 @@
+use thiserror::Error;
+
+use crate::{
+    build::{BuildError, Slot},
+    craft::CraftError,
+    item::error::ItemConvertError,
+};
+
+#[cfg(feature = "database")]
+use crate::database::DatabaseError;
+
+/// Errors that can occur when reading or writing WynnBuilder hashes
+#[derive(Error, Debug)]
+pub enum WynnBuilderError {
+    /// The hash does not start with a version number
+    #[error("Invalid build hash version: `{0}`")]
+    InvalidVersion(String),
+
+    /// The hash uses a version of the format which is not supported
+    #[error("Unsupported build hash version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// The hash contains a character which is not part of the WynnBuilder base64 alphabet
+    #[error("Invalid character in build hash: `{0}`")]
+    InvalidCharacter(char),
+
+    /// The hash ended before all of the data was read
+    #[error("Unexpectedly hit the end of the build hash")]
+    UnexpectedEnd,
+
+    /// A crafted item in the hash is invalid
+    #[error("Invalid crafted item: `{0}`")]
+    InvalidCraft(String),
+
+    /// A custom item in the hash is invalid
+    #[error("Invalid custom item: `{0}`")]
+    InvalidCustom(String),
+
+    /// A stat of a custom item is unknown or its value has the wrong format
+    #[error("Custom item stat `{0}` can not be written")]
+    InvalidCustomStat(String),
+
+    /// A powder id in the hash is invalid
+    #[error("Invalid powder id: {0}")]
+    InvalidPowder(i64),
+
+    /// A value does not fit into the space reserved for it in the hash
+    #[error("Value {value} of {field} does not fit into the build hash")]
+    ValueOutOfRange {
+        /// Name of the value
+        field: &'static str,
+        /// The value
+        value: i64,
+    },
+
+    /// The item id is not in the item id table
+    #[error("Item id: {0} is not in the item id table")]
+    UnknownItemId(u32),
+
+    /// The item name is not in the item id table
+    #[error("Item `{0}` is not in the item id table")]
+    UnknownItemName(String),
+
+    /// The ingredient id is not in the item id table
+    #[error("Ingredient id: {0} is not in the item id table")]
+    UnknownIngredientId(u16),
+
+    /// The recipe id is not in the item id table
+    #[error("Recipe id: {0} is not in the item id table")]
+    UnknownRecipeId(u16),
+
+    /// The item can not be converted between a WynnBuilder item and an item of this crate
+    #[error("The item in slot {0:?} can not be converted")]
+    UnsupportedItem(Slot),
+
+    /// A crafted item could not be crafted
+    #[error("Craft error: {0}")]
+    Craft(#[from] CraftError),
+
+    /// The stats of a custom item do not make a valid item
+    #[error("Invalid item: {0}")]
+    Item(#[from] ItemConvertError),
+
+    /// The items could not be assembled into a build
+    #[error("Build error: {0}")]
+    Build(#[from] BuildError),
+
+    /// The static data of an item could not be looked up
+    #[cfg(feature = "database")]
+    #[error("Database error: {0}")]
+    Database(#[from] DatabaseError),
+}
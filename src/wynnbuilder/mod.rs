@@ -0,0 +1,474 @@
+//! Module for reading and writing WynnBuilder build hashes
+//!
+//! [WynnBuilder](https://wynnbuilder.github.io) shares builds as url hashes such as `#5_...`.
+//! This module supports the text based hash format, versions 1 through 7. Versions 6 and 7 store tomes and the ability tree after the powders,
+//! this data is kept as is in [`WynnBuilderHash::extra`] so that such hashes can be written back without losing data.
+//!
+//! Hashes refer to items, ingredients and recipes using the ids WynnBuilder gives them. These ids are not part of the Wynntils format,
+//! an [`ItemIds`] table is needed for converting between the ids and names. Crafted items are stored as the recipe and ingredients
+//! used for crafting them, custom items store all of their stats using the stat names of WynnBuilder.
+//!
+//! With the `database` feature enabled hashes can be converted into items and [`Build`](crate::build::Build)s. Crafted items are
+//! crafted using the [`craft`](crate::craft) module and custom items are converted into crafted gear, as crafted gear is the only item
+//! type which stores its stats in the item itself.
+
+use std::collections::HashMap;
+
+use crate::{
+    build::{SkillPoints, Slot},
+    craft::Recipe,
+    types::{AttackSpeed, ConsumableType, Element, Powder},
+};
+
+mod base64;
+use base64::{write_int, Reader};
+
+#[cfg(feature = "database")]
+mod convert;
+
+mod custom;
+#[doc(inline)]
+pub use custom::{CustomItem, CustomStat, StatFormat, CUSTOM_STATS};
+
+mod error;
+#[doc(inline)]
+pub use error::WynnBuilderError;
+
+/// The newest hash version which is written by [`WynnBuilderHash::encode`] when there is no [`WynnBuilderHash::extra`] data
+pub const HASH_VERSION: u32 = 5;
+
+/// The id offset of the empty items WynnBuilder uses for empty slots
+///
+/// The id of the empty item of a slot is this offset plus the index of the slot in [`Slot::ALL`].
+pub const NONE_ITEM_ID: u32 = 10000;
+
+/// The id WynnBuilder uses for empty ingredient slots of crafted items
+pub const NO_INGREDIENT_ID: u16 = 4000;
+
+/// The default level of a build
+pub const DEFAULT_LEVEL: u8 = 106;
+
+/// The slots which can hold powders, in the order they are stored in the hash
+pub const POWDER_SLOTS: [Slot; 5] = [
+    Slot::Helmet,
+    Slot::Chestplate,
+    Slot::Leggings,
+    Slot::Boots,
+    Slot::Weapon,
+];
+
+/// A build as stored in a WynnBuilder hash
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct WynnBuilderHash {
+    /// The version of the hash format
+    pub version: u32,
+    /// The items of the build in the order of [`Slot::ALL`]
+    pub items: [HashItem; 9],
+    /// Skill points assigned manually
+    pub skill_points: SkillPoints,
+    /// Level of the build
+    pub level: u8,
+    /// Powders of the items in the order of [`POWDER_SLOTS`]
+    pub powders: [Vec<Powder>; 5],
+    /// Data stored after the powders in newer versions, such as tomes and the ability tree
+    pub extra: String,
+}
+
+/// An item in a WynnBuilder hash
+#[derive(PartialEq, Eq, Clone, Hash, Debug, Default)]
+pub enum HashItem {
+    /// The slot is empty
+    #[default]
+    None,
+    /// An item referenced by its WynnBuilder id
+    Item(u32),
+    /// A crafted item
+    Crafted(CraftHash),
+    /// A custom item
+    Custom(CustomItem),
+}
+
+/// A crafted item as stored in a WynnBuilder hash
+///
+/// Crafted items are stored as the recipe and ingredients used for crafting them.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct CraftHash {
+    /// WynnBuilder ids of the ingredients in the order of the crafting grid, [`NO_INGREDIENT_ID`] for empty slots
+    pub ingredients: [u16; 6],
+    /// WynnBuilder id of the recipe
+    pub recipe: u16,
+    /// Tiers of the two materials, between 1 and 3
+    pub material_tiers: [u8; 2],
+    /// Attack speed of the item. One of [`AttackSpeed::Slow`], [`AttackSpeed::Normal`] or [`AttackSpeed::Fast`]
+    pub attack_speed: AttackSpeed,
+}
+
+/// Table mapping item names to WynnBuilder item ids
+///
+/// The table also holds the ingredient ids and recipes needed for crafted items.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ItemIds {
+    by_name: HashMap<String, u32>,
+    by_id: HashMap<u32, String>,
+    ingredients: HashMap<u16, String>,
+    recipes: HashMap<u16, Recipe>,
+    consumable_recipes: HashMap<u16, ConsumableType>,
+}
+
+const CRAFT_SPEEDS: [AttackSpeed; 3] = [AttackSpeed::Slow, AttackSpeed::Normal, AttackSpeed::Fast];
+/// Length of an encoded crafted item without its prefix
+const CRAFT_LEN: usize = 17;
+
+impl Default for WynnBuilderHash {
+    fn default() -> Self {
+        Self {
+            version: HASH_VERSION,
+            items: Default::default(),
+            skill_points: SkillPoints::default(),
+            level: DEFAULT_LEVEL,
+            powders: Default::default(),
+            extra: String::new(),
+        }
+    }
+}
+
+impl WynnBuilderHash {
+    /// Decode a build hash
+    ///
+    /// The input may be the hash on its own (`5_...`), with the leading `#` or a full WynnBuilder url.
+    pub fn decode(input: &str) -> Result<Self, WynnBuilderError> {
+        let hash = input.rsplit('#').next().unwrap_or(input);
+        let (version, data) = hash
+            .split_once('_')
+            .ok_or_else(|| WynnBuilderError::InvalidVersion(hash.to_string()))?;
+        let version: u32 = version
+            .parse()
+            .map_err(|_| WynnBuilderError::InvalidVersion(version.to_string()))?;
+
+        if !(1..=7).contains(&version) {
+            return Err(WynnBuilderError::UnsupportedVersion(version));
+        }
+
+        let mut reader = Reader::new(data);
+        let mut out = Self {
+            version,
+            ..Default::default()
+        };
+
+        for item in &mut out.items {
+            *item = read_item(&mut reader, version)?;
+        }
+
+        if version >= 2 {
+            for points in &mut out.skill_points.0 {
+                *points = reader.signed(2)? as i32;
+            }
+        }
+        if version >= 3 {
+            out.level = reader.int(2)? as u8;
+        }
+
+        for powders in &mut out.powders {
+            *powders = read_powders(&mut reader)?;
+        }
+
+        out.extra = reader.rest().to_string();
+
+        Ok(out)
+    }
+
+    /// Encode the build into a hash, without the leading `#`
+    ///
+    /// Hashes are written using [`HASH_VERSION`] unless the hash contains [`WynnBuilderHash::extra`] data, in which case the version of the hash is kept.
+    pub fn encode(&self) -> Result<String, WynnBuilderError> {
+        let version = if self.extra.is_empty() {
+            HASH_VERSION
+        } else {
+            self.version.max(HASH_VERSION)
+        };
+
+        let mut out = format!("{version}_");
+
+        for (i, item) in self.items.iter().enumerate() {
+            match item {
+                HashItem::None => write_int((NONE_ITEM_ID + i as u32) as i64, 3, &mut out),
+                HashItem::Item(id) => write_checked("item id", *id as i64, 3, &mut out)?,
+                HashItem::Crafted(craft) => {
+                    out.push_str("CR-");
+                    craft.write(&mut out)?;
+                }
+                HashItem::Custom(custom) => {
+                    let custom = custom.encode()?;
+                    // the length includes the prefix
+                    write_checked("custom item length", custom.len() as i64 + 3, 3, &mut out)?;
+                    out.push_str("CI-");
+                    out.push_str(&custom);
+                }
+            }
+        }
+
+        for points in self.skill_points.0 {
+            write_checked("skill points", points as i64, 2, &mut out)?;
+        }
+        write_int(self.level as i64, 2, &mut out);
+
+        for powders in &self.powders {
+            write_powders(powders, &mut out)?;
+        }
+
+        out.push_str(&self.extra);
+
+        Ok(out)
+    }
+
+    /// Get the item in the given slot
+    pub fn item(&self, slot: Slot) -> &HashItem {
+        &self.items[slot.index()]
+    }
+
+    /// Get the powders of the item in the given slot
+    ///
+    /// Returns an empty slice for slots which can not hold powders.
+    pub fn powders(&self, slot: Slot) -> &[Powder] {
+        POWDER_SLOTS
+            .iter()
+            .position(|s| *s == slot)
+            .map_or(&[], |i| &self.powders[i])
+    }
+}
+
+impl CraftHash {
+    /// Decode a crafted item from its hash, without the `CR-` prefix
+    pub fn decode(hash: &str) -> Result<Self, WynnBuilderError> {
+        let invalid = || WynnBuilderError::InvalidCraft(hash.to_string());
+
+        let mut reader = Reader::new(hash);
+        if reader.take(1)? != "1" || hash.len() != CRAFT_LEN {
+            return Err(invalid());
+        }
+
+        let mut ingredients = [0; 6];
+        for ingredient in &mut ingredients {
+            *ingredient = reader.int(2)? as u16;
+        }
+        let recipe = reader.int(2)? as u16;
+
+        let tiers = reader.int(1)?;
+        if !(1..=9).contains(&tiers) {
+            return Err(invalid());
+        }
+        let material_tiers = [((tiers - 1) % 3 + 1) as u8, ((tiers - 1) / 3 + 1) as u8];
+
+        let attack_speed = *CRAFT_SPEEDS
+            .get(reader.int(1)? as usize)
+            .ok_or_else(invalid)?;
+
+        Ok(Self {
+            ingredients,
+            recipe,
+            material_tiers,
+            attack_speed,
+        })
+    }
+
+    /// Encode the crafted item into a hash, without the `CR-` prefix
+    pub fn encode(&self) -> Result<String, WynnBuilderError> {
+        let mut out = String::with_capacity(CRAFT_LEN);
+        self.write(&mut out)?;
+        Ok(out)
+    }
+
+    fn write(&self, out: &mut String) -> Result<(), WynnBuilderError> {
+        out.push('1');
+        for ingredient in self.ingredients {
+            write_checked("ingredient id", ingredient as i64, 2, out)?;
+        }
+        write_checked("recipe id", self.recipe as i64, 2, out)?;
+
+        let [a, b] = self.material_tiers;
+        if !(1..=3).contains(&a) || !(1..=3).contains(&b) {
+            return Err(WynnBuilderError::ValueOutOfRange {
+                field: "material tier",
+                value: a.max(b) as i64,
+            });
+        }
+        write_int((a + (b - 1) * 3) as i64, 1, out);
+
+        let speed = CRAFT_SPEEDS
+            .iter()
+            .position(|s| *s == self.attack_speed)
+            .ok_or(WynnBuilderError::ValueOutOfRange {
+                field: "attack speed",
+                value: u8::from(self.attack_speed) as i64,
+            })?;
+        write_int(speed as i64, 1, out);
+
+        Ok(())
+    }
+}
+
+impl ItemIds {
+    /// Create an empty id table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item with the given id to the table
+    pub fn insert(&mut self, name: impl Into<String>, id: u32) {
+        let name = name.into();
+
+        if let Some(old) = self.by_id.insert(id, name.clone()) {
+            self.by_name.remove(&old);
+        }
+        if let Some(old) = self.by_name.insert(name, id) {
+            if old != id {
+                self.by_id.remove(&old);
+            }
+        }
+    }
+
+    /// Get the id of the item with the given name
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Get the name of the item with the given id
+    pub fn name(&self, id: u32) -> Option<&str> {
+        self.by_id.get(&id).map(String::as_str)
+    }
+
+    /// Add an ingredient with the given id to the table
+    pub fn insert_ingredient(&mut self, name: impl Into<String>, id: u16) {
+        self.ingredients.insert(id, name.into());
+    }
+
+    /// Get the name of the ingredient with the given id
+    pub fn ingredient(&self, id: u16) -> Option<&str> {
+        self.ingredients.get(&id).map(String::as_str)
+    }
+
+    /// Add a recipe with the given id to the table
+    pub fn insert_recipe(&mut self, recipe: Recipe, id: u16) {
+        self.recipes.insert(id, recipe);
+    }
+
+    /// Get the recipe with the given id
+    pub fn recipe(&self, id: u16) -> Option<&Recipe> {
+        self.recipes.get(&id)
+    }
+
+    /// Number of items in the table
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    /// Check if the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u32)> for ItemIds {
+    fn from_iter<T: IntoIterator<Item = (S, u32)>>(iter: T) -> Self {
+        let mut out = Self::new();
+        for (name, id) in iter {
+            out.insert(name, id);
+        }
+        out
+    }
+}
+
+fn write_checked(
+    field: &'static str,
+    value: i64,
+    n: usize,
+    out: &mut String,
+) -> Result<(), WynnBuilderError> {
+    let bits = n as u32 * 6;
+    // signed values are allowed to use the full range in two's complement
+    if value >= (1 << bits) || value < -(1 << (bits - 1)) {
+        return Err(WynnBuilderError::ValueOutOfRange { field, value });
+    }
+
+    write_int(value, n, out);
+    Ok(())
+}
+
+fn read_item(reader: &mut Reader, version: u32) -> Result<HashItem, WynnBuilderError> {
+    let crafted = match version {
+        4 => reader.starts_with("-").then_some(1),
+        5.. => reader.starts_with("CR-").then_some(3),
+        _ => None,
+    };
+    if let Some(prefix) = crafted {
+        reader.take(prefix)?;
+        return Ok(HashItem::Crafted(CraftHash::decode(
+            reader.take(CRAFT_LEN)?,
+        )?));
+    }
+
+    if version >= 5 && reader.starts_with_at(3, "CI-") {
+        let len = reader.int(3)? as usize;
+        let custom = reader.take(len)?;
+        let custom = custom
+            .strip_prefix("CI-")
+            .ok_or_else(|| WynnBuilderError::InvalidCustom(custom.to_string()))?;
+        return Ok(HashItem::Custom(CustomItem::decode(custom)?));
+    }
+
+    let id = reader.int(3)? as u32;
+    Ok(if (NONE_ITEM_ID..NONE_ITEM_ID + 9).contains(&id) {
+        HashItem::None
+    } else {
+        HashItem::Item(id)
+    })
+}
+
+/// Read the powders of one item
+///
+/// Powders are stored in blocks of up to six powders, each powder taking 5 bits with the first powder in the lowest bits.
+fn read_powders(reader: &mut Reader) -> Result<Vec<Powder>, WynnBuilderError> {
+    let blocks = reader.int(1)?;
+    let mut out = Vec::new();
+
+    for _ in 0..blocks {
+        let mut block = reader.int(5)?;
+        for _ in 0..6 {
+            if block == 0 {
+                break;
+            }
+            out.push(powder_from_id((block & 0b11111) - 1)?);
+            block >>= 5;
+        }
+    }
+
+    Ok(out)
+}
+
+fn write_powders(powders: &[Powder], out: &mut String) -> Result<(), WynnBuilderError> {
+    write_checked("powder count", powders.len().div_ceil(6) as i64, 1, out)?;
+
+    for chunk in powders.chunks(6) {
+        let block = chunk
+            .iter()
+            .rev()
+            .fold(0, |acc, p| (acc << 5) + 1 + powder_id(p));
+        write_int(block, 5, out);
+    }
+
+    Ok(())
+}
+
+/// WynnBuilder powder ids are ordered by element and then by tier
+fn powder_id(powder: &Powder) -> i64 {
+    u8::from(powder.element()) as i64 * 6 + powder.tier() as i64 - 1
+}
+
+fn powder_from_id(id: i64) -> Result<Powder, WynnBuilderError> {
+    let element = u8::try_from(id / 6)
+        .ok()
+        .and_then(|e| Element::try_from(e).ok())
+        .ok_or(WynnBuilderError::InvalidPowder(id))?;
+
+    Powder::new(element, (id % 6 + 1) as u8).map_err(|_| WynnBuilderError::InvalidPowder(id))
+}
@@ -0,0 +1,402 @@
+use idmangler_lib::{
+    block::{
+        CraftedGearTypeData, CraftedIdentificationData, DamageData, DefenseData, DurabilityData,
+        RequirementsData,
+    },
+    build::{Build, BuildError, BuildPiece, SkillPoints, Slot},
+    item::{CraftedGear, GenericItem},
+    types::{AttackSpeed, CraftedGearType, CraftedStat, Element, IdKeys, ItemType, SkillType},
+};
+
+fn id_keys() -> IdKeys {
+    IdKeys::from_iter([
+        ("rawHealth", 2),
+        ("lifeSteal", 4),
+        ("rawAgility", 41),
+        ("rawDexterity", 45),
+        ("rawStrength", 46),
+    ])
+}
+
+fn crafted(
+    gear_type: CraftedGearType,
+    skills: Vec<(SkillType, i32)>,
+    idents: Vec<CraftedStat>,
+    defense: Option<DefenseData>,
+) -> BuildPiece {
+    let item = CraftedGear::try_from(GenericItem {
+        kind: ItemType::CraftedGear,
+        crafted_type: Some(CraftedGearTypeData(gear_type)),
+        crafted_durability: Some(DurabilityData {
+            effect_strenght: 100,
+            current: 100,
+            max: 100,
+        }),
+        crafted_reqs: Some(RequirementsData {
+            level: 60,
+            class: None,
+            skills,
+        }),
+        crafted_defense: defense,
+        crafted_damage: matches!(gear_type, CraftedGearType::Spear).then(|| DamageData {
+            attack_speed: AttackSpeed::Normal,
+            damages: vec![(None, 50..80)],
+        }),
+        crafted_identifications: Some(CraftedIdentificationData { idents }),
+        ..Default::default()
+    })
+    .unwrap();
+
+    BuildPiece::from_crafted(&item, &id_keys())
+}
+
+#[test]
+fn crafted_piece() {
+    let piece = crafted(
+        CraftedGearType::Helmet,
+        vec![(SkillType::Strength, 20)],
+        vec![
+            CraftedStat { kind: 46, max: 7 },
+            CraftedStat { kind: 2, max: 100 },
+        ],
+        Some(DefenseData {
+            health: 500,
+            defences: vec![(Element::Earth, 30)],
+        }),
+    );
+
+    assert_eq!(piece.name, "Crafted Helmet");
+    assert_eq!(piece.gear_type, CraftedGearType::Helmet);
+    assert_eq!(piece.identifications, vec![(46, 7), (2, 100)]);
+    assert_eq!(piece.skill_bonuses, SkillPoints([7, 0, 0, 0, 0]));
+    assert_eq!(piece.health, 500);
+    assert_eq!(piece.defences, vec![(Element::Earth, 30)]);
+    assert_eq!(piece.damage, None);
+}
+
+#[test]
+fn slot_compatibility() {
+    let mut build = Build::new();
+    let helmet = crafted(CraftedGearType::Helmet, vec![], vec![], None);
+    let ring = crafted(CraftedGearType::Ring, vec![], vec![], None);
+
+    assert!(matches!(
+        build.set(Slot::Boots, helmet.clone()),
+        Err(BuildError::IncompatibleSlot {
+            slot: Slot::Boots,
+            gear_type: CraftedGearType::Helmet
+        })
+    ));
+    assert!(build.is_empty());
+
+    assert_eq!(build.set(Slot::Helmet, helmet.clone()).unwrap(), None);
+    assert_eq!(
+        build.set(Slot::Helmet, helmet.clone()).unwrap(),
+        Some(helmet)
+    );
+
+    // rings fill both ring slots before running out of space
+    assert_eq!(build.equip(ring.clone()).unwrap(), Slot::Ring1);
+    assert_eq!(build.equip(ring.clone()).unwrap(), Slot::Ring2);
+    assert!(matches!(
+        build.equip(ring),
+        Err(BuildError::NoFreeSlot(CraftedGearType::Ring))
+    ));
+
+    // the accessory fallback type fits any accessory slot
+    let accessory = crafted(CraftedGearType::Accessory, vec![], vec![], None);
+    assert_eq!(build.equip(accessory).unwrap(), Slot::Bracelet);
+
+    let spear = crafted(CraftedGearType::Spear, vec![], vec![], None);
+    assert!(Slot::Weapon.accepts(CraftedGearType::Weapon));
+    assert_eq!(build.equip(spear).unwrap(), Slot::Weapon);
+
+    assert_eq!(build.len(), 5);
+    assert!(build.remove(Slot::Ring2).is_some());
+    assert_eq!(
+        build.iter().map(|(s, _)| s).collect::<Vec<_>>(),
+        vec![Slot::Helmet, Slot::Ring1, Slot::Bracelet, Slot::Weapon]
+    );
+}
+
+#[test]
+fn build_totals() {
+    let mut build = Build::new();
+
+    build
+        .equip(crafted(
+            CraftedGearType::Helmet,
+            vec![(SkillType::Strength, 20)],
+            vec![
+                CraftedStat { kind: 46, max: 7 },
+                CraftedStat { kind: 2, max: 100 },
+            ],
+            Some(DefenseData {
+                health: 500,
+                defences: vec![(Element::Earth, 30), (Element::Air, -10)],
+            }),
+        ))
+        .unwrap();
+    build
+        .equip(crafted(
+            CraftedGearType::Boots,
+            vec![(SkillType::Strength, 35), (SkillType::Agility, 10)],
+            vec![
+                CraftedStat { kind: 2, max: 50 },
+                CraftedStat { kind: 41, max: -3 },
+                CraftedStat { kind: 4, max: 12 },
+            ],
+            Some(DefenseData {
+                health: 250,
+                defences: vec![(Element::Air, 25)],
+            }),
+        ))
+        .unwrap();
+    build
+        .equip(crafted(CraftedGearType::Spear, vec![], vec![], None))
+        .unwrap();
+
+    assert_eq!(
+        build.identifications(),
+        vec![(2, 150), (4, 12), (41, -3), (46, 7)]
+    );
+    assert_eq!(build.health(), 750);
+    assert_eq!(
+        build.defences(),
+        vec![
+            (Element::Earth, 30),
+            (Element::Thunder, 0),
+            (Element::Water, 0),
+            (Element::Fire, 0),
+            (Element::Air, 15),
+        ]
+    );
+    assert_eq!(build.skill_requirements(), SkillPoints([35, 0, 0, 0, 10]));
+    assert_eq!(build.skill_bonuses(), SkillPoints([7, 0, 0, 0, -3]));
+    assert_eq!(build.skill_bonuses()[SkillType::Agility], -3);
+    assert_eq!(build.level_requirement(), 60);
+}
+
+#[test]
+fn solve_equip_order() {
+    let mut build = Build::new();
+
+    // equipping the boots first lets their strength bonus cover part of the helmet requirement
+    build
+        .equip(crafted(
+            CraftedGearType::Helmet,
+            vec![(SkillType::Strength, 50)],
+            vec![CraftedStat { kind: 45, max: 20 }],
+            None,
+        ))
+        .unwrap();
+    build
+        .equip(crafted(
+            CraftedGearType::Boots,
+            vec![(SkillType::Dexterity, 40)],
+            vec![CraftedStat { kind: 46, max: 30 }],
+            None,
+        ))
+        .unwrap();
+    build
+        .equip(crafted(CraftedGearType::Ring, vec![], vec![], None))
+        .unwrap();
+
+    let solution = build.solve_skill_points().unwrap();
+
+    assert_eq!(solution.assigned, SkillPoints([20, 40, 0, 0, 0]));
+    assert_eq!(solution.total, SkillPoints([50, 60, 0, 0, 0]));
+    assert_eq!(solution.order, vec![Slot::Ring1, Slot::Boots, Slot::Helmet]);
+    assert!(build.is_equippable());
+
+    assert!(matches!(
+        build.solve_skill_points_with(50),
+        Err(BuildError::Unequippable {
+            assigned: SkillPoints([20, 40, 0, 0, 0])
+        })
+    ));
+}
+
+#[test]
+fn solve_negative_bonuses() {
+    let mut build = Build::new();
+
+    build
+        .equip(crafted(
+            CraftedGearType::Helmet,
+            vec![(SkillType::Agility, 50)],
+            vec![],
+            None,
+        ))
+        .unwrap();
+    build
+        .equip(crafted(
+            CraftedGearType::Boots,
+            vec![],
+            vec![CraftedStat { kind: 41, max: -10 }],
+            None,
+        ))
+        .unwrap();
+
+    // the helmet needs to keep meeting its requirement after the boots are equipped
+    let solution = build.solve_skill_points().unwrap();
+    assert_eq!(solution.assigned, SkillPoints([0, 0, 0, 0, 60]));
+    assert_eq!(solution.total, SkillPoints([0, 0, 0, 0, 50]));
+}
+
+#[test]
+fn solve_weapon_last() {
+    let mut build = Build::new();
+
+    build
+        .equip(crafted(
+            CraftedGearType::Spear,
+            vec![],
+            vec![CraftedStat { kind: 46, max: 50 }],
+            None,
+        ))
+        .unwrap();
+    build
+        .equip(crafted(
+            CraftedGearType::Helmet,
+            vec![(SkillType::Strength, 50)],
+            vec![],
+            None,
+        ))
+        .unwrap();
+
+    let solution = build.solve_skill_points().unwrap();
+    assert_eq!(solution.assigned, SkillPoints([50, 0, 0, 0, 0]));
+    assert_eq!(solution.order, vec![Slot::Helmet, Slot::Weapon]);
+
+    assert_eq!(
+        Build::new().solve_skill_points().unwrap().assigned,
+        SkillPoints::default()
+    );
+}
+
+#[test]
+fn solve_unequippable() {
+    let mut build = Build::new();
+    build
+        .equip(crafted(
+            CraftedGearType::Helmet,
+            vec![(SkillType::Strength, 101)],
+            vec![],
+            None,
+        ))
+        .unwrap();
+
+    // a single skill can not have more than 100 points assigned
+    assert!(!build.is_equippable());
+
+    let mut build = Build::new();
+    for (gear_type, skill) in [
+        (CraftedGearType::Helmet, SkillType::Strength),
+        (CraftedGearType::Chestplate, SkillType::Dexterity),
+        (CraftedGearType::Leggings, SkillType::Intelligence),
+    ] {
+        build
+            .equip(crafted(gear_type, vec![(skill, 80)], vec![], None))
+            .unwrap();
+    }
+
+    match build.solve_skill_points() {
+        Err(BuildError::Unequippable { assigned }) => assert_eq!(assigned.total(), 240),
+        other => panic!("expected the build to be unequippable: {other:?}"),
+    }
+}
+
+#[cfg(feature = "database")]
+mod database {
+    use idmangler_lib::{
+        build::{Build, BuildError, BuildPiece, SkillPoints, Slot},
+        database::{ItemDatabase, RollMode},
+        item::GenericItem,
+        types::{CraftedGearType, Element, IdKeys},
+    };
+
+    const WARP: &str = "󰀁󰄀󰉗󶅲󷀀󰌉󰄁󲤲󴖴󰅱󱅤󶔢󵥣󱢏󰍍󱦯󰥋󱜻󷀄󱹵󵇨󰉐󲛖󰑙󰐃󰀅󰔆󰘂󰃿";
+    const BREEZEHANDS: &str = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+
+    fn database() -> ItemDatabase {
+        let keys = IdKeys::from_json(include_str!("data/id_keys.json")).unwrap();
+
+        ItemDatabase::from_json(include_str!("data/items.json"), keys).unwrap()
+    }
+
+    #[test]
+    fn decode_build() {
+        let db = database();
+        let cap = db
+            .entry("Ghostly Cap")
+            .unwrap()
+            .to_gear(RollMode::Max)
+            .unwrap()
+            .encode(idmangler_lib::types::EncodingVersion::V2)
+            .unwrap();
+
+        let build = Build::decode([WARP, BREEZEHANDS, cap.as_str()], &db).unwrap();
+
+        assert_eq!(build.get(Slot::Weapon).unwrap().name, "Warp");
+        assert_eq!(build.get(Slot::Bracelet).unwrap().name, "Breezehands");
+        assert_eq!(build.get(Slot::Helmet).unwrap().name, "Ghostly Cap");
+
+        assert_eq!(build.health(), 420);
+        assert_eq!(
+            build.defences()[Element::Water as usize],
+            (Element::Water, 30)
+        );
+        assert_eq!(build.skill_requirements(), SkillPoints([0, 55, 15, 0, 100]));
+        assert_eq!(build.skill_bonuses(), SkillPoints([0, 1, 85, 0, 25]));
+        assert_eq!(build.level_requirement(), 75);
+
+        let solution = build.solve_skill_points().unwrap();
+        assert_eq!(solution.assigned, SkillPoints([0, 55, 15, 0, 100]));
+        assert_eq!(*solution.order.last().unwrap(), Slot::Weapon);
+    }
+
+    #[test]
+    fn unidentified_piece() {
+        let db = database();
+        let mut item = db
+            .entry("Ghostly Cap")
+            .unwrap()
+            .to_gear(RollMode::Max)
+            .unwrap();
+        item.identifications = None;
+
+        let piece = BuildPiece::from_gear(&item, &db).unwrap();
+
+        assert_eq!(piece.gear_type, CraftedGearType::Helmet);
+        assert_eq!(piece.skill_bonuses, SkillPoints([0, 0, 65, 0, 0]));
+    }
+
+    #[test]
+    fn invalid_build_items() {
+        let db = database();
+        let tome = db
+            .entry("Tome of Lesser Allegiance")
+            .unwrap()
+            .to_item(RollMode::Max);
+
+        assert!(matches!(
+            BuildPiece::from_item(&tome, &db),
+            Err(BuildError::InvalidItemType(_))
+        ));
+        assert!(matches!(
+            Build::decode([WARP, WARP], &db),
+            Err(BuildError::NoFreeSlot(CraftedGearType::Wand))
+        ));
+        assert!(matches!(
+            BuildPiece::from_item(
+                &GenericItem {
+                    name: Some(String::from("Not an item")),
+                    ..Default::default()
+                },
+                &db
+            ),
+            Err(BuildError::Database(_))
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use idmangler_lib::{
+    block::{
+        decode_str, AnyBlock, BundleData, DataBlockId, PowderData, StartData, RESERVED_IDS_START,
+    },
+    encoding::DecodeError,
+    item::{
+        decode_bundle, decode_bundle_generic, encode_bundle, error::ItemDecodeError, CharmItem,
+        GearItem, GenericItem, Item, TomeItem,
+    },
+    types::{Element, EncodingVersion, ItemType, Powder},
+};
+
+const BREEZEHANDS: &str = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+const COMPLEX: &str = "󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿";
+
+fn items() -> Vec<Item> {
+    vec![
+        GearItem {
+            name: "Warp".to_string(),
+            identifications: None,
+            powders: Some(PowderData {
+                powder_slots: 2,
+                powders: vec![Powder::new(Element::Air, 6).unwrap()],
+            }),
+            shiny: None,
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        }
+        .into(),
+        TomeItem {
+            name: "Tome of Lesser Allegiance".to_string(),
+            identifications: None,
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        }
+        .into(),
+        CharmItem {
+            name: "Charm of the Void".to_string(),
+            identifications: None,
+            rerolls: None,
+            tier: None,
+            extra_blocks: Vec::new(),
+        }
+        .into(),
+    ]
+}
+
+#[test]
+fn roundtrip() {
+    let items = items();
+    let encoded = encode_bundle(items.clone(), EncodingVersion::V2).unwrap();
+
+    assert_eq!(decode_bundle(&encoded).unwrap(), items);
+}
+
+#[test]
+fn header() {
+    let encoded = encode_bundle(items(), EncodingVersion::V1).unwrap();
+
+    // the first item is read up to its end block
+    let blocks = decode_str(&encoded).unwrap();
+    assert_eq!(
+        blocks[0],
+        AnyBlock::StartData(StartData(EncodingVersion::V1))
+    );
+    assert_eq!(blocks[1], AnyBlock::BundleData(BundleData(3)));
+}
+
+#[test]
+fn header_id() {
+    // the header is an extension of this crate and does not take an id from the wynntils format
+    assert!(DataBlockId::BundleData as u8 >= RESERVED_IDS_START);
+}
+
+#[test]
+fn empty_bundle() {
+    let encoded = encode_bundle(Vec::<GenericItem>::new(), EncodingVersion::V2).unwrap();
+
+    assert!(decode_bundle(encoded).unwrap().is_empty());
+}
+
+#[test]
+fn concatenated_idstrings() {
+    let input = format!("{BREEZEHANDS}{COMPLEX}\n{BREEZEHANDS}");
+    let items = decode_bundle_generic(input).unwrap();
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0], GenericItem::decode_string(BREEZEHANDS).unwrap());
+    assert_eq!(items[1], GenericItem::decode_string(COMPLEX).unwrap());
+    assert_eq!(items[2], items[0]);
+}
+
+#[test]
+fn mixed_input() {
+    let bundle = encode_bundle(items(), EncodingVersion::V2).unwrap();
+    let input = format!("{BREEZEHANDS} {bundle}");
+    let items = decode_bundle(input).unwrap();
+
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[0].kind(), ItemType::Gear);
+    assert!(matches!(&items[3], Item::Charm(charm) if charm.name == "Charm of the Void"));
+}
+
+#[test]
+fn truncated_bundle() {
+    let mut encoded = encode_bundle(items(), EncodingVersion::V2).unwrap();
+    // drop the last item
+    let cut = encoded.chars().count() / 2;
+    encoded = encoded.chars().take(cut).collect();
+
+    assert!(matches!(
+        decode_bundle(encoded),
+        Err(ItemDecodeError::DecoderError(e)) if matches!(e.error, DecodeError::UnexpectedEndOfBytes)
+    ));
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "checksum")]
+
+use idmangler_lib::{
+    block::{
+        decode_str, encode_blocks_checksummed, AnyBlock, BundleData, ChecksumData, EndData,
+        StartData,
+    },
+    encoding::{
+        string::{decode_string, encode_string},
+        DecodeError,
+    },
+    item::{decode_bundle_generic, error::ItemDecodeError, GenericItem},
+    types::EncodingVersion,
+};
+
+const BREEZEHANDS: &str = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+
+#[test]
+fn crc32() {
+    assert_eq!(ChecksumData::of(b"123456789"), ChecksumData(0xCBF43926));
+    assert_eq!(ChecksumData::of(b""), ChecksumData(0));
+}
+
+#[test]
+fn roundtrip() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let encoded = item
+        .clone()
+        .encode_with_checksum(EncodingVersion::V1)
+        .unwrap();
+
+    let blocks = decode_str(&encoded).unwrap();
+    assert!(matches!(
+        blocks[blocks.len() - 2],
+        AnyBlock::ChecksumData(_)
+    ));
+    assert_eq!(blocks[blocks.len() - 1], AnyBlock::EndData(EndData));
+
+    assert_eq!(GenericItem::decode_string(&encoded).unwrap(), item);
+}
+
+#[test]
+fn checksum_before_end() {
+    let blocks = [StartData(EncodingVersion::V2).into(), EndData.into()];
+
+    // the trailing end block is replaced by the checksum and a new end block
+    let with_end = encode_blocks_checksummed(EncodingVersion::V2, &blocks).unwrap();
+    let without_end = encode_blocks_checksummed(EncodingVersion::V2, &blocks[..1]).unwrap();
+
+    assert_eq!(with_end, without_end);
+    // the checksum block is length prefixed so decoders without the checksum feature can skip it
+    assert_eq!(with_end.len(), 2 + 6 + 1);
+}
+
+#[test]
+fn corrupted_data() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let encoded = item.encode_with_checksum(EncodingVersion::V1).unwrap();
+
+    // change a letter of the name
+    let mut bytes = decode_string(&encoded).unwrap();
+    bytes[5] ^= 0x01;
+    let corrupted = encode_string(&bytes);
+
+    match GenericItem::decode_string(corrupted) {
+        Err(ItemDecodeError::DecoderError(e)) => {
+            assert!(matches!(e.error, DecodeError::ChecksumMismatch { .. }));
+        }
+        res => panic!("expected a checksum mismatch, got {res:?}"),
+    }
+}
+
+#[test]
+fn concatenated() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let encoded = item
+        .clone()
+        .encode_with_checksum(EncodingVersion::V1)
+        .unwrap();
+
+    let items = decode_bundle_generic(format!("{encoded}{encoded}{BREEZEHANDS}")).unwrap();
+    assert_eq!(items, vec![item; 3]);
+}
+
+#[test]
+fn bundle_header() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+
+    // the checksum of the last item covers the bundle header and the items before it
+    let mut blocks = vec![StartData(EncodingVersion::V1).into(), BundleData(2).into()];
+    blocks.append(&mut item.clone().into_blocks());
+    blocks.push(EndData.into());
+    blocks.append(&mut item.clone().into_blocks());
+    let bytes = encode_blocks_checksummed(EncodingVersion::V1, &blocks).unwrap();
+
+    let items = decode_bundle_generic(encode_string(&bytes)).unwrap();
+    assert_eq!(items, vec![item; 2]);
+
+    // change the last byte of the second item, before the checksum block and the end block
+    let mut corrupted = bytes.clone();
+    corrupted[bytes.len() - 8] ^= 0x01;
+    match decode_bundle_generic(encode_string(&corrupted)) {
+        Err(ItemDecodeError::DecoderError(e)) => {
+            assert!(matches!(e.error, DecodeError::ChecksumMismatch { .. }));
+        }
+        res => panic!("expected a checksum mismatch, got {res:?}"),
+    }
+}
+
+#[test]
+fn without_start() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let blocks = item.into_blocks();
+
+    // decoding without a start block verifies the bytes read by the decoder
+    let bytes = encode_blocks_checksummed(EncodingVersion::V1, &blocks).unwrap();
+    let decoded = AnyBlock::decode_all(EncodingVersion::V1, &mut bytes.into_iter()).unwrap();
+    assert_eq!(decoded[..blocks.len()], blocks);
+}
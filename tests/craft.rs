@@ -0,0 +1,259 @@
+use idmangler_lib::{
+    block::{CraftedIdentificationData, DamageData, DefenseData, DurabilityData},
+    craft::{Craft, CraftError, Ingredient, IngredientStat, PositionModifiers, Recipe},
+    item::{CraftedGear, GenericItem},
+    types::{AttackSpeed, ClassType, CraftedGearType, CraftedStat, EncodingVersion, SkillType},
+};
+
+fn helmet() -> Recipe {
+    Recipe {
+        gear_type: CraftedGearType::Helmet,
+        min_level: 103,
+        max_level: 105,
+        health_or_damage: 100..120,
+        durability: 150..182,
+        material_amounts: [1, 2],
+    }
+}
+
+fn spear() -> Recipe {
+    Recipe {
+        gear_type: CraftedGearType::Spear,
+        min_level: 103,
+        max_level: 105,
+        health_or_damage: 50..80,
+        durability: 150..182,
+        material_amounts: [1, 1],
+    }
+}
+
+fn booster() -> Ingredient {
+    Ingredient {
+        name: "Booster".to_string(),
+        level: 100,
+        identifications: vec![IngredientStat {
+            kind: 46,
+            min: 2,
+            max: 4,
+        }],
+        modifiers: PositionModifiers {
+            under: 50,
+            ..Default::default()
+        },
+        durability: -20,
+        skills: vec![(SkillType::Strength, 10)],
+    }
+}
+
+fn leech() -> Ingredient {
+    Ingredient {
+        name: "Leech".to_string(),
+        level: 90,
+        identifications: vec![IngredientStat {
+            kind: 4,
+            min: 10,
+            max: 20,
+        }],
+        modifiers: PositionModifiers::default(),
+        durability: 0,
+        skills: vec![],
+    }
+}
+
+#[test]
+fn effectiveness() {
+    let recipe = helmet();
+    let (booster, leech) = (booster(), leech());
+    let craft = Craft::new(&recipe).with_ingredients([
+        Some(&booster),
+        None,
+        Some(&leech),
+        Some(&leech),
+        Some(&leech),
+        None,
+    ]);
+
+    assert_eq!(craft.effectiveness(), [100, 100, 150, 100, 150, 100]);
+
+    let touching = Ingredient {
+        modifiers: PositionModifiers {
+            touching: 20,
+            not_touching: -10,
+            ..Default::default()
+        },
+        ..leech.clone()
+    };
+    let craft =
+        Craft::new(&recipe).with_ingredients([None, None, Some(&touching), None, None, None]);
+
+    assert_eq!(craft.effectiveness(), [120, 90, 100, 120, 120, 90]);
+}
+
+#[test]
+fn craft_armour() {
+    let recipe = helmet();
+    let (booster, leech) = (booster(), leech());
+    let item = Craft::new(&recipe)
+        .with_material_tiers([3, 1])
+        .with_ingredients([
+            Some(&booster),
+            None,
+            Some(&leech),
+            Some(&leech),
+            Some(&leech),
+            None,
+        ])
+        .with_name("Planned Helmet")
+        .craft()
+        .unwrap();
+
+    assert_eq!(item.name(), Some("Planned Helmet"));
+    assert_eq!(item.gear_type().0, CraftedGearType::Helmet);
+    assert_eq!(item.requirements().level, 105);
+    assert_eq!(item.requirements().class, None);
+    assert_eq!(item.requirements().skills, [(SkillType::Strength, 10)]);
+    assert_eq!(
+        item.durability(),
+        &DurabilityData {
+            effect_strenght: 100,
+            current: 162,
+            max: 162,
+        }
+    );
+    assert_eq!(
+        item.defense(),
+        Some(&DefenseData {
+            health: 136,
+            defences: vec![],
+        })
+    );
+    assert_eq!(item.damage(), None);
+    assert_eq!(
+        item.identifications(),
+        Some(&CraftedIdentificationData {
+            idents: vec![
+                CraftedStat { kind: 4, max: 80 },
+                CraftedStat { kind: 46, max: 4 },
+            ]
+        })
+    );
+
+    // the crafted item can be shared as an idstring
+    let encoded = item.clone().encode(EncodingVersion::V2).unwrap();
+    assert_eq!(CraftedGear::decode_string(encoded).unwrap(), item);
+}
+
+#[test]
+fn craft_weapon() {
+    let recipe = spear();
+    let item = Craft::new(&recipe)
+        .with_attack_speed(AttackSpeed::Fast)
+        .craft()
+        .unwrap();
+
+    assert_eq!(item.requirements().class, Some(ClassType::Warrior));
+    assert_eq!(
+        item.damage(),
+        Some(&DamageData {
+            attack_speed: AttackSpeed::Fast,
+            damages: vec![(None, 57..91)],
+        })
+    );
+    assert_eq!(item.defense(), None);
+    assert_eq!(item.identifications(), None);
+}
+
+#[test]
+fn craft_errors() {
+    let recipe = spear();
+
+    assert!(matches!(
+        Craft::new(&recipe)
+            .with_attack_speed(AttackSpeed::SuperFast)
+            .craft(),
+        Err(CraftError::InvalidAttackSpeed(AttackSpeed::SuperFast))
+    ));
+    assert!(matches!(
+        Craft::new(&recipe).with_material_tiers([0, 3]).craft(),
+        Err(CraftError::InvalidMaterialTier(0))
+    ));
+
+    let high = Ingredient {
+        level: 110,
+        ..leech()
+    };
+    assert!(matches!(
+        Craft::new(&recipe)
+            .with_ingredients([Some(&high), None, None, None, None, None])
+            .craft(),
+        Err(CraftError::IngredientLevel {
+            level: 110,
+            max: 105,
+            ..
+        })
+    ));
+}
+
+#[cfg(feature = "database")]
+mod database {
+    use idmangler_lib::{
+        craft::{IngredientDatabase, IngredientStat},
+        types::IdKeys,
+    };
+
+    #[test]
+    fn load_ingredients() {
+        let keys = IdKeys::from_json(include_str!("data/id_keys.json")).unwrap();
+        let db = IngredientDatabase::from_json(include_str!("data/items.json"), keys).unwrap();
+
+        assert_eq!(db.len(), 1);
+
+        let ingredient = db.entry("Corkian Insulator").unwrap();
+        assert_eq!(
+            ingredient.identifications,
+            [IngredientStat {
+                kind: 2,
+                min: 60,
+                max: 80,
+            }]
+        );
+        assert!(db.entry("Warp").is_err());
+    }
+}
+
+#[test]
+fn effective_values() {
+    let recipe = spear();
+    let leech = leech();
+    let item = Craft::new(&recipe)
+        .with_ingredients([Some(&leech), None, None, None, None, None])
+        .craft()
+        .unwrap();
+
+    let with_durability = |effect_strenght, current| {
+        let mut generic = GenericItem::from(item.clone());
+        generic.crafted_durability = Some(DurabilityData {
+            effect_strenght,
+            current,
+            max: 182,
+        });
+        CraftedGear::try_from(generic).unwrap()
+    };
+
+    let full = with_durability(100, 182);
+    assert_eq!(full.effectiveness(), 100);
+    assert_eq!(full.effective_identifications(), [(4, 20)]);
+    assert_eq!(full.effective_damage().as_ref(), full.damage());
+
+    let worn = with_durability(75, 10);
+    assert_eq!(worn.effectiveness(), 75);
+    assert_eq!(worn.effective_identifications(), [(4, 15)]);
+    assert_eq!(worn.effective_identification(4), Some(15));
+    assert_eq!(worn.effective_identification(2), None);
+    assert_eq!(worn.effective_damage().unwrap().damages, [(None, 53..84)]);
+
+    let broken = with_durability(100, 0);
+    assert_eq!(broken.effectiveness(), 0);
+    assert_eq!(broken.effective_identifications(), [(4, 0)]);
+    assert_eq!(broken.effective_damage().unwrap().damages, [(None, 0..0)]);
+}
@@ -0,0 +1,251 @@
+use idmangler_lib::{
+    block::{
+        encode_blocks, encode_blocks_str, is_framed, AnyBlock, BlockRegistry, CustomBlock,
+        CustomData, DataBlockId, EndData, NameData, RegistryError, StartData, TypeData,
+        UnknownBlock,
+    },
+    encoding::{string::encode_string, DecodeError, EncodeError},
+    item::GenericItem,
+    types::{EncodingVersion, ItemType},
+};
+
+const NOTE_ID: u8 = 150;
+const UNFRAMED_ID: u8 = 100;
+
+/// Example custom block holding a short note
+#[derive(PartialEq, Eq, Debug)]
+struct NoteData(String);
+
+impl CustomData for NoteData {
+    fn encode_custom(&self, _ver: EncodingVersion, out: &mut Vec<u8>) -> Result<(), EncodeError> {
+        out.push(self.0.len() as u8);
+        out.extend(self.0.bytes());
+
+        Ok(())
+    }
+
+    fn decode_custom(
+        bytes: &mut impl Iterator<Item = u8>,
+        _ver: EncodingVersion,
+    ) -> Result<Self, DecodeError> {
+        let len = bytes.next().ok_or(DecodeError::UnexpectedEndOfBytes)?;
+        let data = bytes.take(len as usize).collect::<Vec<_>>();
+        if data.len() != len as usize {
+            return Err(DecodeError::UnexpectedEndOfBytes);
+        }
+
+        String::from_utf8(data)
+            .map(Self)
+            .map_err(|_| DecodeError::BadString)
+    }
+}
+
+impl From<NoteData> for AnyBlock {
+    fn from(data: NoteData) -> Self {
+        CustomBlock::new(NOTE_ID, data).unwrap().into()
+    }
+}
+
+fn blocks() -> Vec<AnyBlock> {
+    vec![
+        StartData(EncodingVersion::V1).into(),
+        TypeData(ItemType::Gear).into(),
+        NameData("Warp".to_string()).into(),
+        NoteData("for sale".to_string()).into(),
+        EndData.into(),
+    ]
+}
+
+#[test]
+fn roundtrip() {
+    let mut registry = BlockRegistry::new();
+    registry.register::<NoteData>(NOTE_ID).unwrap();
+
+    let encoded = encode_blocks_str(EncodingVersion::V1, &blocks()).unwrap();
+    let decoded = registry.decode_str(&encoded).unwrap();
+
+    assert_eq!(decoded, blocks());
+
+    let AnyBlock::Custom(note) = &decoded[3] else {
+        panic!("expected a custom block");
+    };
+    assert_eq!(note.id(), NOTE_ID);
+    assert_eq!(note.downcast_ref::<NoteData>().unwrap().0, "for sale");
+    assert_eq!(decoded[3].as_id(), DataBlockId::Custom);
+
+    // the item keeps the custom block and encodes it back
+    let item = GenericItem::from_blocks(decoded.clone()).unwrap();
+    assert_eq!(item.name.as_deref(), Some("Warp"));
+    assert_eq!(item.extra_blocks, [decoded[3].clone()]);
+    assert_eq!(item.encode(EncodingVersion::V1).unwrap(), encoded);
+}
+
+#[test]
+fn unframed_ids() {
+    let mut registry = BlockRegistry::new();
+
+    // decoders without the block registered could not skip blocks with these ids
+    for id in [16, UNFRAMED_ID, 127] {
+        assert!(matches!(
+            registry.register::<NoteData>(id),
+            Err(RegistryError::UnframedId(e)) if e == id
+        ));
+        assert!(CustomBlock::new(id, NoteData(String::new())).is_err());
+    }
+}
+
+#[test]
+fn registry_errors() {
+    let mut registry = BlockRegistry::new();
+
+    assert!(matches!(
+        registry.register::<NoteData>(DataBlockId::NameData as u8),
+        Err(RegistryError::BuiltinId(2))
+    ));
+    assert!(matches!(
+        registry.register::<NoteData>(DataBlockId::Custom as u8),
+        Err(RegistryError::ReservedId(_))
+    ));
+    assert!(matches!(
+        registry.register::<NoteData>(DataBlockId::BundleData as u8),
+        Err(RegistryError::ReservedId(_))
+    ));
+
+    registry.register::<NoteData>(NOTE_ID).unwrap();
+    assert!(registry.is_registered(NOTE_ID));
+    assert!(matches!(
+        registry.register::<NoteData>(NOTE_ID),
+        Err(RegistryError::AlreadyRegistered(NOTE_ID))
+    ));
+
+    assert!(CustomBlock::new(DataBlockId::EndData as u8, NoteData(String::new())).is_err());
+}
+
+#[test]
+fn custom_decode_error() {
+    let mut registry = BlockRegistry::new();
+    registry.register::<NoteData>(NOTE_ID).unwrap();
+
+    let mut blocks = blocks();
+    blocks[3] = NoteData("x".repeat(10)).into();
+    let mut bytes = encode_blocks(EncodingVersion::V1, &blocks).unwrap();
+    // cut the note short
+    bytes.truncate(bytes.len() - 4);
+
+    let err = registry.decode(&mut bytes.into_iter()).unwrap_err();
+    assert_eq!(err.during, Some(DataBlockId::Custom));
+    assert!(matches!(err.error, DecodeError::UnexpectedEndOfBytes));
+}
+
+#[test]
+fn skip_unknown_framed() {
+    assert!(is_framed(NOTE_ID));
+    let encoded = encode_blocks(EncodingVersion::V1, &blocks()).unwrap();
+
+    // without the block registered the payload is kept as is
+    let decoded = AnyBlock::decode(&mut encoded.clone().into_iter()).unwrap();
+    assert_eq!(
+        decoded[3],
+        AnyBlock::Unknown(UnknownBlock {
+            id: NOTE_ID,
+            data: [&[8][..], b"for sale"].concat(),
+        })
+    );
+    assert_eq!(decoded[3].as_id(), DataBlockId::Unknown);
+    assert_eq!(
+        encode_blocks(EncodingVersion::V1, &decoded).unwrap(),
+        encoded
+    );
+
+    // and items can still be decoded, keeping the unknown block
+    let item = GenericItem::decode_string(encode_string(&encoded)).unwrap();
+    assert_eq!(item.name.as_deref(), Some("Warp"));
+    assert_eq!(item.extra_blocks, [decoded[3].clone()]);
+    assert_eq!(
+        item.encoded_len(EncodingVersion::V1).unwrap().bytes,
+        encoded.len()
+    );
+    assert_eq!(
+        item.encode(EncodingVersion::V1).unwrap(),
+        encode_string(&encoded)
+    );
+
+    let mut registry = BlockRegistry::new();
+    registry.register::<NoteData>(NOTE_ID).unwrap();
+    assert_eq!(registry.decode(&mut encoded.into_iter()).unwrap(), blocks());
+}
+
+#[test]
+fn framed_trailing_data() {
+    let mut registry = BlockRegistry::new();
+    registry.register::<NoteData>(NOTE_ID).unwrap();
+
+    // a newer version of the block with extra data after the note
+    let blocks = [
+        StartData(EncodingVersion::V1).into(),
+        TypeData(ItemType::Gear).into(),
+        UnknownBlock {
+            id: NOTE_ID,
+            data: vec![2, b'h', b'i', 1, 2, 3],
+        }
+        .into(),
+        EndData.into(),
+    ];
+    let encoded = encode_blocks(EncodingVersion::V1, &blocks).unwrap();
+
+    let decoded = registry.decode(&mut encoded.into_iter()).unwrap();
+    let AnyBlock::Custom(note) = &decoded[2] else {
+        panic!("expected a custom block");
+    };
+    assert_eq!(note.downcast_ref::<NoteData>().unwrap().0, "hi");
+    assert_eq!(decoded[3], AnyBlock::EndData(EndData));
+}
+
+#[test]
+fn unknown_block_ids() {
+    let unknown = |id| {
+        AnyBlock::from(UnknownBlock {
+            id,
+            data: vec![1, 2],
+        })
+    };
+    let encode = |ver, id| encode_blocks(ver, &[StartData(ver).into(), unknown(id)]);
+
+    assert!(encode(EncodingVersion::V1, NOTE_ID).is_ok());
+
+    // decoders would read the payload as a name block
+    let err = encode(EncodingVersion::V1, DataBlockId::NameData as u8).unwrap_err();
+    assert_eq!(err.during, DataBlockId::Unknown);
+    assert!(matches!(err.error, EncodeError::InvalidUnknownBlockId(2)));
+
+    // decoders of this version can not skip unframed blocks
+    let err = encode(EncodingVersion::V2, UNFRAMED_ID).unwrap_err();
+    assert!(matches!(
+        err.error,
+        EncodeError::InvalidUnknownBlockId(UNFRAMED_ID)
+    ));
+    assert!(unknown(UNFRAMED_ID)
+        .encoded_len(EncodingVersion::V2)
+        .is_err());
+}
+
+#[test]
+fn bad_frame() {
+    let start = [
+        DataBlockId::StartData as u8,
+        0,
+        DataBlockId::TypeData as u8,
+        0,
+    ];
+
+    // negative length
+    let bytes = [&start[..], &[NOTE_ID, 0x01]].concat();
+    let err = AnyBlock::decode(&mut bytes.into_iter()).unwrap_err();
+    assert_eq!(err.during, Some(DataBlockId::Unknown));
+    assert!(matches!(err.error, DecodeError::BadFrameLength(-1)));
+
+    // payload shorter than the length
+    let bytes = [&start[..], &[NOTE_ID, 0x08, 1, 2]].concat();
+    let err = AnyBlock::decode(&mut bytes.into_iter()).unwrap_err();
+    assert!(matches!(err.error, DecodeError::UnexpectedEndOfBytes));
+}
@@ -0,0 +1,161 @@
+use idmangler_lib::{
+    block::DamageData,
+    build::SkillPoints,
+    damage::{skill_point_percentage, DamageBonuses, DamageCalculator},
+    types::{AttackSpeed, Element, IdKeys, Powder},
+};
+
+fn weapon() -> DamageData {
+    DamageData {
+        attack_speed: AttackSpeed::Normal,
+        damages: vec![(None, 100..200), (Some(Element::Earth), 50..70)],
+    }
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-6, "{a} != {b}");
+}
+
+#[test]
+fn skill_points() {
+    assert_eq!(skill_point_percentage(0), 0.0);
+    assert_eq!(skill_point_percentage(-10), 0.0);
+    assert_close(skill_point_percentage(100), 0.649487);
+    assert_close(skill_point_percentage(150), 0.80765775);
+    // skill points past the cap do not do anything
+    assert_eq!(skill_point_percentage(200), skill_point_percentage(150));
+}
+
+#[test]
+fn attack_speeds() {
+    assert_eq!(AttackSpeed::Normal.multiplier(), 2.05);
+    assert_eq!(AttackSpeed::SuperSlow.multiplier(), 0.51);
+
+    assert_eq!(AttackSpeed::Normal.shifted(1), AttackSpeed::Fast);
+    assert_eq!(AttackSpeed::VeryFast.shifted(3), AttackSpeed::SuperFast);
+    assert_eq!(AttackSpeed::Normal.shifted(-5), AttackSpeed::SuperSlow);
+}
+
+#[test]
+fn melee_damage() {
+    let base = DamageCalculator::new(&weapon()).melee();
+    assert_eq!(base.per_type, [150.0, 60.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_close(base.per_hit, 210.0);
+    assert_close(base.per_second, 430.5);
+
+    let boosted = DamageCalculator::new(&weapon())
+        .with_skill_points(SkillPoints([100, 0, 0, 0, 0]))
+        .with_bonuses(DamageBonuses {
+            damage_percent: 10,
+            melee_percent: 20,
+            melee_raw: 50,
+            ..Default::default()
+        })
+        .melee();
+    assert_close(boosted.per_type[0], 195.0);
+    assert_close(boosted.per_type[1], 116.96922);
+    assert_close(boosted.per_hit, 361.96922);
+}
+
+#[test]
+fn crit_damage() {
+    let calc = DamageCalculator::new(&weapon())
+        .with_skill_points(SkillPoints([0, 100, 0, 0, 0]))
+        .with_bonuses(DamageBonuses {
+            crit_damage_percent: 20,
+            ..Default::default()
+        });
+    assert_close(calc.crit_chance(), 0.649487);
+
+    // dexterity does not boost neutral or earth damage, only the chance of critical hits
+    let melee = calc.melee();
+    assert_eq!(melee.per_type, [150.0, 60.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_close(melee.crit_per_hit, 462.0);
+    assert_close(melee.per_hit, 373.670724);
+    assert_close(melee.per_second, 766.0249842);
+
+    // without dexterity critical hits never happen
+    let melee = DamageCalculator::new(&weapon()).melee();
+    assert_close(melee.crit_per_hit, 420.0);
+    assert_close(melee.per_hit, 210.0);
+}
+
+#[test]
+fn reduced_skill_bonuses() {
+    let weapon = DamageData {
+        attack_speed: AttackSpeed::Normal,
+        damages: vec![
+            (Some(Element::Fire), 100..100),
+            (Some(Element::Air), 100..100),
+        ],
+    };
+
+    let melee = DamageCalculator::new(&weapon)
+        .with_skill_points(SkillPoints([0, 0, 0, 100, 100]))
+        .melee();
+    assert_close(melee.per_type[Element::Fire as usize + 1], 156.3105229);
+    assert_close(melee.per_type[Element::Air as usize + 1], 161.7662137);
+}
+
+#[test]
+fn spell_damage() {
+    let calc = DamageCalculator::new(&weapon()).with_bonuses(DamageBonuses {
+        spell_raw: 100,
+        attack_speed: 1,
+        ..Default::default()
+    });
+    assert_eq!(calc.attack_speed(), AttackSpeed::Fast);
+
+    let spell = calc.spell(150, 0.5);
+    assert_close(spell.per_type[0], 562.5);
+    assert_close(spell.per_type[1], 225.0);
+    assert_close(spell.per_hit, 937.5);
+    assert_close(spell.per_second, 468.75);
+}
+
+#[test]
+fn powdered_damage() {
+    let fire = [Powder::new(Element::Fire, 6).unwrap(); 2];
+    let calc = DamageCalculator::new(&weapon()).with_powders(&fire);
+
+    assert_eq!(calc.damage(), &weapon().with_powders(&fire));
+    assert!(calc.melee().per_type[Element::Fire as usize + 1] > 0.0);
+}
+
+#[test]
+fn bonuses_from_identifications() {
+    let keys = IdKeys::from_iter([
+        ("damage", 1),
+        ("rawMainAttackDamage", 2),
+        ("earthDamage", 3),
+        ("rawAttackSpeed", 4),
+        ("rawHealth", 5),
+        ("criticalDamageBonus", 6),
+    ]);
+
+    let bonuses = DamageBonuses::from_identifications(
+        [
+            (1, 10),
+            (2, 300),
+            (3, 15),
+            (3, 5),
+            (4, -1),
+            (5, 1000),
+            (6, 25),
+            (99, 1),
+        ],
+        &keys,
+    );
+
+    assert_eq!(
+        bonuses,
+        DamageBonuses {
+            damage_percent: 10,
+            melee_raw: 300,
+            element_percent: [20, 0, 0, 0, 0],
+            attack_speed: -1,
+            crit_damage_percent: 25,
+            ..Default::default()
+        }
+    );
+}
@@ -0,0 +1,606 @@
+#![cfg(feature = "database")]
+
+use idmangler_lib::{
+    block::{
+        CraftedGearTypeData, CraftedIdentificationData, DamageData, DefenseData, DurabilityData,
+        IdentificationData, PowderData, RequirementsData,
+    },
+    database::{
+        DatabaseError, ItemDatabase, JsonPowder, JsonShiny, JsonStat, RollMode, ShinyStats,
+        StatMismatch,
+    },
+    item::{GearItem, GenericItem, TomeItem},
+    types::{
+        AttackSpeed, ClassType, CraftedGearType, CraftedStat, Element, EncodingVersion, IdKeys,
+        ItemTier, ItemType, Powder, RollType, SkillType, Stat,
+    },
+};
+
+fn database() -> ItemDatabase {
+    let keys = IdKeys::from_json(include_str!("data/id_keys.json")).unwrap();
+
+    ItemDatabase::from_json(include_str!("data/items.json"), keys).unwrap()
+}
+
+#[test]
+fn load_database() {
+    let db = database();
+
+    // the ingredient is skipped
+    assert_eq!(db.len(), 6);
+    assert!(db.get("Corkian Insulator").is_none());
+
+    let warp = db.get("Warp").unwrap();
+    assert_eq!(warp.kind, ItemType::Gear);
+    assert_eq!(warp.tier, ItemTier::Legendary);
+    assert_eq!(warp.gear_type, Some(CraftedGearType::Wand));
+    assert_eq!(warp.powder_slots, 3);
+    assert_eq!(
+        warp.requirements,
+        RequirementsData {
+            level: 75,
+            class: Some(ClassType::Mage),
+            skills: vec![(SkillType::Agility, 100)],
+        }
+    );
+
+    let damage = warp.damage.as_ref().unwrap();
+    assert_eq!(damage.attack_speed, AttackSpeed::SuperFast);
+    assert_eq!(
+        damage.damages,
+        vec![(None, 0..0), (Some(Element::Air), 38..46)]
+    );
+
+    let agi = warp.stat(41).unwrap();
+    assert!(agi.pre_identified);
+    assert_eq!((agi.base, agi.min, agi.max), (25, 25, 25));
+
+    let cap = db.get("Ghostly Cap").unwrap();
+    assert_eq!(cap.health, 420);
+    assert_eq!(
+        cap.defences,
+        vec![(Element::Water, 30), (Element::Fire, -20)]
+    );
+
+    assert_eq!(
+        db.get("Tome of Lesser Allegiance").unwrap().kind,
+        ItemType::Tome
+    );
+}
+
+#[test]
+fn hydrate_gear() {
+    let db = database();
+
+    let mut item = GearItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+    let entry = item.hydrate(&db).unwrap();
+
+    assert_eq!(entry.tier, ItemTier::Legendary);
+    assert_eq!(entry.gear_type, Some(CraftedGearType::Relik));
+
+    let idents = item.identifications.unwrap();
+    assert!(!idents.extended_encoding);
+    assert_eq!(
+        idents.identifications,
+        vec![
+            Stat {
+                kind: 48,
+                base: Some(15),
+                roll: RollType::PreIdentified,
+            },
+            Stat {
+                kind: 24,
+                base: Some(-30),
+                roll: RollType::Value(81),
+            },
+            Stat {
+                kind: 23,
+                base: Some(-20),
+                roll: RollType::Value(73),
+            },
+            Stat {
+                kind: 18,
+                base: Some(10),
+                roll: RollType::Value(75),
+            },
+            Stat {
+                kind: 4,
+                base: Some(300),
+                roll: RollType::Value(102),
+            },
+            Stat {
+                kind: 2,
+                base: Some(2500),
+                roll: RollType::Value(48),
+            },
+        ]
+    );
+    assert_eq!(idents.identifications[1].value(), Some(-24));
+}
+
+#[test]
+fn hydrate_errors() {
+    let db = database();
+
+    let mut unknown = TomeItem {
+        name: String::from("Not an item"),
+        identifications: None,
+        rerolls: None,
+        tier: None,
+        extra_blocks: Vec::new(),
+    };
+    assert!(matches!(
+        unknown.hydrate(&db),
+        Err(DatabaseError::UnknownItem(_))
+    ));
+
+    let mut wrong_type = TomeItem {
+        name: String::from("Warp"),
+        identifications: None,
+        rerolls: None,
+        tier: None,
+        extra_blocks: Vec::new(),
+    };
+    assert!(matches!(
+        wrong_type.hydrate(&db),
+        Err(DatabaseError::WrongItemType {
+            expected: ItemType::Tome,
+            found: ItemType::Gear,
+            ..
+        })
+    ));
+
+    let mut idents = IdentificationData {
+        identifications: vec![
+            Stat {
+                kind: 69,
+                base: None,
+                roll: RollType::Value(100),
+            },
+            Stat {
+                kind: 200,
+                base: None,
+                roll: RollType::Value(100),
+            },
+        ],
+        extended_encoding: false,
+    };
+    let original = idents.clone();
+    assert!(matches!(
+        db.hydrate("Warp", &mut idents),
+        Err(DatabaseError::UnknownStat { kind: 200, .. })
+    ));
+    // nothing is modified on error
+    assert_eq!(idents, original);
+}
+
+#[test]
+fn unknown_stat_name() {
+    let keys: IdKeys = [("rawHealth", 2)].into_iter().collect();
+
+    let res = ItemDatabase::from_json(include_str!("data/items.json"), keys);
+
+    assert!(matches!(res, Err(DatabaseError::UnknownStatName { .. })));
+}
+
+const WARP: &str = "󰀁󰄀󰉗󶅲󷀀󰌉󰄁󲤲󴖴󰅱󱅤󶔢󵥣󱢏󰍍󱦯󰥋󱜻󷀄󱹵󵇨󰉐󲛖󰑙󰐃󰀅󰔆󰘂󰃿";
+
+#[test]
+fn compact_roundtrip() {
+    let db = database();
+
+    let original = GearItem::decode_string(WARP).unwrap();
+
+    let mut item = original.clone();
+    item.compact(&db).unwrap();
+
+    let idents = item.identifications.as_ref().unwrap();
+    assert!(!idents.extended_encoding);
+    assert!(idents
+        .identifications
+        .iter()
+        .all(|s| s.base.is_none() && !s.pre_identified()));
+
+    let compact = item.clone().encode(EncodingVersion::V2).unwrap();
+    assert!(compact.chars().count() < WARP.chars().count());
+
+    // hydrating the compact item restores the original identifications
+    let mut decoded = GearItem::decode_string(&compact).unwrap();
+    decoded.hydrate(&db).unwrap();
+
+    assert_eq!(
+        decoded.identifications.unwrap().identifications,
+        original.identifications.unwrap().identifications
+    );
+}
+
+#[test]
+fn compact_mismatch() {
+    let db = database();
+
+    let mut item = GearItem::decode_string(WARP).unwrap();
+    let idents = item.identifications.as_mut().unwrap();
+    idents.identifications[1].base = Some(80);
+    // remove the pre-identified agility
+    idents.identifications.remove(0);
+
+    let original = item.clone();
+    match item.compact(&db) {
+        Err(DatabaseError::BaseMismatch { item, stats }) => {
+            assert_eq!(item, "Warp");
+            assert_eq!(
+                stats,
+                vec![
+                    StatMismatch {
+                        kind: 69,
+                        encoded: Some(80),
+                        current: Some(90),
+                    },
+                    StatMismatch {
+                        kind: 41,
+                        encoded: None,
+                        current: Some(25),
+                    },
+                ]
+            );
+        }
+        other => panic!("Expected a mismatch, got {other:?}"),
+    }
+    assert_eq!(item, original);
+}
+
+#[test]
+fn non_extended_mismatches() {
+    let db = database();
+
+    // pre-identified stats are not encoded without extended encoding and are not reported as missing
+    let mut item = GearItem::decode_string(WARP).unwrap();
+    item.compact(&db).unwrap();
+
+    let entry = db.get("Warp").unwrap();
+    assert!(entry
+        .mismatches(item.identifications.as_ref().unwrap())
+        .is_empty());
+}
+
+#[test]
+fn outdated_items() {
+    let mut db = database();
+
+    // simulate a balance patch changing the base value of one stat and removing another
+    let mut warp = db.get("Warp").unwrap().clone();
+    warp.identifications
+        .iter_mut()
+        .find(|s| s.kind == 69)
+        .unwrap()
+        .base = 100;
+    warp.identifications.retain(|s| s.kind != 4);
+    db.insert(warp);
+
+    let mut item = GearItem::decode_string(WARP).unwrap();
+    let report = item.check_outdated(&db).unwrap();
+
+    assert!(report.is_outdated());
+    assert_eq!(
+        report.stats,
+        vec![
+            StatMismatch {
+                kind: 69,
+                encoded: Some(90),
+                current: Some(100),
+            },
+            StatMismatch {
+                kind: 4,
+                encoded: Some(15),
+                current: None,
+            },
+        ]
+    );
+
+    let air = report
+        .updated
+        .identifications
+        .iter()
+        .find(|s| s.kind == 69)
+        .unwrap();
+    assert_eq!(air.base, Some(100));
+    assert_eq!(air.roll, RollType::Value(113));
+    assert!(!report.updated.identifications.iter().any(|s| s.kind == 4));
+
+    // updating the item makes it match the database
+    item.update(&db).unwrap();
+    assert!(!item.check_outdated(&db).unwrap().is_outdated());
+    assert!(item.compact(&db).is_ok());
+
+    // non extended items can not have outdated base values
+    let immolation = GearItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+    assert!(!immolation.check_outdated(&db).unwrap().is_outdated());
+}
+
+#[test]
+fn import_api_item() {
+    let keys = IdKeys::from_json(include_str!("data/id_keys.json")).unwrap();
+    let json = r#"{
+        "type": "armour",
+        "armourType": "helmet",
+        "rarity": "unique",
+        "powderSlots": 2,
+        "requirements": { "level": 40 },
+        "identifications": {
+            "rawIntelligence": 4,
+            "walkSpeed": { "min": -8, "max": -4, "raw": -6 },
+            "rawHealth": { "min": 20, "max": 85, "raw": 65 }
+        }
+    }"#;
+
+    let max = GearItem::from_api_json("Ghostly Cap", json, &keys, RollMode::Max).unwrap();
+    assert_eq!(max.name, "Ghostly Cap");
+    assert_eq!(max.powders.as_ref().unwrap().powder_slots, 2);
+
+    let idents = max.identifications.unwrap();
+    assert!(idents.extended_encoding);
+    assert_eq!(
+        idents.identifications,
+        vec![
+            Stat {
+                kind: 47,
+                base: Some(4),
+                roll: RollType::PreIdentified,
+            },
+            Stat {
+                kind: 2,
+                base: Some(65),
+                roll: RollType::Value(130),
+            },
+            Stat {
+                kind: 34,
+                base: Some(-6),
+                roll: RollType::Value(70),
+            },
+        ]
+    );
+    let values: Vec<_> = idents.identifications.iter().map(|s| s.value()).collect();
+    assert_eq!(values, vec![Some(4), Some(85), Some(-4)]);
+
+    let min = GearItem::from_api_json("Ghostly Cap", json, &keys, RollMode::Min).unwrap();
+    let values: Vec<_> = min
+        .identifications
+        .unwrap()
+        .identifications
+        .iter()
+        .map(|s| s.value())
+        .collect();
+    assert_eq!(values, vec![Some(4), Some(20), Some(-8)]);
+
+    // rolls outside of the possible rolls are clamped
+    let rolls = |mode| {
+        GearItem::from_api_json("Ghostly Cap", json, &keys, mode)
+            .unwrap()
+            .identifications
+            .unwrap()
+            .identifications
+            .into_iter()
+            .map(|s| s.roll)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        rolls(RollMode::Roll(255)),
+        vec![
+            RollType::PreIdentified,
+            RollType::Value(130),
+            RollType::Value(130),
+        ]
+    );
+    assert_eq!(
+        rolls(RollMode::Roll(0)),
+        vec![
+            RollType::PreIdentified,
+            RollType::Value(30),
+            RollType::Value(70),
+        ]
+    );
+
+    // the created items can be encoded
+    let roll = GearItem::from_api_json("Ghostly Cap", json, &keys, RollMode::Roll(100)).unwrap();
+    let encoded = roll.clone().encode(EncodingVersion::V1).unwrap();
+    assert_eq!(GearItem::decode_string(encoded).unwrap(), roll);
+
+    let ingredient = r#"{ "type": "ingredient", "identifications": {} }"#;
+    assert!(matches!(
+        GearItem::from_api_json("Corkian Insulator", ingredient, &keys, RollMode::Max),
+        Err(DatabaseError::InvalidField { field: "type", .. })
+    ));
+}
+
+#[test]
+fn database_entry_to_item() {
+    let db = database();
+
+    let tome = db
+        .get("Tome of Lesser Allegiance")
+        .unwrap()
+        .to_tome(RollMode::Max)
+        .unwrap();
+    assert_eq!(
+        tome.identifications.unwrap().identifications[0].value(),
+        Some(130)
+    );
+
+    assert!(matches!(
+        db.get("Warp").unwrap().to_charm(RollMode::Max),
+        Err(DatabaseError::WrongItemType { .. })
+    ));
+}
+
+fn database_with_shiny() -> ItemDatabase {
+    let mut db = database();
+    db.set_shiny_stats(
+        ShinyStats::from_json(
+            r#"[
+                { "id": 6, "key": "warsWon", "displayName": "Wars Won" },
+                { "id": 7, "key": "raidsWon", "displayName": "Raids Won" }
+            ]"#,
+        )
+        .unwrap(),
+    );
+    db
+}
+
+#[test]
+fn export_gear() {
+    let db = database_with_shiny();
+    let item = GenericItem::decode_string(WARP).unwrap();
+
+    let json = db.export(&item).unwrap();
+    assert_eq!(json.kind, "gear");
+    assert_eq!(json.rarity.as_deref(), Some("legendary"));
+    assert_eq!(json.rerolls, Some(5));
+    assert_eq!(
+        json.shiny_stat,
+        Some(JsonShiny {
+            name: String::from("warsWon"),
+            value: 0,
+            rerolls: 2,
+        })
+    );
+
+    let idents = &json.identifications.as_ref().unwrap().0;
+    assert_eq!(idents[0], (String::from("rawAgility"), JsonStat::Fixed(25)));
+    assert_eq!(
+        idents[1],
+        (
+            String::from("airDamage"),
+            JsonStat::Rolled {
+                value: Some(102),
+                base: Some(90),
+                roll: 113,
+            }
+        )
+    );
+
+    let string = db.export_json(&item).unwrap();
+    assert!(string.contains(r#""airDamage":{"value":102,"base":90,"roll":113}"#));
+    assert!(string.contains(r#""baseAirDamage":{"min":38,"max":46}"#));
+    assert_eq!(db.import_json(&string).unwrap(), item);
+}
+
+#[test]
+fn export_non_extended() {
+    let db = database_with_shiny();
+    let item = GenericItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+
+    let json = db.export(&item).unwrap();
+
+    // values are computed using the database while the base values are not exported
+    assert_eq!(
+        json.identifications.as_ref().unwrap().0[0],
+        (
+            String::from("raw2ndSpellCost"),
+            JsonStat::Rolled {
+                value: Some(-24),
+                base: None,
+                roll: 81,
+            }
+        )
+    );
+    assert_eq!(
+        json.powders,
+        Some(vec![
+            JsonPowder {
+                element: String::from("air"),
+                tier: 6,
+            },
+            JsonPowder {
+                element: String::from("fire"),
+                tier: 6,
+            },
+            JsonPowder {
+                element: String::from("fire"),
+                tier: 6,
+            },
+        ])
+    );
+
+    let string = db.export_json(&item).unwrap();
+    assert_eq!(db.import_json(&string).unwrap(), item);
+
+    // unknown shiny stats can not be exported
+    assert!(matches!(
+        database().export(&item),
+        Err(DatabaseError::UnknownShinyStat(6))
+    ));
+}
+
+#[test]
+fn export_crafted() {
+    let db = database();
+    let item = GenericItem {
+        kind: ItemType::CraftedGear,
+        name: Some(String::from("Crafted Spear")),
+        crafted_type: Some(CraftedGearTypeData(CraftedGearType::Spear)),
+        crafted_durability: Some(DurabilityData {
+            effect_strenght: 92,
+            current: 100,
+            max: 150,
+        }),
+        crafted_reqs: Some(RequirementsData {
+            level: 100,
+            class: Some(ClassType::Warrior),
+            skills: vec![(SkillType::Strength, 40), (SkillType::Agility, 20)],
+        }),
+        crafted_damage: Some(DamageData {
+            attack_speed: AttackSpeed::Slow,
+            damages: vec![(None, 100..140), (Some(Element::Earth), 50..70)],
+        }),
+        crafted_defense: Some(DefenseData {
+            health: 0,
+            defences: vec![(Element::Air, -10)],
+        }),
+        crafted_identifications: Some(CraftedIdentificationData {
+            idents: vec![CraftedStat { kind: 4, max: 12 }],
+        }),
+        powders: Some(PowderData {
+            powder_slots: 2,
+            powders: vec![Powder::new(Element::Earth, 5).unwrap()],
+        }),
+        ..Default::default()
+    };
+
+    let string = db.export_json(&item).unwrap();
+    assert!(string.contains(r#""identifications":{"lifeSteal":12}"#));
+    assert!(string.contains(
+        r#""requirements":{"level":100,"classRequirement":"warrior","strength":40,"agility":20}"#
+    ));
+    assert!(string.contains(r#""base":{"baseDamage":{"min":100,"max":140},"baseEarthDamage":{"min":50,"max":70},"baseHealth":0,"baseAirDefence":-10}"#));
+
+    assert_eq!(db.import_json(&string).unwrap(), item);
+}
+
+#[test]
+fn powdered_stats() {
+    let db = database();
+    let water = Powder::new(Element::Water, 6).unwrap();
+
+    let cap = db.entry("Ghostly Cap").unwrap();
+    assert_eq!(cap.powdered_damage(&[water]), None);
+    assert_eq!(
+        cap.powdered_defense(&[water, water]),
+        DefenseData {
+            health: 420,
+            defences: vec![
+                (Element::Thunder, -20),
+                (Element::Water, 110),
+                (Element::Fire, -20),
+            ],
+        }
+    );
+
+    let warp = db.entry("Warp").unwrap();
+    assert_eq!(
+        warp.powdered_damage(&[water]).unwrap(),
+        DamageData {
+            attack_speed: AttackSpeed::SuperFast,
+            damages: vec![(Some(Element::Water), 9..11), (Some(Element::Air), 38..46)],
+        }
+    );
+}
@@ -1,7 +1,7 @@
 use idmangler_lib::{
     block::{
-        decode_str, AnyBlock, EndData, IdentificationData, NameData, PowderData, RerollData,
-        ShinyData, StartData, TypeData,
+        decode_str, AnyBlock, DataBlockId, EndData, IdentificationData, NameData, PowderData,
+        RerollData, ShinyData, StartData, TypeData,
     },
     types::{Element, EncodingVersion, ItemType, Powder, RollType, Stat},
 };
@@ -187,3 +187,72 @@ fn negative_ids() {
 
     assert!(decode.next().is_none());
 }
+
+#[test]
+#[cfg(not(feature = "checksum"))]
+fn unverified_checksum() {
+    use idmangler_lib::{
+        block::{encode_blocks_str, ChecksumData},
+        item::GenericItem,
+    };
+
+    let input = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+    let item = GenericItem::decode_string(input).unwrap();
+
+    // without the checksum feature checksum blocks are decoded but not verified
+    let mut blocks = vec![StartData(EncodingVersion::V1).into()];
+    blocks.append(&mut item.clone().into_blocks());
+    blocks.push(ChecksumData(0xDEADBEEF).into());
+    blocks.push(EndData.into());
+    let encoded = encode_blocks_str(EncodingVersion::V1, &blocks).unwrap();
+
+    assert_eq!(decode_str(&encoded).unwrap(), blocks);
+    assert_eq!(GenericItem::decode_string(&encoded).unwrap(), item);
+}
+
+#[test]
+#[cfg(not(any(feature = "checksum", feature = "signature")))]
+fn extension_blocks_without_features() {
+    use idmangler_lib::{block::ChecksumData, item::GenericItem};
+
+    let item = GenericItem::decode_string("󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿").unwrap();
+
+    // Breezehands with a checksum block and signed with the key [7; 32]
+    let checksummed = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋰󰡤󼋓󱓿";
+    let signed = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋱󸀁󶔸󻸌󺍌󰜵󰬕󹷓󼮤󺙳󹤝󱅝󱃷󲮗󿒟󷉓󶉌󱔳󾆇󱿋󺂎󵔤󴟍󹋡󿬀󼥧󺿤󼅙󺮳󼖩󷑰󷘜󸫺󰘎􏿮";
+
+    let blocks = decode_str(checksummed).unwrap();
+    assert_eq!(
+        blocks[blocks.len() - 2],
+        AnyBlock::ChecksumData(ChecksumData(0x64C2D314))
+    );
+    assert_eq!(GenericItem::decode_string(checksummed).unwrap(), item);
+
+    let blocks = decode_str(signed).unwrap();
+    assert!(matches!(
+        blocks[blocks.len() - 2],
+        AnyBlock::SignatureData(_)
+    ));
+    assert_eq!(GenericItem::decode_string(signed).unwrap(), item);
+}
+
+#[test]
+fn unknown_extension_block() {
+    use idmangler_lib::block::{encode_blocks, UnknownBlock};
+
+    // extension blocks added by later versions of this crate are skipped by older decoders
+    let blocks = [
+        StartData(EncodingVersion::V1).into(),
+        TypeData(ItemType::Gear).into(),
+        UnknownBlock {
+            id: 250,
+            data: vec![1, 2, 3],
+        }
+        .into(),
+        EndData.into(),
+    ];
+    let bytes = encode_blocks(EncodingVersion::V1, &blocks).unwrap();
+
+    assert_eq!(bytes[4..10], [250, 6, 1, 2, 3, DataBlockId::EndData as u8]);
+    assert_eq!(AnyBlock::decode(&mut bytes.into_iter()).unwrap(), blocks);
+}
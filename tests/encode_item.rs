@@ -1,7 +1,13 @@
 use idmangler_lib::{
-    block::{IdentificationData, PowderData, RerollData, ShinyData},
-    item::GenericItem,
-    types::{Element, EncodingVersion, ItemType, Powder, RollType, Stat},
+    block::{
+        CraftedConsumableTypeData, EffectsData, IdentificationData, PowderData, RequirementsData,
+        RerollData, ShinyData, UsesData,
+    },
+    item::{error::ConsumeError, ConsumableEffects, CraftedConsumable, GenericItem},
+    types::{
+        ConsumableType, Effect, EffectType, Element, EncodingVersion, ItemType, Powder, RollType,
+        Stat,
+    },
 };
 
 #[test]
@@ -161,4 +167,65 @@ fn v2_shiny() {
     .unwrap();
 
     assert_eq!(&out,"󰀁󰄀󰉗󶅲󷀀󰌉󰄁󲤲󴖴󰅱󱅤󶔢󵥣󱢏󰍍󱦯󰥋󱜻󷀄󱹵󵇨󰉐󲛖󰑙󰐃󰀅󰔆󰘂󰃿")
-}
\ No newline at end of file
+}
+
+#[test]
+fn consume_consumable() {
+    let ver = EncodingVersion::V2;
+
+    let item = CraftedConsumable::try_from(GenericItem {
+        kind: ItemType::CraftedConsu,
+        crafted_consumable_type: Some(CraftedConsumableTypeData(ConsumableType::Potion)),
+        crafted_uses: Some(UsesData { current: 2, max: 3 }),
+        crafted_reqs: Some(RequirementsData {
+            level: 50,
+            class: None,
+            skills: vec![],
+        }),
+        crafted_effects: Some(EffectsData {
+            effects: vec![
+                Effect {
+                    kind: EffectType::Heal,
+                    value: 400,
+                },
+                Effect {
+                    kind: EffectType::Duration,
+                    value: 60,
+                },
+                Effect {
+                    kind: EffectType::Heal,
+                    value: 50,
+                },
+            ],
+        }),
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        item.effects_per_use(),
+        ConsumableEffects {
+            heal: 450,
+            mana: 0,
+            duration: 60,
+        }
+    );
+    assert_eq!(
+        item.remaining_effects(),
+        ConsumableEffects {
+            heal: 900,
+            mana: 0,
+            duration: 120,
+        }
+    );
+
+    let (used, encoded) = item.consume(ver).unwrap();
+    assert_eq!(used.uses, UsesData { current: 1, max: 3 });
+    assert_eq!(CraftedConsumable::decode_string(&encoded).unwrap(), used);
+    assert_eq!(used.remaining_effects().heal, 450);
+
+    let (used, _) = used.consume(ver).unwrap();
+    assert!(used.is_used_up());
+    assert_eq!(used.remaining_effects(), ConsumableEffects::default());
+    assert!(matches!(used.consume(ver), Err(ConsumeError::NoUsesLeft)));
+}
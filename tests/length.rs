@@ -0,0 +1,203 @@
+use idmangler_lib::{
+    block::{
+        encode_blocks, encoded_blocks_len, AnyBlock, CraftedConsumableTypeData,
+        CraftedIdentificationData, DataBlockId, EffectsData, EndData, NameData, PowderData,
+        RequirementsData, ShinyData, StartData, UnknownBlock, UsesData,
+    },
+    encoding::{DataEncoder, EncodeError},
+    item::{error::BudgetError, GenericItem, Item, OptionalBlock},
+    types::{
+        ConsumableType, CraftedStat, Effect, EffectType, Element, EncodingVersion, ItemType, Powder,
+    },
+};
+
+/// Items decoded from idstrings encoded by wynntils
+const ITEMS: [&str; 4] = [
+    "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿",
+    "󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿",
+    "󰀀󰄀󰉇󶡯󷍴󶱹󲁃󶅰󰀃󰌁󰀣󰡽󳶂󰄬󲄋󷸄󰈀􏿮",
+    "󰀁󰄀󰉗󶅲󷀀󰌉󰄁󲤲󴖴󰅱󱅤󶔢󵥣󱢏󰍍󱦯󰥋󱜻󷀄󱹵󵇨󰉐󲛖󰑙󰐃󰀅󰔆󰘂󰃿",
+];
+
+fn consumable() -> GenericItem {
+    GenericItem {
+        kind: ItemType::CraftedConsu,
+        name: Some("Potion of Healing".to_string()),
+        crafted_consumable_type: Some(CraftedConsumableTypeData(ConsumableType::Potion)),
+        crafted_uses: Some(UsesData { current: 2, max: 3 }),
+        crafted_reqs: Some(RequirementsData {
+            level: 103,
+            class: None,
+            skills: vec![],
+        }),
+        crafted_effects: Some(EffectsData {
+            effects: vec![
+                Effect {
+                    kind: EffectType::Heal,
+                    value: 2000,
+                },
+                Effect {
+                    kind: EffectType::Duration,
+                    value: -12,
+                },
+            ],
+        }),
+        crafted_identifications: Some(CraftedIdentificationData {
+            idents: vec![CraftedStat {
+                kind: 4,
+                max: 1_000_000,
+            }],
+        }),
+        ..Default::default()
+    }
+}
+
+fn items() -> Vec<GenericItem> {
+    ITEMS
+        .iter()
+        .map(|i| GenericItem::decode_string(i).unwrap())
+        .chain([consumable()])
+        .collect()
+}
+
+#[test]
+fn item_len() {
+    for item in items() {
+        for &ver in EncodingVersion::ALL {
+            let len = item.encoded_len(ver).unwrap();
+
+            let mut blocks = vec![StartData(ver).into()];
+            blocks.append(&mut item.clone().into_blocks());
+            blocks.push(EndData.into());
+
+            assert_eq!(len.bytes, encode_blocks(ver, &blocks).unwrap().len());
+            assert_eq!(len, encoded_blocks_len(ver, &blocks).unwrap());
+            assert_eq!(
+                len.chars(),
+                item.clone().encode(ver).unwrap().chars().count()
+            );
+            assert_eq!(
+                len.ascii_chars(),
+                item.clone().encode_ascii(ver).unwrap().len()
+            );
+            assert_eq!(
+                len.utf16_len(),
+                item.clone().encode(ver).unwrap().encode_utf16().count()
+            );
+
+            // the specific item types calculate the same length
+            let specific = Item::try_from(item.clone()).unwrap();
+            assert_eq!(specific.encoded_len(ver).unwrap(), len);
+        }
+    }
+}
+
+#[test]
+fn block_len() {
+    let blocks: Vec<AnyBlock> = vec![
+        ShinyData {
+            id: 3,
+            rr: 4,
+            val: -100_000,
+        }
+        .into(),
+        PowderData {
+            powder_slots: 20,
+            powders: [(Element::Fire, 6); 17]
+                .into_iter()
+                .chain([(Element::Air, 1), (Element::Water, 3)])
+                .map(|p| Powder::try_from(p).unwrap())
+                .collect(),
+        }
+        .into(),
+        UnknownBlock {
+            id: 200,
+            data: vec![0; 300],
+        }
+        .into(),
+    ];
+
+    for block in blocks {
+        for &ver in EncodingVersion::ALL {
+            let mut out = Vec::new();
+            block.encode(ver, &mut out).unwrap();
+
+            assert_eq!(block.encoded_len(ver).unwrap().bytes, out.len());
+        }
+    }
+}
+
+#[test]
+fn len_errors() {
+    let err = NameData("Ünicode".to_string())
+        .encoded_len(EncodingVersion::V1)
+        .unwrap_err();
+
+    assert!(matches!(err.error, EncodeError::NonAsciiString));
+    assert_eq!(err.during, DataBlockId::NameData);
+}
+
+#[test]
+fn fit_to_budget() {
+    let ver = EncodingVersion::V2;
+    let item = GenericItem::decode_string(ITEMS[3]).unwrap();
+    let len = item.encoded_len(ver).unwrap().chars();
+
+    // nothing is dropped if the item already fits
+    let fitted = item
+        .clone()
+        .fit_to_budget(ver, len, &[OptionalBlock::Shiny])
+        .unwrap();
+    assert_eq!(fitted, item);
+
+    // blocks are dropped in order until the item fits
+    let fitted = item
+        .clone()
+        .fit_to_budget(
+            ver,
+            len - 1,
+            &[
+                OptionalBlock::Rerolls,
+                OptionalBlock::Shiny,
+                OptionalBlock::Name,
+            ],
+        )
+        .unwrap();
+    assert_eq!(fitted.rerolls, None);
+    assert_eq!(fitted.name, item.name);
+    assert!(fitted.encoded_len(ver).unwrap().chars() < len);
+
+    let err = item
+        .clone()
+        .fit_to_budget(ver, 3, &[OptionalBlock::Name])
+        .unwrap_err();
+    assert!(matches!(err, BudgetError::TooLong { budget: 3, .. }));
+}
+
+#[test]
+fn typed_extra_blocks() {
+    let unknown = AnyBlock::from(UnknownBlock {
+        id: 200,
+        data: vec![1, 2, 3],
+    });
+
+    for mut item in items() {
+        item.extra_blocks.push(unknown.clone());
+
+        // the specific item types keep the extra blocks through conversions and encoding
+        let specific = Item::try_from(item.clone()).unwrap();
+        assert_eq!(GenericItem::from(specific.clone()), item);
+
+        for &ver in EncodingVersion::ALL {
+            let encoded = specific.clone().encode(ver).unwrap();
+            assert_eq!(encoded, item.clone().encode(ver).unwrap());
+            if specific.minimum_version().is_some_and(|min| ver >= min) {
+                assert_eq!(Item::decode_string(&encoded).unwrap(), specific);
+            }
+            assert_eq!(
+                specific.encoded_len(ver).unwrap(),
+                item.encoded_len(ver).unwrap()
+            );
+        }
+    }
+}
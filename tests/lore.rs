@@ -0,0 +1,303 @@
+use idmangler_lib::{
+    block::{
+        CraftedConsumableTypeData, CraftedGearTypeData, CraftedIdentificationData, DamageData,
+        DefenseData, DurabilityData, EffectsData, PowderData, RequirementsData, UsesData,
+    },
+    item::{CraftedConsumable, CraftedGear, GearItem, GenericItem},
+    lore::{Color, LoreRenderer, TextComponent},
+    types::{
+        AttackSpeed, ConsumableType, CraftedGearType, CraftedStat, Effect, EffectType, Element,
+        IdKeys, ItemType, Powder, SkillType,
+    },
+};
+
+fn id_keys() -> IdKeys {
+    IdKeys::from_iter([
+        ("rawHealth", 2),
+        ("lifeSteal", 4),
+        ("manaSteal", 18),
+        ("raw1stSpellCost", 23),
+        ("raw2ndSpellCost", 24),
+        ("rawDexterity", 45),
+        ("healthRegenRaw", 81),
+    ])
+}
+
+#[test]
+fn gear_lore() {
+    let keys = id_keys();
+    let item = GearItem::decode_string("󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿").unwrap();
+
+    let lore = LoreRenderer::new().with_id_keys(&keys).gear(&item);
+
+    assert_eq!(
+        lore.plain_text(),
+        vec!["Breezehands", "+5 Health Regen", "+1 Dexterity"]
+    );
+
+    // stat values are coloured by sign and the names are gray
+    assert_eq!(
+        lore.lines[0],
+        TextComponent::text("")
+            .italic(false)
+            .append(TextComponent::colored("+5", Color::Green))
+            .append(TextComponent::colored(" Health Regen", Color::Gray))
+    );
+}
+
+#[test]
+fn non_extended_lore() {
+    let keys = id_keys();
+    let item = GearItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+
+    let lore = LoreRenderer::new().with_id_keys(&keys).gear(&item);
+
+    // without the base values only the rolls can be displayed
+    assert_eq!(
+        lore.plain_text(),
+        vec![
+            "⬡ Shiny Immolation",
+            "[81%] 2nd Spell Cost",
+            "[73%] 1st Spell Cost",
+            "[75%] Mana Steal",
+            "[102%] Life Steal",
+            "[48%] Health",
+            "",
+            "[3/3] Powder Slots [❋ ✹ ✹]",
+            "⬡ Shiny Stat #6: 0",
+            "Rerolls: 4",
+        ]
+    );
+
+    let powders = lore.lines[6].spans();
+    assert_eq!(powders[2].text, "❋");
+    assert_eq!(powders[2].color, Color::White);
+    assert_eq!(powders[4].text, "✹");
+    assert_eq!(powders[4].color, Color::Red);
+}
+
+#[test]
+fn unknown_stat_names() {
+    let item = GearItem::decode_string("󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿").unwrap();
+
+    let lore = LoreRenderer::new().gear(&item);
+
+    assert_eq!(lore.plain_text()[1..], ["+5 Stat #81", "+1 Stat #45"]);
+}
+
+fn crafted_gear() -> CraftedGear {
+    CraftedGear::try_from(GenericItem {
+        kind: ItemType::CraftedGear,
+        crafted_type: Some(CraftedGearTypeData(CraftedGearType::Spear)),
+        crafted_durability: Some(DurabilityData {
+            effect_strenght: 92,
+            current: 100,
+            max: 150,
+        }),
+        crafted_reqs: Some(RequirementsData {
+            level: 100,
+            class: None,
+            skills: vec![(SkillType::Strength, 40)],
+        }),
+        crafted_damage: Some(DamageData {
+            attack_speed: AttackSpeed::Slow,
+            damages: vec![(None, 100..140), (Some(Element::Earth), 50..70)],
+        }),
+        crafted_identifications: Some(CraftedIdentificationData {
+            idents: vec![
+                CraftedStat { kind: 4, max: 12 },
+                CraftedStat { kind: 23, max: -5 },
+                CraftedStat { kind: 45, max: -2 },
+            ],
+        }),
+        powders: Some(PowderData {
+            powder_slots: 2,
+            powders: vec![Powder::new(Element::Earth, 5).unwrap()],
+        }),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn crafted_gear_lore() {
+    let keys = id_keys();
+    let lore = LoreRenderer::new()
+        .with_id_keys(&keys)
+        .crafted_gear(&crafted_gear());
+
+    assert_eq!(
+        lore.plain_text(),
+        vec![
+            "Crafted Spear",
+            "Slow Attack Speed",
+            "✣ Neutral Damage: 100-140",
+            "✤ Earth Damage: 50-70",
+            "",
+            "Combat Lv. Min: 100",
+            "Strength Min: 40",
+            "",
+            "+12/3s Life Steal",
+            "-5 1st Spell Cost",
+            "-2 Dexterity",
+            "",
+            "[1/2] Powder Slots [✤]",
+            "Durability: 100/150 [92%]",
+            "Crafted Spear",
+        ]
+    );
+
+    // negative spell costs are beneficial
+    assert_eq!(lore.lines[8].spans()[0].color, Color::Green);
+    assert_eq!(lore.lines[9].spans()[0].color, Color::Red);
+    assert_eq!(lore.name.spans()[0].color, Color::DarkAqua);
+}
+
+#[test]
+fn crafted_consumable_lore() {
+    let item = CraftedConsumable::try_from(GenericItem {
+        kind: ItemType::CraftedConsu,
+        name: Some(String::from("Healing Potion")),
+        crafted_consumable_type: Some(CraftedConsumableTypeData(ConsumableType::Potion)),
+        crafted_uses: Some(UsesData { current: 2, max: 3 }),
+        crafted_reqs: Some(RequirementsData {
+            level: 20,
+            class: None,
+            skills: Vec::new(),
+        }),
+        crafted_effects: Some(EffectsData {
+            effects: vec![
+                Effect {
+                    kind: EffectType::Heal,
+                    value: 150,
+                },
+                Effect {
+                    kind: EffectType::Duration,
+                    value: 60,
+                },
+            ],
+        }),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let lore = LoreRenderer::new().crafted_consumable(&item);
+
+    assert_eq!(
+        lore.plain_text(),
+        vec![
+            "Healing Potion",
+            "Effect:",
+            "- Heal: +150",
+            "- Duration: 60 s",
+            "",
+            "Combat Lv. Min: 20",
+            "Crafted Potion [2/3]",
+        ]
+    );
+}
+
+#[test]
+fn generic_item_lore() {
+    let generic = GenericItem::from(crafted_gear());
+
+    assert_eq!(
+        LoreRenderer::new().item(&generic).unwrap(),
+        LoreRenderer::new().crafted_gear(&crafted_gear())
+    );
+
+    let mut broken = generic;
+    broken.crafted_durability = None;
+    assert!(LoreRenderer::new().item(&broken).is_err());
+}
+
+#[test]
+fn defense_lore() {
+    let mut generic = GenericItem::from(crafted_gear());
+    generic.crafted_type = Some(CraftedGearTypeData(CraftedGearType::Helmet));
+    generic.crafted_damage = None;
+    generic.crafted_defense = Some(DefenseData {
+        health: 300,
+        defences: vec![(Element::Water, 20), (Element::Fire, -10)],
+    });
+
+    let lore = LoreRenderer::new().item(&generic).unwrap();
+
+    assert_eq!(
+        lore.plain_text()[1..4],
+        [
+            "❤ Health: +300",
+            "❉ Water Defence: +20",
+            "✹ Fire Defence: -10"
+        ]
+    );
+}
+
+#[test]
+fn component_json() {
+    let component = TextComponent::text("")
+        .italic(false)
+        .append(TextComponent::colored("\"quoted\"\n", Color::DarkGreen).bold(true));
+
+    assert_eq!(
+        component.to_json(),
+        r#"{"text":"","italic":false,"extra":[{"text":"\"quoted\"\n","color":"dark_green","bold":true}]}"#
+    );
+}
+
+#[cfg(feature = "database")]
+mod database {
+    use idmangler_lib::{
+        database::{ItemDatabase, ShinyStat, ShinyStats},
+        item::GearItem,
+        lore::{Color, LoreRenderer},
+        types::IdKeys,
+    };
+
+    fn database() -> ItemDatabase {
+        let keys = IdKeys::from_json(include_str!("data/id_keys.json")).unwrap();
+
+        let mut db = ItemDatabase::from_json(include_str!("data/items.json"), keys).unwrap();
+        db.set_shiny_stats(ShinyStats::from_iter([ShinyStat {
+            id: 6,
+            key: String::from("warsWon"),
+            display_name: String::from("Wars Won"),
+        }]));
+        db
+    }
+
+    #[test]
+    fn database_lore() {
+        let db = database();
+        let item = GearItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+
+        let lore = LoreRenderer::new().with_database(&db).gear(&item);
+
+        assert_eq!(
+            lore.plain_text(),
+            vec![
+                "⬡ Shiny Immolation",
+                "Normal Attack Speed",
+                "✹ Fire Damage: 180-230",
+                "❋ Air Damage: 130-160",
+                "",
+                "Class Req: Shaman/Skyseer",
+                "Combat Lv. Min: 100",
+                "Defence Min: 55",
+                "Agility Min: 45",
+                "",
+                "+15 Defence",
+                "-24 2nd Spell Cost",
+                "-15 1st Spell Cost",
+                "+8/3s Mana Steal",
+                "+306/3s Life Steal*",
+                "+1200 Health",
+                "",
+                "[3/3] Powder Slots [❋ ✹ ✹]",
+                "⬡ Wars Won: 0",
+                "Legendary Item [4]",
+            ]
+        );
+        assert_eq!(lore.name.spans()[1].color, Color::Aqua);
+    }
+}
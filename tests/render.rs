@@ -0,0 +1,82 @@
+#![cfg(feature = "render")]
+
+use idmangler_lib::{
+    item::GenericItem,
+    lore::{Color, ItemLore, LoreRenderer, TextComponent},
+    render::Tooltip,
+};
+
+fn lore() -> ItemLore {
+    ItemLore {
+        name: TextComponent::colored("Warp", Color::Aqua),
+        lines: vec![
+            TextComponent::colored("+25 Agility", Color::Green),
+            TextComponent::text(""),
+            TextComponent::colored("✤ ❋", Color::DarkGreen).bold(true),
+        ],
+    }
+}
+
+#[test]
+fn render_lore() {
+    let image = Tooltip::new().with_scale(1).render_lore(&lore());
+
+    // 4 lines of text with padding on both sides and a gap after the name
+    assert_eq!(image.height(), 4 + 8 + 10 * 3 + 2 + 4);
+    assert_eq!(
+        image.pixels().len(),
+        (image.width() * image.height() * 4) as usize
+    );
+
+    // the corners are transparent while the rest is covered by the background
+    assert_eq!(image.pixel(0, 0)[3], 0);
+    assert_eq!(image.pixel(image.width() - 1, image.height() - 1)[3], 0);
+    assert_ne!(image.pixel(2, 2)[3], 0);
+
+    // the first column of the `W` in the name
+    assert_eq!(image.pixel(4, 4), [0x55, 0xFF, 0xFF, 0xFF]);
+    // and its shadow below it
+    assert_eq!(image.pixel(5, 11), [0x15, 0x3F, 0x3F, 0xFF]);
+}
+
+#[test]
+fn render_scale() {
+    let small = Tooltip::new().with_scale(1).render_lore(&lore());
+    let large = Tooltip::new().with_scale(3).render_lore(&lore());
+
+    assert_eq!(large.width(), small.width() * 3);
+    assert_eq!(large.height(), small.height() * 3);
+    assert_eq!(large.pixel(14, 14), small.pixel(4, 4));
+
+    // huge scales are capped instead of overflowing the image size
+    let capped = Tooltip::new().with_scale(u32::MAX).render_lore(&lore());
+    assert_eq!(capped.width(), small.width() * Tooltip::MAX_SCALE);
+}
+
+#[test]
+fn render_png() {
+    let item = GenericItem::decode_string("󰀀󰄀󰉉󶵭󶽬󶅴󶥯󶸀󰌅󰀘󵄗󴤒󴬄󶘂󳀄󰌃󿘰󰔄󰘆󰃿").unwrap();
+    let tooltip = Tooltip::new();
+
+    let image = tooltip.render(&item, &LoreRenderer::new()).unwrap();
+    let png = tooltip.render_png(&item, &LoreRenderer::new()).unwrap();
+
+    let decoder = png::Decoder::new(png.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+
+    assert_eq!(info.width, image.width());
+    assert_eq!(info.height, image.height());
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(&buf[..info.buffer_size()], image.pixels());
+}
+
+#[test]
+fn render_invalid_item() {
+    let item = GenericItem::default();
+
+    assert!(Tooltip::new()
+        .render_png(&item, &LoreRenderer::new())
+        .is_err());
+}
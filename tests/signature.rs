@@ -0,0 +1,114 @@
+#![cfg(feature = "signature")]
+
+use idmangler_lib::{
+    block::{decode_str, AnyBlock, SignatureData},
+    ed25519_dalek::{SigningKey, VerifyingKey},
+    item::{error::SignatureError, GenericItem, SignedItem},
+    types::EncodingVersion,
+};
+
+const BREEZEHANDS: &str = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+}
+
+#[test]
+fn sign_and_verify() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let encoded = item
+        .clone()
+        .encode_signed(EncodingVersion::V1, &key())
+        .unwrap();
+
+    let signed = SignedItem::decode_string(&encoded).unwrap();
+    assert_eq!(signed.item, item);
+    assert_eq!(signed.version, EncodingVersion::V1);
+    assert!(signed.signature.is_some());
+
+    signed.verify(&key().verifying_key()).unwrap();
+
+    // re-encoding keeps the signature
+    assert_eq!(signed.encode().unwrap(), encoded);
+}
+
+#[test]
+fn strip_signature() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let encoded = item
+        .clone()
+        .encode_signed(EncodingVersion::V1, &key())
+        .unwrap();
+
+    assert!(decode_str(&encoded)
+        .unwrap()
+        .iter()
+        .any(|b| matches!(b, AnyBlock::SignatureData(_))));
+
+    // decoding as a generic item drops the signature
+    let stripped = GenericItem::decode_string(&encoded).unwrap();
+    assert_eq!(stripped, item);
+    assert_eq!(stripped.encode(EncodingVersion::V1).unwrap(), BREEZEHANDS);
+
+    let signed = SignedItem::decode_string(&encoded).unwrap();
+    assert_eq!(signed.into_item(), item);
+}
+
+#[test]
+fn wrong_key() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let signed = item.sign(EncodingVersion::V1, &key()).unwrap();
+
+    let other = SigningKey::from_bytes(&[8; 32]).verifying_key();
+    assert!(matches!(
+        signed.verify(&other),
+        Err(SignatureError::Invalid(_))
+    ));
+}
+
+#[test]
+fn modified_item() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+    let mut signed = item.sign(EncodingVersion::V1, &key()).unwrap();
+
+    signed.item.name = Some("Breezehandz".to_string());
+    assert!(matches!(
+        signed.verify(&key().verifying_key()),
+        Err(SignatureError::Invalid(_))
+    ));
+}
+
+#[test]
+fn unsigned() {
+    let signed = SignedItem::decode_string(BREEZEHANDS).unwrap();
+
+    assert!(signed.signature.is_none());
+    assert!(matches!(
+        signed.verify(&key().verifying_key()),
+        Err(SignatureError::Unsigned)
+    ));
+}
+
+#[test]
+fn weak_key() {
+    let item = GenericItem::decode_string(BREEZEHANDS).unwrap();
+
+    // with the identity point as key the identity point and a zero scalar are a valid signature of anything,
+    // unless weak keys are rejected
+    let mut identity = [0; 32];
+    identity[0] = 1;
+    let key = VerifyingKey::from_bytes(&identity).unwrap();
+
+    let mut signature = [0; SignatureData::LEN];
+    signature[..32].copy_from_slice(&identity);
+    let signed = SignedItem {
+        item,
+        version: EncodingVersion::V1,
+        signature: Some(SignatureData(signature)),
+    };
+
+    assert!(matches!(
+        signed.verify(&key),
+        Err(SignatureError::Invalid(_))
+    ));
+}
@@ -1,5 +1,18 @@
+mod build;
+mod bundle;
+mod checksum;
+mod craft;
+mod custom;
+mod damage;
+mod database;
 mod decode;
 mod decode_item;
 mod encode;
 mod encode_item;
+mod length;
+mod lore;
+mod render;
+mod signature;
 mod unit;
+mod v3;
+mod wynnbuilder;
@@ -0,0 +1,94 @@
+use idmangler_lib::{
+    block::decode_str,
+    encoding::{
+        ascii::{
+            decode_any, decode_ascii, encode_ascii, from_ascii, is_ascii_encoded, to_ascii,
+            BadAsciiString, ASCII_PREFIX,
+        },
+        string::{decode_string, encode_string},
+    },
+    item::{decode_bundle_generic, GenericItem},
+    types::EncodingVersion,
+};
+
+const BREEZEHANDS: &str = "󰀀󰄀󰉂󷉥󶕺󶕨󶅮󶑳󰀃󰀁󰉑󰨭󰋿";
+
+#[test]
+fn known_values() {
+    for (bytes, text) in [
+        (&b""[..], ""),
+        (b"f", "Zg"),
+        (b"fo", "Zm8"),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg"),
+        (&[0xFB, 0xFF][..], "-_8"),
+    ] {
+        let enc = encode_ascii(bytes);
+        assert_eq!(enc, format!("{ASCII_PREFIX}{text}"));
+        assert_eq!(decode_ascii(&enc).unwrap(), bytes);
+    }
+
+    // padding is optional
+    assert_eq!(decode_ascii(format!("{ASCII_PREFIX}Zg==")).unwrap(), b"f");
+}
+
+#[test]
+fn roundtrip_allbytes() {
+    let bytes = (0..=255).collect::<Vec<u8>>();
+
+    for len in 0..bytes.len() {
+        let enc = encode_ascii(&bytes[..len]);
+
+        assert!(enc.is_ascii());
+        assert_eq!(decode_ascii(&enc).unwrap(), &bytes[..len]);
+    }
+}
+
+#[test]
+fn lossless_conversion() {
+    // both even and odd byte lengths
+    for bytes in [&[0, 1, 0xFF, 0xFE][..], &[0, 1, 0xFF, 0xFE, 7][..]] {
+        let pua = encode_string(bytes);
+        let ascii = to_ascii(&pua).unwrap();
+
+        assert!(is_ascii_encoded(&ascii));
+        assert_eq!(from_ascii(&ascii).unwrap(), pua);
+        assert_eq!(decode_any(&ascii).unwrap(), decode_string(&pua).unwrap());
+    }
+}
+
+#[test]
+fn bad_strings() {
+    assert!(matches!(
+        decode_ascii("Zm9v"),
+        Err(BadAsciiString::MissingPrefix)
+    ));
+    assert!(matches!(
+        decode_ascii(format!("{ASCII_PREFIX}Zm+v")),
+        Err(BadAsciiString::InvalidChar('+'))
+    ));
+    assert!(matches!(
+        decode_ascii(format!("{ASCII_PREFIX}Zm9vY")),
+        Err(BadAsciiString::InvalidLength(5))
+    ));
+}
+
+#[test]
+fn decode_entry_points() {
+    let ascii = to_ascii(BREEZEHANDS).unwrap();
+
+    assert_eq!(
+        decode_str(&ascii).unwrap(),
+        decode_str(BREEZEHANDS).unwrap()
+    );
+
+    let item = GenericItem::decode_string(&ascii).unwrap();
+    assert_eq!(item, GenericItem::decode_string(BREEZEHANDS).unwrap());
+    assert_eq!(
+        item.clone().encode_ascii(EncodingVersion::V1).unwrap(),
+        ascii
+    );
+
+    let items = decode_bundle_generic(format!("{ascii}{ascii} {BREEZEHANDS}{ascii}")).unwrap();
+    assert_eq!(items, vec![item; 4]);
+}
@@ -0,0 +1,28 @@
+use idmangler_lib::{block::DurabilityData, types::CraftedStat};
+
+#[test]
+fn durability_effectiveness() {
+    let durability = DurabilityData {
+        effect_strenght: 87,
+        current: 1,
+        max: 200,
+    };
+    assert_eq!(durability.effectiveness(), 87);
+
+    let broken = DurabilityData {
+        current: 0,
+        ..durability
+    };
+    assert_eq!(broken.effectiveness(), 0);
+}
+
+#[test]
+fn crafted_stat_value() {
+    let stat = CraftedStat { kind: 0, max: 25 };
+    assert_eq!(stat.value_at(100), 25);
+    assert_eq!(stat.value_at(50), 13);
+    assert_eq!(stat.value_at(0), 0);
+
+    let negative = CraftedStat { kind: 0, max: -25 };
+    assert_eq!(negative.value_at(50), -12);
+}
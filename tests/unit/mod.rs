@@ -1,6 +1,10 @@
+mod ascii;
+mod durabilitydata;
 mod encoding;
 mod identdata;
 mod namedata;
 mod powderdata;
+mod powders;
 mod startdata;
 mod typedata;
+mod version;
@@ -0,0 +1,148 @@
+use idmangler_lib::{
+    block::{DamageData, DefenseData, PowderData},
+    types::{
+        ActiveSpecial, AttackSpeed, CraftedGearType, Element, Powder, PowderSpecial, PowderStats,
+    },
+};
+
+fn powder(elem: Element, tier: u8) -> Powder {
+    Powder::new(elem, tier).unwrap()
+}
+
+fn weapon(damages: Vec<(Option<Element>, std::ops::Range<i32>)>) -> DamageData {
+    DamageData {
+        attack_speed: AttackSpeed::Normal,
+        damages,
+    }
+}
+
+#[test]
+fn powder_stats() {
+    assert_eq!(
+        powder(Element::Fire, 6).stats(),
+        PowderStats {
+            min: 10,
+            max: 12,
+            conversion: 37,
+            defence_bonus: 36,
+            defence_penalty: 12,
+        }
+    );
+    assert_eq!(powder(Element::Thunder, 1).stats().conversion, 9);
+
+    assert_eq!(powder(Element::Earth, 1).weakened_element(), Element::Air);
+    assert_eq!(powder(Element::Air, 1).weakened_element(), Element::Fire);
+}
+
+#[test]
+fn weapon_powders() {
+    let base = weapon(vec![(None, 100..200)]);
+
+    let fire = powder(Element::Fire, 6);
+    assert_eq!(
+        base.with_powders(&[fire, fire]).damages,
+        [(None, 26..52), (Some(Element::Fire), 94..172)]
+    );
+
+    // the neutral damage runs out before the last powder is fully converted
+    let earth = powder(Element::Earth, 6);
+    assert_eq!(
+        base.with_powders(&[earth, earth, earth]).damages,
+        [(Some(Element::Earth), 133..239)]
+    );
+
+    // values are rounded after all of the powders are applied
+    let small = weapon(vec![(None, 10..15)]);
+    assert_eq!(
+        small.with_powders(&[powder(Element::Thunder, 1)]).damages,
+        [(None, 9..14), (Some(Element::Thunder), 2..9)]
+    );
+
+    // elemental weapons without neutral damage only gain the flat damage
+    let air = weapon(vec![(None, 0..0), (Some(Element::Air), 38..46)]);
+    assert_eq!(
+        air.with_powders(&[powder(Element::Air, 6)]).damages,
+        [(Some(Element::Air), 46..60)]
+    );
+
+    assert_eq!(base.with_powders(&[]), base);
+}
+
+#[test]
+fn armour_powders() {
+    let armour = DefenseData {
+        health: 420,
+        defences: vec![(Element::Water, 30), (Element::Fire, -20)],
+    };
+
+    let water = powder(Element::Water, 6);
+    assert_eq!(
+        armour.with_powders(&[water, water]),
+        DefenseData {
+            health: 420,
+            defences: vec![
+                (Element::Thunder, -20),
+                (Element::Water, 110),
+                (Element::Fire, -20),
+            ],
+        }
+    );
+}
+
+#[test]
+fn powder_specials() {
+    let data = PowderData {
+        powder_slots: 3,
+        powders: vec![
+            powder(Element::Earth, 6),
+            powder(Element::Earth, 5),
+            powder(Element::Thunder, 3),
+        ],
+    };
+
+    assert_eq!(
+        data.special(CraftedGearType::Spear),
+        Some(ActiveSpecial {
+            special: PowderSpecial::Quake,
+            level: 5,
+        })
+    );
+    assert_eq!(
+        data.special(CraftedGearType::Helmet),
+        Some(ActiveSpecial {
+            special: PowderSpecial::Rage,
+            level: 5,
+        })
+    );
+    assert_eq!(data.special(CraftedGearType::Ring), None);
+
+    // low tier powders do not count towards specials
+    let low = [powder(Element::Air, 3), powder(Element::Air, 3)];
+    assert_eq!(PowderSpecial::detect(CraftedGearType::Bow, &low), None);
+
+    // the element with the highest level wins, ties go to the first element applied
+    let mixed = [
+        powder(Element::Water, 4),
+        powder(Element::Fire, 5),
+        powder(Element::Water, 4),
+        powder(Element::Air, 6),
+    ];
+    assert_eq!(
+        PowderSpecial::detect(CraftedGearType::Wand, &mixed),
+        Some(ActiveSpecial {
+            special: PowderSpecial::WindPrison,
+            level: 3,
+        })
+    );
+    assert_eq!(
+        PowderSpecial::detect(CraftedGearType::Wand, &mixed[..3]),
+        Some(ActiveSpecial {
+            special: PowderSpecial::Curse,
+            level: 2,
+        })
+    );
+
+    assert_eq!(PowderSpecial::ChainLightning.element(), Element::Thunder);
+    assert_eq!(PowderSpecial::KillStreak.name(), "Kill Streak");
+    assert!(!PowderSpecial::Dodge.is_weapon_special());
+}
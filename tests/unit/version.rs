@@ -0,0 +1,148 @@
+use idmangler_lib::{
+    block::{AnyBlock, DataBlockId, ShinyData, UnknownBlock},
+    item::{GearItem, GenericItem},
+    types::{EncodingVersion, VersionFeature},
+};
+
+#[test]
+fn supported_features() {
+    assert!(!EncodingVersion::V1.supports(VersionFeature::ShinyRerolls));
+    assert!(EncodingVersion::V2.supports(VersionFeature::ShinyRerolls));
+
+    for feature in [
+        VersionFeature::ItemTier,
+        VersionFeature::PackedPowders,
+        VersionFeature::BlockLengths,
+    ] {
+        assert!(!EncodingVersion::V1.supports(feature));
+        assert!(!EncodingVersion::V2.supports(feature));
+    }
+}
+
+#[cfg(feature = "v3-draft")]
+#[test]
+fn v3_features() {
+    for feature in [
+        VersionFeature::ShinyRerolls,
+        VersionFeature::ItemTier,
+        VersionFeature::PackedPowders,
+        VersionFeature::BlockLengths,
+    ] {
+        assert!(EncodingVersion::V3.supports(feature));
+    }
+
+    assert_eq!(EncodingVersion::try_from(2).unwrap(), EncodingVersion::V3);
+    assert!(EncodingVersion::V3.is_available());
+}
+
+#[cfg(not(feature = "v3-draft"))]
+#[test]
+fn no_v3() {
+    use idmangler_lib::encoding::EncodeError;
+
+    assert!(EncodingVersion::try_from(2).is_err());
+
+    // the draft version is declared but can not be encoded
+    assert!(!EncodingVersion::V3.is_available());
+    assert!(!EncodingVersion::ALL.contains(&EncodingVersion::V3));
+
+    let item = GenericItem {
+        name: Some("Warp".to_string()),
+        ..Default::default()
+    };
+    let err = item.encode(EncodingVersion::V3).unwrap_err();
+    assert_eq!(err.during, DataBlockId::StartData);
+    assert!(matches!(
+        err.error,
+        EncodeError::UnavailableVersion(EncodingVersion::V3)
+    ));
+
+    // unframed unknown blocks need block lengths, which no version in this build has
+    let unframed = AnyBlock::Unknown(UnknownBlock {
+        id: 50,
+        data: vec![],
+    });
+    assert_eq!(unframed.minimum_version(), None);
+}
+
+#[test]
+fn ordering() {
+    assert!(EncodingVersion::V1 < EncodingVersion::V2);
+    assert_eq!(EncodingVersion::ALL.first(), Some(&EncodingVersion::V1));
+    assert_eq!(
+        EncodingVersion::ALL.last(),
+        Some(&EncodingVersion::latest_draft())
+    );
+    assert_eq!(EncodingVersion::latest(), EncodingVersion::V2);
+    assert!(EncodingVersion::ALL.is_sorted());
+
+    for feature in VersionFeature::ALL {
+        for ver in EncodingVersion::ALL {
+            assert_eq!(
+                ver.supports(feature),
+                feature.since().is_some_and(|since| *ver >= since)
+            );
+        }
+    }
+}
+
+#[test]
+fn capability_table() {
+    assert_eq!(
+        VersionFeature::ShinyRerolls.since(),
+        Some(EncodingVersion::V2)
+    );
+    assert_eq!(
+        VersionFeature::ShinyRerolls.block(),
+        Some(DataBlockId::ShinyData)
+    );
+    assert_eq!(VersionFeature::ShinyRerolls.field(), Some("rr"));
+    assert!(VersionFeature::ShinyRerolls.is_lossy());
+
+    assert_eq!(VersionFeature::BlockLengths.block(), None);
+    assert!(!VersionFeature::PackedPowders.is_lossy());
+
+    assert_eq!(
+        EncodingVersion::V2.features().collect::<Vec<_>>(),
+        [VersionFeature::ShinyRerolls]
+    );
+    assert_eq!(EncodingVersion::V1.features().count(), 0);
+}
+
+#[test]
+fn minimum_version() {
+    let mut item = GenericItem {
+        name: Some("Warp".to_string()),
+        shiny: Some(ShinyData {
+            id: 1,
+            rr: 0,
+            val: 20,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(item.minimum_version(), Some(EncodingVersion::V1));
+
+    item.shiny.as_mut().unwrap().rr = 3;
+    assert_eq!(item.minimum_version(), Some(EncodingVersion::V2));
+    assert_eq!(
+        GearItem::try_from(item.clone()).unwrap().minimum_version(),
+        Some(EncodingVersion::V2)
+    );
+
+    let framed = AnyBlock::Unknown(UnknownBlock {
+        id: 150,
+        data: vec![],
+    });
+    assert_eq!(framed.minimum_version(), Some(EncodingVersion::V1));
+
+    // an unknown block with the id of a block of this crate can not be encoded by any version
+    let builtin = AnyBlock::Unknown(UnknownBlock {
+        id: DataBlockId::NameData as u8,
+        data: vec![],
+    });
+    assert_eq!(builtin.minimum_version(), None);
+
+    item.extra_blocks.push(builtin);
+    assert_eq!(item.minimum_version(), None);
+    assert_eq!(GearItem::try_from(item).unwrap().minimum_version(), None);
+}
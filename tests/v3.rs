@@ -0,0 +1,212 @@
+#![cfg(feature = "v3-draft")]
+
+use idmangler_lib::{
+    block::{
+        decode_str, encode_blocks, AnyBlock, EndData, PowderData, ShinyData, StartData, TierData,
+        TypeData, UnknownBlock,
+    },
+    encoding::{BlockId, DataDecoder, DataEncoder},
+    item::{GearItem, GenericItem, Item},
+    types::{Element, EncodingVersion, ItemTier, ItemType, Powder},
+};
+
+fn item() -> GenericItem {
+    GenericItem {
+        kind: ItemType::Gear,
+        name: Some("Warp".to_string()),
+        powders: Some(PowderData {
+            powder_slots: 3,
+            powders: vec![
+                Powder::new(Element::Air, 6).unwrap(),
+                Powder::new(Element::Air, 6).unwrap(),
+                Powder::new(Element::Thunder, 4).unwrap(),
+            ],
+        }),
+        shiny: Some(ShinyData {
+            id: 1,
+            rr: 2,
+            val: 300,
+        }),
+        tier: Some(TierData(ItemTier::Legendary)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let encoded = item().encode(EncodingVersion::V3).unwrap();
+
+    assert_eq!(GenericItem::decode_string(encoded).unwrap(), item());
+}
+
+#[test]
+fn tier_needs_v3() {
+    let encoded = item().encode(EncodingVersion::V2).unwrap();
+    let decoded = GenericItem::decode_string(encoded).unwrap();
+
+    assert_eq!(decoded.tier, None);
+    assert_eq!(decoded.shiny, item().shiny);
+}
+
+#[test]
+fn block_lengths() {
+    let mut out = Vec::new();
+    TypeData(ItemType::Tome)
+        .encode(EncodingVersion::V3, &mut out)
+        .unwrap();
+
+    // id, zigzag encoded length of 1 and the payload
+    assert_eq!(out, [1, 2, 1]);
+
+    let mut out = Vec::new();
+    StartData(EncodingVersion::V3)
+        .encode(EncodingVersion::V3, &mut out)
+        .unwrap();
+    assert_eq!(out, [0, 2]);
+}
+
+#[test]
+fn packed_powders() {
+    let powders = PowderData {
+        powder_slots: 12,
+        powders: [(Element::Fire, 6); 10]
+            .into_iter()
+            .chain([(Element::Earth, 1)])
+            .map(|p| Powder::try_from(p).unwrap())
+            .collect(),
+    };
+
+    let mut out = Vec::new();
+    powders.encode_data(EncodingVersion::V3, &mut out).unwrap();
+
+    // slots, run count with the runs flag and runs of 8, 2 and 1 powders
+    assert_eq!(out, [12, 0x80 | 3, (7 << 5) | 24, (1 << 5) | 24, 1]);
+
+    let decoded = PowderData::decode_data(&mut out.into_iter(), EncodingVersion::V3).unwrap();
+    assert_eq!(decoded, powders);
+}
+
+#[test]
+fn mixed_powders() {
+    let powders = PowderData {
+        powder_slots: 5,
+        powders: [
+            (Element::Earth, 6),
+            (Element::Thunder, 6),
+            (Element::Water, 6),
+            (Element::Fire, 6),
+            (Element::Air, 6),
+        ]
+        .into_iter()
+        .map(|p| Powder::try_from(p).unwrap())
+        .collect(),
+    };
+
+    // runs would take 5 bytes, so the 5 bit form is used just like in older versions
+    assert_eq!(
+        powders.encoded_data_len(EncodingVersion::V3).unwrap(),
+        powders.encoded_data_len(EncodingVersion::V2).unwrap()
+    );
+
+    let mut v3 = Vec::new();
+    powders.encode_data(EncodingVersion::V3, &mut v3).unwrap();
+    let mut v2 = Vec::new();
+    powders.encode_data(EncodingVersion::V2, &mut v2).unwrap();
+    assert_eq!(v3, v2);
+    assert_eq!(
+        v3.len(),
+        powders.encoded_data_len(EncodingVersion::V3).unwrap()
+    );
+
+    let decoded = PowderData::decode_data(&mut v3.into_iter(), EncodingVersion::V3).unwrap();
+    assert_eq!(decoded, powders);
+}
+
+#[test]
+fn too_many_packed_powders() {
+    let powders = PowderData {
+        powder_slots: 0,
+        powders: vec![Powder::new(Element::Fire, 6).unwrap(); 128],
+    };
+
+    assert!(powders.encoded_data_len(EncodingVersion::V3).is_err());
+    assert!(powders
+        .encode_data(EncodingVersion::V3, &mut Vec::new())
+        .is_err());
+}
+
+#[test]
+fn tier_id() {
+    // the tier block uses an id from the extension range
+    assert_eq!(u8::from(TierData(ItemTier::Legendary).block_id()), 243);
+}
+
+#[test]
+fn skip_unknown_blocks() {
+    // any unknown block can be skipped in V3, not only the ones in the framed range
+    let blocks = [
+        StartData(EncodingVersion::V3).into(),
+        TypeData(ItemType::Gear).into(),
+        UnknownBlock {
+            id: 50,
+            data: vec![1, 2, 3],
+        }
+        .into(),
+        EndData.into(),
+    ];
+    let bytes = encode_blocks(EncodingVersion::V3, &blocks).unwrap();
+
+    let decoded = AnyBlock::decode(&mut bytes.into_iter()).unwrap();
+    assert_eq!(decoded, blocks);
+
+    let encoded = idmangler_lib::encoding::string::encode_string(
+        &encode_blocks(EncodingVersion::V3, &blocks).unwrap(),
+    );
+    assert_eq!(decode_str(encoded).unwrap(), blocks);
+}
+
+#[test]
+fn minimum_version() {
+    assert_eq!(item().minimum_version(), Some(EncodingVersion::V3));
+    assert_eq!(EncodingVersion::latest(), EncodingVersion::V2);
+    assert_eq!(EncodingVersion::latest_draft(), EncodingVersion::V3);
+    assert!(EncodingVersion::V2 < EncodingVersion::V3);
+
+    let unframed = AnyBlock::Unknown(UnknownBlock {
+        id: 50,
+        data: vec![],
+    });
+    assert_eq!(unframed.minimum_version(), Some(EncodingVersion::V3));
+
+    // extra blocks of an item count towards its minimum version
+    let item = GenericItem {
+        kind: ItemType::Gear,
+        extra_blocks: vec![unframed],
+        ..Default::default()
+    };
+    assert_eq!(item.minimum_version(), Some(EncodingVersion::V3));
+}
+
+#[test]
+fn typed_tier() {
+    let gear = GearItem::try_from(item()).unwrap();
+    assert_eq!(gear.tier, Some(TierData(ItemTier::Legendary)));
+    assert_eq!(gear.minimum_version(), item().minimum_version());
+
+    for &ver in EncodingVersion::ALL {
+        assert_eq!(
+            gear.encoded_len(ver).unwrap(),
+            item().encoded_len(ver).unwrap()
+        );
+        assert_eq!(
+            gear.clone().encode(ver).unwrap(),
+            item().encode(ver).unwrap()
+        );
+    }
+
+    let encoded = Item::from(gear.clone())
+        .encode(EncodingVersion::V3)
+        .unwrap();
+    assert_eq!(GearItem::decode_string(&encoded).unwrap(), gear);
+    assert_eq!(Item::decode_string(&encoded).unwrap(), Item::Gear(gear));
+}
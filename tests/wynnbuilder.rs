@@ -0,0 +1,488 @@
+use idmangler_lib::{
+    build::{SkillPoints, Slot},
+    types::{AttackSpeed, Element, Powder},
+    wynnbuilder::{
+        CraftHash, CustomItem, CustomStat, HashItem, WynnBuilderError, WynnBuilderHash,
+        DEFAULT_LEVEL, NO_INGREDIENT_ID,
+    },
+};
+
+// NOTE: these hashes were not exported from WynnBuilder. They were written by hand from a reading of the WynnBuilder
+// encoder, so they only check that the codec agrees with that reading. They should be replaced by hashes exported
+// from WynnBuilder itself, including crafted and custom items.
+// helmet 1200, bracelet 300 and weapon 3488 with 2 W6 powders on the helmet and 3 A6 powders on the weapon
+const V5: &str = "5_0Im2SH2SI2SJ2SK2SL04i2SN0sW000t0F00-x1g10009I0001007lU";
+const V1: &str = "1_0Im2SH2SI2SJ2SK2SL04i2SN0sW10009I0001007lU";
+const V3: &str = "3_0Im2SH2SI2SJ2SK2SL04i2SN0sW000t0F00-x1G10009I0001007lU";
+const V4_CRAFTED: &str = "4_2SG-10A0B00000C0D05612SI2SJ2SK2SL04i2SN0sW000t0F00-x1g00000";
+// crafted helmet, custom ring and custom spear with 2 W6 powders on the helmet and an E6 powder on the spear,
+// also written by hand, see the note above
+const V5_CUSTOM: &str = "5_CR-1GT+W+W+W+WGT01612SH2SI2SJ00oCI-11000CFixed%20Ring0540G0211a0H010K0M0201G0n01042SL2SM2SN01iCI-10000CTest%20Spear022040102058090520-400E0510-300F30M0201a0N00O010o0T01050j010250m0200U1a0t013C50w0201a4i00000000001g10009I000100006";
+const SPEAR: &str = "10000CTest%20Spear022040102058090520-400E0510-300F30M0201a0N00O010o0T01050j010250m0200U1a0t013C50w0201a4i";
+const RING: &str = "11000CFixed%20Ring0540G0211a0H010K0M0201G0n0104";
+const V7: &str = "7_0Im2SH2SI2SJ2SK2SL04i2SN0sW000t0F00-x1g10009I0001007lUtomesAndTree";
+
+fn powders(elem: Element, count: usize) -> Vec<Powder> {
+    vec![Powder::new(elem, 6).unwrap(); count]
+}
+
+fn craft() -> CraftHash {
+    CraftHash {
+        ingredients: [10, 11, 0, 0, 12, 13],
+        recipe: 5,
+        material_tiers: [3, 2],
+        attack_speed: AttackSpeed::Normal,
+    }
+}
+
+#[test]
+fn decode_hash() {
+    let hash = WynnBuilderHash::decode(V5).unwrap();
+
+    assert_eq!(hash.version, 5);
+    assert_eq!(hash.item(Slot::Helmet), &HashItem::Item(1200));
+    assert_eq!(hash.item(Slot::Chestplate), &HashItem::None);
+    assert_eq!(hash.item(Slot::Bracelet), &HashItem::Item(300));
+    assert_eq!(hash.item(Slot::Weapon), &HashItem::Item(3488));
+    assert_eq!(hash.skill_points, SkillPoints([0, 55, 15, 0, -5]));
+    assert_eq!(hash.level, 106);
+    assert_eq!(hash.powders(Slot::Helmet), powders(Element::Water, 2));
+    assert_eq!(hash.powders(Slot::Weapon), powders(Element::Air, 3));
+    assert!(hash.powders(Slot::Ring1).is_empty());
+    assert!(hash.extra.is_empty());
+
+    assert_eq!(hash.encode().unwrap(), V5);
+}
+
+#[test]
+fn decode_url() {
+    let url = format!("https://wynnbuilder.github.io/builder/#{V5}");
+
+    assert_eq!(
+        WynnBuilderHash::decode(&url).unwrap(),
+        WynnBuilderHash::decode(V5).unwrap()
+    );
+    assert_eq!(
+        WynnBuilderHash::decode(&format!("#{V5}")).unwrap(),
+        WynnBuilderHash::decode(V5).unwrap()
+    );
+}
+
+#[test]
+fn old_versions() {
+    let v5 = WynnBuilderHash::decode(V5).unwrap();
+
+    let v1 = WynnBuilderHash::decode(V1).unwrap();
+    assert_eq!(v1.items, v5.items);
+    assert_eq!(v1.powders, v5.powders);
+    assert_eq!(v1.skill_points, SkillPoints::default());
+    assert_eq!(v1.level, DEFAULT_LEVEL);
+
+    let v3 = WynnBuilderHash::decode(V3).unwrap();
+    assert_eq!(v3.items, v5.items);
+    assert_eq!(v3.skill_points, v5.skill_points);
+    assert_eq!(v3.level, 80);
+
+    // old hashes are upgraded when written
+    assert!(v1.encode().unwrap().starts_with("5_"));
+}
+
+fn spear() -> CustomItem {
+    let text = |v: &str| CustomStat::Text(v.to_string());
+    let rolled = |min, max| CustomStat::Rolled { min, max };
+
+    CustomItem {
+        fixed_ids: false,
+        stats: vec![
+            ("name".to_string(), text("Test Spear")),
+            ("tier".to_string(), text("Rare")),
+            ("slots".to_string(), CustomStat::Number(2)),
+            ("type".to_string(), text("Spear")),
+            ("nDam".to_string(), text("20-40")),
+            ("eDam".to_string(), text("10-30")),
+            ("atkSpd".to_string(), text("NORMAL")),
+            ("lvl".to_string(), CustomStat::Number(100)),
+            ("classReq".to_string(), text("Warrior")),
+            ("strReq".to_string(), CustomStat::Number(50)),
+            ("str".to_string(), CustomStat::Number(5)),
+            ("mr".to_string(), rolled(2, 5)),
+            ("ls".to_string(), rolled(30, 100)),
+            ("spd".to_string(), rolled(-12, -5)),
+            ("hpBonus".to_string(), rolled(100, 300)),
+        ],
+    }
+}
+
+#[test]
+fn crafted_items() {
+    let v4 = WynnBuilderHash::decode(V4_CRAFTED).unwrap();
+    assert_eq!(v4.item(Slot::Helmet), &HashItem::None);
+    assert_eq!(v4.item(Slot::Chestplate), &HashItem::Crafted(craft()));
+
+    let v5 = WynnBuilderHash::decode(V5_CUSTOM).unwrap();
+    assert_eq!(
+        v5.item(Slot::Helmet),
+        &HashItem::Crafted(CraftHash {
+            ingredients: [1053, 4000, 4000, 4000, 4000, 1053],
+            recipe: 1,
+            material_tiers: [3, 2],
+            attack_speed: AttackSpeed::Normal,
+        })
+    );
+    assert_eq!(NO_INGREDIENT_ID, 4000);
+    assert_eq!(v5.item(Slot::Weapon), &HashItem::Custom(spear()));
+    assert_eq!(v5.powders(Slot::Weapon), powders(Element::Earth, 1));
+    assert_eq!(v5.encode().unwrap(), V5_CUSTOM);
+
+    assert_eq!(craft().encode().unwrap(), "10A0B00000C0D0561");
+    assert_eq!(CraftHash::decode("10A0B00000C0D0561").unwrap(), craft());
+    assert!(matches!(
+        CraftHash::decode("20A0B00000C0D0561"),
+        Err(WynnBuilderError::InvalidCraft(_))
+    ));
+}
+
+#[test]
+fn custom_items() {
+    assert_eq!(CustomItem::decode(SPEAR).unwrap(), spear());
+    assert_eq!(spear().encode().unwrap(), SPEAR);
+
+    let ring = CustomItem::decode(RING).unwrap();
+    assert!(ring.fixed_ids);
+    assert_eq!(ring.text("name"), Some("Fixed Ring"));
+    assert_eq!(ring.number("hp"), -100);
+    assert_eq!(ring.number("fDef"), 20);
+    assert_eq!(ring.number("agi"), 0);
+    assert_eq!(ring.get("ms"), Some(&CustomStat::Rolled { min: 4, max: 4 }));
+    assert_eq!(ring.encode().unwrap(), RING);
+
+    assert!(matches!(
+        CustomItem::decode("20"),
+        Err(WynnBuilderError::InvalidCustom(_))
+    ));
+    // stat index past the known stats
+    assert!(matches!(
+        CustomItem::decode("10zz"),
+        Err(WynnBuilderError::InvalidCustom(_))
+    ));
+    assert!(matches!(
+        CustomItem::decode("1000CTest"),
+        Err(WynnBuilderError::UnexpectedEnd)
+    ));
+
+    let mut item = spear();
+    item.stats
+        .push(("hp".to_string(), CustomStat::Text("a lot".to_string())));
+    assert!(matches!(
+        item.encode(),
+        Err(WynnBuilderError::InvalidCustomStat(name)) if name == "hp"
+    ));
+}
+
+#[test]
+fn extra_data() {
+    let hash = WynnBuilderHash::decode(V7).unwrap();
+
+    assert_eq!(hash.version, 7);
+    assert_eq!(hash.extra, "tomesAndTree");
+    assert_eq!(hash.encode().unwrap(), V7);
+}
+
+#[test]
+fn encode_errors() {
+    let mut hash = WynnBuilderHash::default();
+    hash.skill_points.0[0] = 5000;
+    assert!(matches!(
+        hash.encode(),
+        Err(WynnBuilderError::ValueOutOfRange {
+            field: "skill points",
+            value: 5000
+        })
+    ));
+
+    let mut hash = WynnBuilderHash::default();
+    hash.items[0] = HashItem::Crafted(CraftHash {
+        attack_speed: AttackSpeed::SuperFast,
+        ..craft()
+    });
+    assert!(matches!(
+        hash.encode(),
+        Err(WynnBuilderError::ValueOutOfRange {
+            field: "attack speed",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn decode_errors() {
+    assert!(matches!(
+        WynnBuilderHash::decode("abc"),
+        Err(WynnBuilderError::InvalidVersion(_))
+    ));
+    assert!(matches!(
+        WynnBuilderHash::decode("9_000"),
+        Err(WynnBuilderError::UnsupportedVersion(9))
+    ));
+    assert!(matches!(
+        WynnBuilderHash::decode(&V5[..20]),
+        Err(WynnBuilderError::UnexpectedEnd)
+    ));
+    assert!(matches!(
+        WynnBuilderHash::decode("5_001CI-"),
+        Err(WynnBuilderError::InvalidCustom(_))
+    ));
+    assert!(matches!(
+        WynnBuilderHash::decode("5_0I!"),
+        Err(WynnBuilderError::InvalidCharacter('!'))
+    ));
+}
+
+#[cfg(feature = "database")]
+mod database {
+    use idmangler_lib::{
+        block::{DamageData, DefenseData, RequirementsData},
+        build::Slot,
+        craft::{CraftError, IngredientDatabase},
+        database::ItemDatabase,
+        item::{CraftedGear, GearItem},
+        types::{
+            AttackSpeed, ClassType, ConsumableType, CraftedGearType, CraftedStat, Element, IdKeys,
+            SkillType,
+        },
+        wynnbuilder::{HashItem, ItemIds, WynnBuilderError, WynnBuilderHash},
+    };
+
+    use super::{powders, spear, V5, V5_CUSTOM};
+
+    fn keys() -> IdKeys {
+        IdKeys::from_json(include_str!("data/id_keys.json")).unwrap()
+    }
+
+    fn database() -> ItemDatabase {
+        ItemDatabase::from_json(include_str!("data/items.json"), keys()).unwrap()
+    }
+
+    fn ingredients() -> IngredientDatabase {
+        IngredientDatabase::from_json(include_str!("data/items.json"), keys()).unwrap()
+    }
+
+    fn ids() -> ItemIds {
+        let mut ids = ItemIds::from_json(
+            r#"{"items": [
+                {"name": "Ghostly Cap", "id": 1200, "tier": "Rare"},
+                {"name": "Breezehands", "id": 300},
+                {"name": "Warp", "id": 3488}
+            ]}"#,
+        )
+        .unwrap();
+
+        ids.add_ingredients_json(
+            r#"{"ingredients": [{"name": "Corkian Insulator", "id": 1053, "tier": 3}]}"#,
+        )
+        .unwrap();
+        ids.add_recipes_json(
+            r#"{"recipes": [
+                {
+                    "type": "Helmet",
+                    "skill": "ARMOURING",
+                    "materials": [{"item": "Refined Iron Ingot", "amount": 1}, {"item": "Refined Paper", "amount": 2}],
+                    "healthOrDamage": {"minimum": 100, "maximum": 120},
+                    "lvl": {"minimum": 103, "maximum": 105},
+                    "durability": {"minimum": 150, "maximum": 182},
+                    "name": "Helmet-103-105",
+                    "id": 1
+                },
+                {
+                    "type": "Potion",
+                    "skill": "ALCHEMISM",
+                    "lvl": {"minimum": 103, "maximum": 105},
+                    "name": "Potion-103-105",
+                    "id": 2
+                }
+            ]}"#,
+        )
+        .unwrap();
+
+        ids
+    }
+
+    #[test]
+    fn to_items() {
+        let db = database();
+        let items = WynnBuilderHash::decode(V5)
+            .unwrap()
+            .to_items(&ids(), &db, &ingredients())
+            .unwrap();
+
+        let slots: Vec<_> = items.iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, [Slot::Helmet, Slot::Bracelet, Slot::Weapon]);
+
+        let warp = GearItem::try_from(items[2].1.clone()).unwrap();
+        assert_eq!(warp.name, "Warp");
+        let powder_data = warp.powders.unwrap();
+        assert_eq!(powder_data.powder_slots, 3);
+        assert_eq!(powder_data.powders, powders(Element::Air, 3));
+
+        let hash = WynnBuilderHash::from_items(items.iter().map(|(s, i)| (*s, i)), &ids()).unwrap();
+        assert_eq!(hash.items, WynnBuilderHash::decode(V5).unwrap().items);
+        assert_eq!(hash.powders, WynnBuilderHash::decode(V5).unwrap().powders);
+    }
+
+    #[test]
+    fn to_build() {
+        let build = WynnBuilderHash::decode(V5)
+            .unwrap()
+            .to_build(&ids(), &database(), &ingredients())
+            .unwrap();
+
+        assert_eq!(build.len(), 3);
+        assert_eq!(build.get(Slot::Weapon).unwrap().name, "Warp");
+        assert_eq!(build.get(Slot::Helmet).unwrap().name, "Ghostly Cap");
+    }
+
+    #[test]
+    fn recipes() {
+        let ids = ids();
+
+        let helmet = ids.recipe(1).unwrap();
+        assert_eq!(helmet.gear_type, CraftedGearType::Helmet);
+        assert_eq!((helmet.min_level, helmet.max_level), (103, 105));
+        assert_eq!(helmet.material_amounts, [1, 2]);
+        // consumables can not be crafted
+        assert!(ids.recipe(2).is_none());
+        assert_eq!(ids.ingredient(1053), Some("Corkian Insulator"));
+    }
+
+    #[test]
+    fn crafted_and_custom_items() {
+        let items = WynnBuilderHash::decode(V5_CUSTOM)
+            .unwrap()
+            .to_items(&ids(), &database(), &ingredients())
+            .unwrap();
+
+        let slots: Vec<_> = items.iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, [Slot::Helmet, Slot::Ring1, Slot::Weapon]);
+
+        let helmet = CraftedGear::try_from(items[0].1.clone()).unwrap();
+        assert_eq!(helmet.gear_type().0, CraftedGearType::Helmet);
+        assert_eq!(helmet.requirements().level, 105);
+        // (1.4 * 1 + 1.25 * 2) / 3 times the health of the recipe
+        assert_eq!(helmet.defense().unwrap().health, 156);
+        assert_eq!(
+            helmet.identifications().unwrap().idents,
+            [CraftedStat { kind: 2, max: 160 }]
+        );
+        assert_eq!(
+            helmet.powders().unwrap().powders,
+            powders(Element::Water, 2)
+        );
+
+        let ring = CraftedGear::try_from(items[1].1.clone()).unwrap();
+        assert_eq!(ring.name(), Some("Fixed Ring"));
+        assert_eq!(ring.gear_type().0, CraftedGearType::Ring);
+        assert_eq!(
+            ring.defense(),
+            Some(&DefenseData {
+                health: -100,
+                defences: vec![(Element::Fire, 20)],
+            })
+        );
+        assert_eq!(
+            ring.identifications().unwrap().idents,
+            [CraftedStat { kind: 18, max: 4 }]
+        );
+        assert_eq!(ring.effectiveness(), 100);
+
+        let weapon = CraftedGear::try_from(items[2].1.clone()).unwrap();
+        assert_eq!(weapon.name(), Some("Test Spear"));
+        assert_eq!(weapon.gear_type().0, CraftedGearType::Spear);
+        assert_eq!(
+            weapon.requirements(),
+            &RequirementsData {
+                level: 100,
+                class: Some(ClassType::Warrior),
+                skills: vec![(SkillType::Strength, 50)],
+            }
+        );
+        assert_eq!(
+            weapon.damage(),
+            Some(&DamageData {
+                attack_speed: AttackSpeed::Normal,
+                damages: vec![(None, 20..40), (Some(Element::Earth), 10..30)],
+            })
+        );
+        assert_eq!(
+            weapon.identifications().unwrap().idents,
+            [
+                CraftedStat { kind: 2, max: 300 },
+                CraftedStat { kind: 4, max: 100 },
+                CraftedStat { kind: 17, max: 5 },
+                CraftedStat { kind: 34, max: -5 },
+                CraftedStat { kind: 46, max: 5 },
+            ]
+        );
+        let powder_data = weapon.powders().unwrap();
+        assert_eq!(powder_data.powder_slots, 2);
+        assert_eq!(powder_data.powders, powders(Element::Earth, 1));
+
+        assert_eq!(
+            spear().to_item(&database()).unwrap(),
+            CraftedGear::try_from({
+                let mut item = items[2].1.clone();
+                item.powders = None;
+                item
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn unsupported_items() {
+        let mut hash = WynnBuilderHash::decode(V5_CUSTOM).unwrap();
+        let HashItem::Crafted(craft) = &mut hash.items[0] else {
+            panic!("expected a crafted item")
+        };
+        craft.ingredients[1] = 7;
+        assert!(matches!(
+            hash.to_items(&ids(), &database(), &ingredients()),
+            Err(WynnBuilderError::UnknownIngredientId(7))
+        ));
+
+        let HashItem::Crafted(craft) = &mut hash.items[0] else {
+            unreachable!()
+        };
+        craft.recipe = 2;
+        assert!(matches!(
+            hash.to_items(&ids(), &database(), &ingredients()),
+            Err(WynnBuilderError::Craft(CraftError::ConsumableRecipe(
+                ConsumableType::Potion
+            )))
+        ));
+
+        let HashItem::Crafted(craft) = &mut hash.items[0] else {
+            unreachable!()
+        };
+        craft.recipe = 3;
+        assert!(matches!(
+            hash.to_items(&ids(), &database(), &ingredients()),
+            Err(WynnBuilderError::UnknownRecipeId(3))
+        ));
+
+        let mut hash = WynnBuilderHash::default();
+        hash.items[0] = HashItem::Item(1);
+        assert!(matches!(
+            hash.to_items(&ids(), &database(), &ingredients()),
+            Err(WynnBuilderError::UnknownItemId(1))
+        ));
+
+        let items = WynnBuilderHash::decode(V5_CUSTOM)
+            .unwrap()
+            .to_items(&ids(), &database(), &ingredients())
+            .unwrap();
+        assert!(matches!(
+            WynnBuilderHash::from_items(items.iter().map(|(s, i)| (*s, i)), &ids()),
+            Err(WynnBuilderError::UnsupportedItem(Slot::Helmet))
+        ));
+    }
+}